@@ -0,0 +1,238 @@
+use alloy::network::{Ethereum, EthereumWallet, NetworkWallet, TransactionBuilder};
+use alloy::primitives::{Address, U256};
+use alloy::providers::fillers::{FillerControlFlow, TxFiller};
+use alloy::providers::{Provider, SendableTx};
+use alloy::rpc::types::TransactionRequest;
+use alloy::transports::{RpcError, TransportResult};
+use anyhow::{anyhow, Result};
+use std::sync::{Arc, RwLock};
+
+/// An entry of the "Select account" list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountChoice {
+    /// The `private_key` account, signed locally
+    PrivateKey(Address),
+    /// An account from `eth_accounts`, unlocked on the node
+    Node(Address),
+    /// Ask for any address and impersonate it
+    Impersonate,
+}
+
+/// Address the node signs for, shared between the app and the provider's `SignerFiller`.
+/// None while transactions are signed with the private key.
+#[derive(Debug, Clone, Default)]
+pub struct NodeSender(Arc<RwLock<Option<Address>>>);
+
+impl NodeSender {
+    pub fn get(&self) -> Option<Address> {
+        *self.0.read().expect("node sender lock")
+    }
+
+    pub fn set(&self, address: Option<Address>) {
+        *self.0.write().expect("node sender lock") = address;
+    }
+}
+
+/// Signs transactions from the private key locally, like alloy's `WalletFiller`, and
+/// leaves those from the node sender unsigned so the provider sends them with
+/// `eth_sendTransaction` for the node to sign
+#[derive(Debug, Clone)]
+pub struct SignerFiller {
+    wallet: EthereumWallet,
+    node_sender: NodeSender,
+}
+
+impl SignerFiller {
+    pub fn new(wallet: EthereumWallet, node_sender: NodeSender) -> Self {
+        Self {
+            wallet,
+            node_sender,
+        }
+    }
+
+    fn default_from(&self) -> Address {
+        self.node_sender
+            .get()
+            .unwrap_or_else(|| NetworkWallet::<Ethereum>::default_signer_address(&self.wallet))
+    }
+
+    fn signed_by_node(&self, tx: &TransactionRequest) -> bool {
+        tx.from.is_some() && tx.from == self.node_sender.get()
+    }
+}
+
+impl TxFiller<Ethereum> for SignerFiller {
+    type Fillable = ();
+
+    fn status(&self, tx: &TransactionRequest) -> FillerControlFlow {
+        if tx.from.is_none() {
+            return FillerControlFlow::Ready;
+        }
+        if self.signed_by_node(tx) {
+            return FillerControlFlow::Finished;
+        }
+        match tx.complete_preferred() {
+            Ok(_) => FillerControlFlow::Ready,
+            Err(e) => FillerControlFlow::Missing(vec![("Wallet", e)]),
+        }
+    }
+
+    fn fill_sync(&self, tx: &mut SendableTx<Ethereum>) {
+        if let Some(builder) = tx.as_mut_builder() {
+            if builder.from.is_none() {
+                builder.set_from(self.default_from());
+            }
+        }
+    }
+
+    async fn prepare<P: Provider<Ethereum>>(
+        &self,
+        _provider: &P,
+        _tx: &TransactionRequest,
+    ) -> TransportResult<Self::Fillable> {
+        Ok(())
+    }
+
+    async fn fill(
+        &self,
+        _fillable: Self::Fillable,
+        tx: SendableTx<Ethereum>,
+    ) -> TransportResult<SendableTx<Ethereum>> {
+        let builder = match tx {
+            SendableTx::Builder(builder) if !self.signed_by_node(&builder) => builder,
+            _ => return Ok(tx),
+        };
+        let envelope = builder
+            .build(&self.wallet)
+            .await
+            .map_err(RpcError::local_usage)?;
+        Ok(SendableTx::Envelope(envelope))
+    }
+
+    async fn prepare_call(&self, tx: &mut TransactionRequest) -> TransportResult<()> {
+        self.prepare_call_sync(tx)
+    }
+
+    fn prepare_call_sync(&self, tx: &mut TransactionRequest) -> TransportResult<()> {
+        if tx.from.is_none() {
+            tx.set_from(self.default_from());
+        }
+        Ok(())
+    }
+}
+
+/// The private key's account, the node's unlocked accounts and the impersonation entry,
+/// with the balance of each account. A node without `eth_accounts` lists no accounts of
+/// its own.
+pub async fn choices<P: Provider>(
+    provider: &P,
+    key_address: Address,
+) -> Vec<(AccountChoice, Option<U256>)> {
+    let mut choices = vec![AccountChoice::PrivateKey(key_address)];
+    match provider.get_accounts().await {
+        Ok(accounts) => choices.extend(
+            accounts
+                .into_iter()
+                .filter(|address| *address != key_address)
+                .map(AccountChoice::Node),
+        ),
+        Err(e) => log::info!("[ACCOUNTS] eth_accounts unavailable: {e}"),
+    }
+
+    let mut listed = Vec::new();
+    for choice in choices {
+        let balance = match choice {
+            AccountChoice::PrivateKey(address) | AccountChoice::Node(address) => {
+                provider.get_balance(address).await.ok()
+            }
+            AccountChoice::Impersonate => None,
+        };
+        listed.push((choice, balance));
+    }
+    listed.push((AccountChoice::Impersonate, None));
+    listed
+}
+
+/// Let the node sign for `address` without its key, through the dev node's impersonation RPC
+pub async fn impersonate<P: Provider>(provider: &P, address: Address) -> Result<()> {
+    let mut errors = Vec::new();
+    for method in ["anvil_impersonateAccount", "hardhat_impersonateAccount"] {
+        match provider
+            .raw_request::<_, serde_json::Value>(method.into(), (address,))
+            .await
+        {
+            Ok(_) => return Ok(()),
+            Err(e) => errors.push(format!("{method}: {e}")),
+        }
+    }
+    Err(anyhow!(
+        "The node cannot impersonate accounts (only dev nodes such as anvil can)\n{}",
+        errors.join("\n")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc_log::{RecordingLayer, RpcLog};
+    use alloy::primitives::B256;
+    use alloy::providers::ProviderBuilder;
+    use alloy::rpc::client::ClientBuilder;
+    use alloy::signers::local::PrivateKeySigner;
+    use alloy::transports::mock::{Asserter, MockTransport};
+
+    /// Send a fully filled transaction from `from` and return the RPC method it went out with
+    async fn send_method(
+        from: Address,
+        node_sender: NodeSender,
+        signer: PrivateKeySigner,
+    ) -> String {
+        let log = RpcLog::default();
+        let asserter = Asserter::new();
+        asserter.push_success(&format!("0x{}", "11".repeat(32)));
+        let client = ClientBuilder::default()
+            .layer(RecordingLayer::new(log.clone()))
+            .transport(MockTransport::new(asserter), true);
+        let provider = ProviderBuilder::new()
+            .filler(SignerFiller::new(EthereumWallet::from(signer), node_sender))
+            .connect_client(client);
+
+        let tx = TransactionRequest::default()
+            .from(from)
+            .to(Address::repeat_byte(0x22))
+            .nonce(0)
+            .gas_limit(21_000)
+            .max_fee_per_gas(2_000_000_000)
+            .max_priority_fee_per_gas(1_000_000_000)
+            .with_chain_id(31337);
+        let pending = provider.send_transaction(tx).await.unwrap();
+        assert_eq!(*pending.tx_hash(), B256::repeat_byte(0x11));
+
+        let exchanges = log.snapshot("");
+        assert_eq!(exchanges.len(), 1);
+        exchanges[0].method.clone()
+    }
+
+    #[tokio::test]
+    async fn test_signer_filler() {
+        let signer = PrivateKeySigner::random();
+        let node_account = Address::repeat_byte(0x33);
+        let node_sender = NodeSender::default();
+
+        assert_eq!(
+            send_method(signer.address(), node_sender.clone(), signer.clone()).await,
+            "eth_sendRawTransaction"
+        );
+
+        node_sender.set(Some(node_account));
+        assert_eq!(
+            send_method(node_account, node_sender.clone(), signer.clone()).await,
+            "eth_sendTransaction"
+        );
+        // The private key still signs its own transactions
+        assert_eq!(
+            send_method(signer.address(), node_sender, signer).await,
+            "eth_sendRawTransaction"
+        );
+    }
+}
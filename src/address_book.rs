@@ -0,0 +1,330 @@
+use alloy::json_abi::JsonAbi;
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::store::DeploymentRecord;
+
+/// Optional outputs written next to the per-chain JSON files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExportFormats {
+    /// `addresses.ts` with the addresses of every chain and the ABIs
+    pub typescript: bool,
+    /// `<chainId>.env` with one `<CONTRACT>_ADDRESS` line per contract
+    pub env: bool,
+}
+
+impl ExportFormats {
+    pub const ALL: Self = Self {
+        typescript: true,
+        env: true,
+    };
+}
+
+/// A contract with its instances on one chain, oldest first
+#[derive(Debug, Clone)]
+pub struct BookEntry {
+    pub name: String,
+    pub source: PathBuf,
+    pub abi: Option<JsonAbi>,
+    pub deployments: Vec<DeploymentRecord>,
+}
+
+/// Deployments grouped by chain; contracts are keyed by name, or by `<path>:<Name>` when
+/// several sources define a contract with the same name, the path being relative to the
+/// directory those sources share
+#[derive(Debug, Clone, Default)]
+pub struct AddressBook {
+    chains: BTreeMap<u64, BTreeMap<String, BookEntry>>,
+}
+
+impl AddressBook {
+    pub fn new(entries: Vec<(u64, BookEntry)>) -> Self {
+        let mut sources_per_name: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        for (_, entry) in &entries {
+            let sources = sources_per_name.entry(entry.name.clone()).or_default();
+            if !sources.contains(&entry.source) {
+                sources.push(entry.source.clone());
+            }
+        }
+
+        let mut chains: BTreeMap<u64, BTreeMap<String, BookEntry>> = BTreeMap::new();
+        for (chain_id, entry) in entries {
+            let sources = &sources_per_name[&entry.name];
+            let key = if sources.len() > 1 {
+                // Distinct paths stay distinct once their shared directory is stripped
+                let common = common_dir(sources);
+                let relative = entry.source.strip_prefix(&common).unwrap_or(&entry.source);
+                format!("{}:{}", relative.display(), entry.name)
+            } else {
+                entry.name.clone()
+            };
+            chains.entry(chain_id).or_default().insert(key, entry);
+        }
+        Self { chains }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chains.is_empty()
+    }
+
+    /// The address book of one chain: latest address, every address and ABI per contract
+    pub fn chain_json(&self, chain_id: u64) -> Value {
+        let mut contracts = Map::new();
+        for (key, entry) in self.chains.get(&chain_id).into_iter().flatten() {
+            let latest = entry.deployments.last();
+            contracts.insert(
+                key.clone(),
+                json!({
+                    "address": latest.map(|r| r.address),
+                    "deployments": entry.deployments.iter().map(|r| r.address).collect::<Vec<_>>(),
+                    "txHash": latest.and_then(|r| r.tx_hash),
+                    "blockNumber": latest.and_then(|r| r.block_number),
+                    "source": entry.source,
+                    "abi": entry.abi,
+                }),
+            );
+        }
+        json!({ "chainId": chain_id, "contracts": contracts })
+    }
+
+    /// A TypeScript module with `addresses[chainId][Contract]` and `abis[Contract]`. Keys are
+    /// quoted, as `<path>:<Name>` keys are not identifiers.
+    pub fn typescript(&self) -> String {
+        let mut out = String::from("// Generated by evm-cli. Do not edit.\n\n");
+
+        out.push_str("export const addresses = {\n");
+        for (chain_id, contracts) in &self.chains {
+            out.push_str(&format!("  {chain_id}: {{\n"));
+            for (key, entry) in contracts {
+                if let Some(latest) = entry.deployments.last() {
+                    out.push_str(&format!("    {}: \"{:?}\",\n", quoted(key), latest.address));
+                }
+            }
+            out.push_str("  },\n");
+        }
+        out.push_str("} as const;\n\n");
+
+        let mut abis = BTreeMap::new();
+        for contracts in self.chains.values() {
+            for (key, entry) in contracts {
+                if let Some(abi) = &entry.abi {
+                    abis.insert(key, abi);
+                }
+            }
+        }
+        out.push_str("export const abis = {\n");
+        for (key, abi) in abis {
+            let abi = serde_json::to_string(abi).unwrap_or_else(|_| "[]".to_string());
+            out.push_str(&format!("  {}: {abi},\n", quoted(key)));
+        }
+        out.push_str("} as const;\n");
+        out
+    }
+
+    /// `MY_TOKEN_ADDRESS=0x...` lines for one chain
+    pub fn dotenv(&self, chain_id: u64) -> String {
+        let mut out = format!("# Generated by evm-cli for chain {chain_id}\n");
+        for (key, entry) in self.chains.get(&chain_id).into_iter().flatten() {
+            if let Some(latest) = entry.deployments.last() {
+                out.push_str(&format!("{}_ADDRESS={:?}\n", env_name(key), latest.address));
+            }
+        }
+        out
+    }
+
+    /// Write `<chainId>.json` per chain, plus the optional formats, returning the files
+    pub fn write(&self, dir: &Path, formats: ExportFormats) -> Result<Vec<PathBuf>> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+
+        let mut files = Vec::new();
+        let mut write = |name: String, content: String| -> Result<()> {
+            let path = dir.join(name);
+            fs::write(&path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            files.push(path);
+            Ok(())
+        };
+
+        for &chain_id in self.chains.keys() {
+            let json = serde_json::to_string_pretty(&self.chain_json(chain_id))
+                .context("Failed to serialize address book")?;
+            write(format!("{chain_id}.json"), json)?;
+            if formats.env {
+                write(format!("{chain_id}.env"), self.dotenv(chain_id))?;
+            }
+        }
+        if formats.typescript {
+            write("addresses.ts".to_string(), self.typescript())?;
+        }
+        Ok(files)
+    }
+}
+
+/// Directory that every one of `paths` is in
+fn common_dir(paths: &[PathBuf]) -> PathBuf {
+    let mut common = paths
+        .first()
+        .and_then(|path| path.parent())
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    for path in paths {
+        while !path.starts_with(&common) && common.pop() {}
+    }
+    common
+}
+
+/// `key` as a string literal, valid in TypeScript as it is in JSON
+fn quoted(key: &str) -> String {
+    Value::from(key).to_string()
+}
+
+/// `MyToken` -> `MY_TOKEN`, `Token_V2` -> `TOKEN_V2`
+fn env_name(key: &str) -> String {
+    let mut out = String::new();
+    let mut previous: Option<char> = None;
+    for c in key.chars() {
+        if c.is_ascii_uppercase()
+            && previous.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit())
+        {
+            out.push('_');
+        }
+        out.push(if c.is_ascii_alphanumeric() {
+            c.to_ascii_uppercase()
+        } else {
+            '_'
+        });
+        previous = Some(c);
+    }
+    out
+}
+
+/// Default export directory (~/.evm-cli/address-book)
+pub fn default_export_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".evm-cli")
+        .join("address-book")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Address;
+    use tempfile::TempDir;
+
+    fn entry(name: &str, source: &str, addresses: &[u8]) -> BookEntry {
+        BookEntry {
+            name: name.to_string(),
+            source: PathBuf::from(source),
+            abi: Some(JsonAbi::parse(["function mint(address to)"]).unwrap()),
+            deployments: addresses
+                .iter()
+                .map(|&b| DeploymentRecord::new(Address::repeat_byte(b)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_keys_and_json() {
+        let book = AddressBook::new(vec![
+            (31337, entry("MyToken", "/p/src/MyToken.sol", &[0x11, 0x12])),
+            (31337, entry("Vault", "/p/src/Vault.sol", &[0x22])),
+            (31337, entry("Vault", "/p/src/VaultV2.sol", &[0x23])),
+            (1, entry("MyToken", "/p/src/MyToken.sol", &[0x33])),
+        ]);
+
+        let local = book.chain_json(31337);
+        assert_eq!(local["chainId"], 31337);
+        let contracts = local["contracts"].as_object().unwrap();
+        let keys: Vec<_> = contracts.keys().map(String::as_str).collect();
+        assert_eq!(keys, ["MyToken", "Vault.sol:Vault", "VaultV2.sol:Vault"]);
+        // The latest instance is the address, every instance is listed
+        assert_eq!(
+            contracts["MyToken"]["address"],
+            json!(Address::repeat_byte(0x12))
+        );
+        assert_eq!(
+            contracts["MyToken"]["deployments"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(contracts["MyToken"]["abi"][0]["name"], "mint");
+
+        let mainnet = book.chain_json(1);
+        assert_eq!(mainnet["contracts"].as_object().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_keys_of_same_file_names() {
+        let book = AddressBook::new(vec![
+            (31337, entry("Vault", "/p/src/Vault.sol", &[0x22])),
+            (31337, entry("Vault", "/p/lib/v2/src/Vault.sol", &[0x23])),
+            (31337, entry("Vault", "/p/src/1inch/Vault.sol", &[0x24])),
+        ]);
+
+        let local = book.chain_json(31337);
+        let keys: Vec<_> = local["contracts"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                "lib/v2/src/Vault.sol:Vault",
+                "src/1inch/Vault.sol:Vault",
+                "src/Vault.sol:Vault"
+            ]
+        );
+        let ts = book.typescript();
+        assert!(ts.contains(
+            "    \"src/1inch/Vault.sol:Vault\": \"0x2424242424242424242424242424242424242424\","
+        ));
+        assert!(ts.contains("  \"lib/v2/src/Vault.sol:Vault\": [{"));
+    }
+
+    #[test]
+    fn test_typescript_and_env() {
+        let book = AddressBook::new(vec![
+            (31337, entry("MyToken", "/p/MyToken.sol", &[0x11])),
+            (1, entry("MyToken", "/p/MyToken.sol", &[0x33])),
+        ]);
+
+        let ts = book.typescript();
+        assert!(ts.contains(
+            "  1: {\n    \"MyToken\": \"0x3333333333333333333333333333333333333333\",\n  },"
+        ));
+        assert!(ts.contains(
+            "  31337: {\n    \"MyToken\": \"0x1111111111111111111111111111111111111111\","
+        ));
+        assert!(ts.contains("export const abis = {\n  \"MyToken\": [{\"type\":\"function\""));
+
+        assert_eq!(
+            book.dotenv(31337),
+            "# Generated by evm-cli for chain 31337\nMY_TOKEN_ADDRESS=0x1111111111111111111111111111111111111111\n"
+        );
+        assert_eq!(env_name("Token_V2"), "TOKEN_V2");
+        assert_eq!(env_name("ERC20Mock"), "ERC20_MOCK");
+    }
+
+    #[test]
+    fn test_write_formats() {
+        let dir = TempDir::new().unwrap();
+        let book = AddressBook::new(vec![(31337, entry("MyToken", "/p/MyToken.sol", &[0x11]))]);
+
+        let files = book.write(dir.path(), ExportFormats::default()).unwrap();
+        assert_eq!(files, [dir.path().join("31337.json")]);
+
+        let files = book.write(dir.path(), ExportFormats::ALL).unwrap();
+        assert_eq!(files.len(), 3);
+        assert!(dir.path().join("31337.env").is_file());
+        assert!(dir.path().join("addresses.ts").is_file());
+    }
+}
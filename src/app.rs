@@ -1,9 +1,10 @@
 use alloy::dyn_abi::{DynSolType, DynSolValue, EventExt, FunctionExt, JsonAbiExt};
-use alloy::json_abi::{Function, JsonAbi};
-use alloy::network::TransactionBuilder;
-use alloy::primitives::{Address, U256};
+use alloy::json_abi::{Function, JsonAbi, Param, StateMutability};
+use alloy::network::{EthereumWallet, TransactionBuilder};
+use alloy::primitives::{Address, Bytes, TxHash, B256, U256};
 use alloy::providers::Provider;
-use alloy::rpc::types::TransactionRequest;
+use alloy::rpc::types::{BlockNumberOrTag, TransactionReceipt, TransactionRequest};
+use alloy::signers::local::PrivateKeySigner;
 use anyhow::{Context, Result};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::Frame;
@@ -12,26 +13,244 @@ use separator::Separatable;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
-use crate::compile::{BytecodeTarget, CompiledContract};
-
-type AbiCache = RefCell<HashMap<PathBuf, Vec<(String, Arc<JsonAbi>)>>>;
+use crate::accounts::{self, AccountChoice, NodeSender};
+use crate::address_book::{self, AddressBook, BookEntry, ExportFormats};
+use crate::attach;
+use crate::block_watcher::{self, BlockEvent};
+use crate::cards::TransactionStatus;
+use crate::broadcast::{self, Broadcast};
+use crate::call_options::{CallOptions, CALL_OPTION_FIELDS};
+use crate::compile::{BuildError, BytecodeTarget, CompiledContract, CompilerSettings, LoadedAbis, RuntimeCode};
+use crate::diagnostics::{self, Diagnostic, Severity};
+use crate::create2;
+use crate::dashboard::{self, Dashboard};
+use crate::manifest::{self, Manifest};
+use crate::method_list;
+use crate::multicall::{self, Multicall3Setup};
+use crate::pending_tx::{self, Fees, ReceiptPolicy, SentTx, Watched};
+use crate::provider::Endpoints;
+use crate::proxy::{self, ProxyCode, ProxyInfo, ProxyKind};
+use crate::raw_tx::{self, SignedTx};
+use crate::signing::{self, Payload, SignTool};
+use crate::rpc_log::{self, RpcLog};
+use crate::size_report::{self, SizeReport};
+use crate::verify::{self, VerifyOutcome};
+
+type AbiCache = RefCell<HashMap<PathBuf, CachedAbis>>;
+
+/// ABIs of the contracts in a source file, as kept by `load_contract_abi_cached`
+#[derive(Clone)]
+enum CachedAbis {
+    /// Being compiled in the background
+    Loading,
+    /// Compilation failed; tried again once the entry is dropped
+    Failed,
+    Loaded(Vec<(String, Arc<JsonAbi>)>),
+}
 
 use crate::prompts;
-use crate::store::{ContractId, DeploymentStore};
+use crate::store::{AttachedContract, ContractId, DeploymentRecord, DeploymentStore, NodeAccount};
 use crate::tui::layout::AppLayout;
 use crate::tui::state::{
-    AppState, ConnectionStatus, FieldState, Focus, OutputStyle, PopupState,
+    AddressInputKind, AppState, ConnectionStatus, FieldState, FilePickerKind, Focus, OutputStyle, PopupState,
+    RpcInspectorState,
 };
 use crate::tui::widgets::{
-    AutocompleteInput, CommandPalette, ContractTree, DebugBarWidget, OutputArea, ParameterPopup,
-    StatusBarWidget, parse_path_for_autocomplete, scan_path_suggestions,
+    AutocompleteInput, CommandPalette, ContractTree, CompilerSettingsPopup, DashboardPanel, DebugBarWidget, DiagnosticsPanel, OutputArea,
+    ParameterPopup, RpcInspector, StatusBarWidget, parse_path_for_autocomplete, scan_path_suggestions,
 };
+use crate::tui::widgets::rpc_inspector;
 use crate::tui::widgets::command_palette::default_commands;
 use crate::tui::widgets::contract_tree::TreeNode;
 use crate::tui::InputEvent;
 
+/// Result of a compilation running on a blocking worker thread,
+/// together with what to do once it's done
+enum CompileOutcome {
+    /// ABIs for a file opened through the file picker (`open`), or for the sidebar cache
+    Load {
+        label: String,
+        path: PathBuf,
+        result: Result<LoadedAbis>,
+        open: bool,
+    },
+    /// Bytecode for a deployment waiting to be sent
+    Deploy {
+        label: String,
+        contract_name: String,
+        contract_path: PathBuf,
+        args: Vec<DynSolValue>,
+        target: BytecodeTarget,
+        settings: CompilerSettings,
+        plan: DeployPlan,
+        result: Box<Result<CompiledContract>>,
+        /// Proxy contracts, compiled alongside for `DeployPlan::Proxy`
+        proxy_code: Option<Box<Result<ProxyCode>>>,
+    },
+    /// Every contract of a manifest, compiled before any transaction is sent
+    Manifest {
+        label: String,
+        path: PathBuf,
+        manifest: Box<Manifest>,
+        settings: Vec<CompilerSettings>,
+        compiled: Vec<Result<CompiledContract>>,
+    },
+    /// Sizes of a contract built for both targets
+    SizeReport { label: String, report: SizeReport },
+    /// Runtime bytecode to compare with the code deployed at `address`
+    Verify {
+        label: String,
+        address: Address,
+        contract_name: String,
+        target: BytecodeTarget,
+        onchain: Bytes,
+        result: Result<RuntimeCode>,
+    },
+}
+
+/// Progress of a transaction sent by `submit_transaction`, usually on a background task
+enum TxUpdate {
+    /// Accepted by the node, waiting for the receipt. `sent` is what it takes to replace
+    /// it, if the node returned the transaction.
+    Sent {
+        id: u64,
+        hash: TxHash,
+        sent: Option<SentTx>,
+    },
+    /// A speed-up or cancellation with the same nonce was accepted
+    Replaced {
+        id: u64,
+        hash: TxHash,
+        fees: Fees,
+        cancel: bool,
+    },
+    ReplaceFailed {
+        id: u64,
+        cancel: bool,
+        error: String,
+    },
+    Confirmed {
+        id: u64,
+        receipt: Box<TransactionReceipt>,
+        /// Replayed revert reason when the receipt has a failed status
+        revert_reason: Option<String>,
+    },
+    /// Rejected when sending (`hash` is None) or no receipt arrived
+    Failed {
+        id: u64,
+        hash: Option<TxHash>,
+        error: String,
+    },
+    /// A transaction of a background flow, to track before it is sent. `done` is told
+    /// how it ended once its receipt has been handled.
+    Tracked {
+        id: u64,
+        request: Box<TransactionRequest>,
+        purpose: Box<TxPurpose>,
+        watched: Watched,
+        done: tokio::sync::oneshot::Sender<TxResult>,
+    },
+    /// A background flow ended, with a summary or why it stopped
+    FlowDone {
+        title: String,
+        result: std::result::Result<String, String>,
+    },
+    /// The slots of `proxy` as read back after an upgrade
+    ProxyUpgraded { proxy: Address, info: ProxyInfo },
+    /// A manifest step deployed (or found deployed before), with the number of its calls
+    /// sent so far
+    ManifestStep {
+        chain_id: u64,
+        manifest: PathBuf,
+        step: String,
+        contract_id: ContractId,
+        address: Address,
+        calls: usize,
+    },
+    /// The CREATE2 factory is in place and the predicted address is free
+    Create2Ready {
+        pending: Box<PendingCreate2>,
+        factory: create2::FactorySetup,
+    },
+}
+
+/// Stored instances of `chain_id` found gone from the node: contract name, address and why
+struct InstanceCheck {
+    chain_id: u64,
+    dead: Vec<(String, Address, &'static str)>,
+}
+
+/// What a transaction in flight was sent for, which decides what happens with its receipt
+enum TxPurpose {
+    Call {
+        address: Address,
+        contract_name: String,
+        title: String,
+        call_str: String,
+    },
+    Deploy {
+        contract_name: String,
+        contract_path: PathBuf,
+        target: BytecodeTarget,
+        settings: CompilerSettings,
+        constructor_args: Vec<String>,
+        role: DeploymentRole,
+    },
+}
+
+impl TxPurpose {
+    /// Sending `function` with `args` to `address`
+    fn call(function: &Function, address: Address, args: &[DynSolValue], contract_name: String) -> Self {
+        TxPurpose::Call {
+            address,
+            contract_name,
+            title: function.name.clone(),
+            call_str: prompts::format_method_call(&function.name, &function.inputs, args),
+        }
+    }
+
+    fn title(&self) -> String {
+        match self {
+            TxPurpose::Call { address, call_str, .. } => format!("{call_str} @ {address:?}"),
+            TxPurpose::Deploy { contract_name, role, .. } => role.card_title(contract_name),
+        }
+    }
+}
+
+/// A transaction waiting for its receipt, listed in the pending transactions popup
+struct InFlight {
+    purpose: TxPurpose,
+    /// The request as first sent, which a speed-up sends again
+    request: TransactionRequest,
+    /// The original hash and its replacements, all polled for a receipt
+    watched: Watched,
+    /// Hash shown on the transaction card: the latest one sent
+    card_hash: Option<TxHash>,
+    /// Nonce, gas limit and the latest fees
+    sent: Option<SentTx>,
+    /// Cancellations sent; one of them being mined means the original never ran
+    cancel_hashes: Vec<TxHash>,
+    sent_at: std::time::Instant,
+    /// Background flow waiting for this transaction before going on
+    done: Option<tokio::sync::oneshot::Sender<TxResult>>,
+}
+
+/// How a transaction ended once its receipt (or error) was handled
+#[derive(Debug, Clone, Copy)]
+enum TxResult {
+    Deployed(Address),
+    Succeeded,
+    Reverted,
+    /// A cancellation was mined in its place
+    Cancelled,
+    /// Not sent or no receipt; the error has been reported
+    NotConfirmed,
+}
+
 #[derive(Clone)]
 enum PendingAction {
     None,
@@ -39,11 +258,101 @@ enum PendingAction {
         contract_name: String,
         contract_path: PathBuf,
         abi: Arc<JsonAbi>,
+        mode: DeployMode,
     },
     CallMethod {
         function: Function,
         address: Address,
     },
+    /// Hand-written calldata and value ("Raw call", fallback() and receive()); the popup
+    /// fields are `RAW_CALL_PARAMS`
+    RawCall,
+}
+
+/// Fields of the raw call popup
+const RAW_CALL_PARAMS: [&str; 4] = ["address to", "bytes data", "uint256 value", "bool send"];
+
+/// Which deploy flow opened the parameter popup
+#[derive(Clone)]
+enum DeployMode {
+    /// "Deploy new instance": constructor parameters and an optional CREATE2 salt
+    Instance,
+    /// "Deploy behind proxy": `initialize` parameters, run through the new proxy
+    Proxy(ProxyKind),
+    /// "Upgrade implementation": constructor parameters of the new implementation, then the
+    /// parameters of `call`, run by the proxy right after the upgrade. `target` is the one
+    /// the proxy runs on, preset in the popup.
+    Upgrade { proxy: Address, info: ProxyInfo, target: BytecodeTarget, call: Option<Function> },
+}
+
+/// How a compiled contract gets on chain
+#[derive(Debug, Clone)]
+enum DeployPlan {
+    Create,
+    /// Through the CREATE2 factory with this salt
+    Create2(B256),
+    /// As the implementation of a new proxy initialized with `init_data`
+    Proxy { kind: ProxyKind, init_data: Bytes },
+    /// As the new implementation of an existing proxy, which then runs `data`
+    Upgrade { proxy: Address, info: ProxyInfo, data: Bytes },
+    /// Sign the plain CREATE transaction without sending it
+    SignOnly,
+}
+
+/// Split the values of a deploy popup into the constructor's and those that follow (the
+/// upgrade call's)
+fn split_constructor_args(abi: &JsonAbi, mut args: Vec<DynSolValue>) -> (Vec<DynSolValue>, Vec<DynSolValue>) {
+    let constructor_len = abi.constructor.as_ref().map_or(0, |ctor| ctor.inputs.len());
+    let rest = args.split_off(constructor_len.min(args.len()));
+    (args, rest)
+}
+
+/// What a deployment transaction creates, which decides its card title and
+/// whether the new address is added to the sidebar
+#[derive(Debug, Clone, Copy)]
+enum DeploymentRole {
+    Instance,
+    /// Through the CREATE2 factory; receipts carry no contract address so the
+    /// predicted one is used
+    Create2(Address),
+    /// Implementation behind a proxy, only reachable through the proxy
+    Implementation,
+    ProxyAdmin,
+    Proxy {
+        kind: ProxyKind,
+        implementation: Address,
+        admin: Option<Address>,
+    },
+}
+
+impl DeploymentRole {
+    fn card_title(&self, contract_name: &str) -> String {
+        match self {
+            DeploymentRole::Instance | DeploymentRole::ProxyAdmin => format!("Deploy {contract_name}"),
+            DeploymentRole::Create2(_) => format!("Deploy {contract_name} (CREATE2)"),
+            DeploymentRole::Implementation => format!("Deploy {contract_name} (implementation)"),
+            DeploymentRole::Proxy { kind, .. } => format!("Deploy {contract_name} ({kind} proxy)"),
+        }
+    }
+}
+
+/// One view call of a batch sent through Multicall3
+struct BatchedCall {
+    function: Function,
+    address: Address,
+    args: Vec<DynSolValue>,
+}
+
+/// A compiled CREATE2 deployment waiting for the user to confirm the predicted address
+struct PendingCreate2 {
+    contract_name: String,
+    contract_path: PathBuf,
+    target: BytecodeTarget,
+    settings: CompilerSettings,
+    salt: B256,
+    predicted: Address,
+    init_code: Vec<u8>,
+    constructor_args: Vec<String>,
 }
 
 pub struct App<P> {
@@ -53,25 +362,97 @@ pub struct App<P> {
     pub contract: Option<CompiledContract>,
     pub contract_path: Option<PathBuf>,
     pub address: Option<Address>,
+    /// Account transactions are sent from: the private key's or `store.config.node_account`
     pub signer_address: Address,
+    /// Account of the private key
+    key_address: Address,
+    /// The private key, for signing transactions without sending them and messages
+    signer: PrivateKeySigner,
+    /// Nonces of the transactions signed without sending them
+    signed_nonces: raw_tx::SignedNonces,
+    /// Tells the provider which sender the node signs for
+    node_sender: NodeSender,
     running: bool,
     pending_action: PendingAction,
     edit_config_requested: bool,
+    /// Open the "Attach by signature" editor; handled by the main loop
+    attach_requested: bool,
+    /// Editor text of the last attach that failed to parse, offered again on the next attempt
+    attach_draft: Option<String>,
+    /// Open the "Broadcast raw tx" editor; handled by the main loop like `attach_requested`
+    broadcast_requested: bool,
+    /// Editor text of the last raw transaction that failed to decode
+    broadcast_draft: Option<String>,
+    /// Open a signing tool's editor on this text; handled by the main loop
+    sign_requested: Option<(SignTool, String)>,
+    /// Editor text of the last signing attempt that failed to parse, offered again by its tool
+    sign_draft: Option<(SignTool, String)>,
     /// Content to display in external editor. Set this field and the main loop
     /// will handle terminal restore, editor launch, and terminal re-setup.
     pending_editor_content: Option<String>,
+    /// Source location (file, line, column) to open in the external editor.
+    /// Handled by the main loop like `pending_editor_content`.
+    pending_editor_location: Option<(PathBuf, usize, usize)>,
     /// Cache of loaded ABIs to avoid re-parsing files on every render
     abi_cache: AbiCache,
+    /// Channel on which background compilations report back to the main loop
+    compile_tx: std::sync::mpsc::Sender<CompileOutcome>,
+    compile_rx: std::sync::mpsc::Receiver<CompileOutcome>,
+    /// Transactions sent in the background report back here; `in_flight` holds what
+    /// each one is for until its receipt arrives
+    transaction_tx: std::sync::mpsc::Sender<TxUpdate>,
+    transaction_rx: std::sync::mpsc::Receiver<TxUpdate>,
+    /// Dashboard refreshes running in the background report here
+    dashboard_tx: std::sync::mpsc::Sender<dashboard::Update>,
+    dashboard_rx: std::sync::mpsc::Receiver<dashboard::Update>,
+    /// Dead instance checks started on connect report here
+    instances_tx: std::sync::mpsc::Sender<InstanceCheck>,
+    instances_rx: std::sync::mpsc::Receiver<InstanceCheck>,
+    in_flight: HashMap<u64, InFlight>,
+    /// Shared with background flows, which pick ids for the transactions they send
+    next_transaction_id: Arc<AtomicU64>,
+    /// Endpoints behind `provider`; `active_endpoint` is the one last shown on the connection card
+    endpoints: Endpoints,
+    active_endpoint: usize,
+    /// JSON-RPC traffic of `provider`, browsed in the RPC inspector
+    rpc_log: RpcLog,
+    /// Chain heads from the block watcher, started by `run_interactive`
+    block_rx: Option<std::sync::mpsc::Receiver<BlockEvent>>,
+    /// Last EVM vs PVM size report, for "Export size report"
+    last_size_report: Option<SizeReport>,
+    /// CREATE2 deployment shown in the `ConfirmCreate2` popup
+    pending_create2: Option<PendingCreate2>,
+    /// Whether view calls can be batched through Multicall3, once checked for this
+    /// connection; false while the check runs
+    multicall_ready: Option<bool>,
+    /// The Multicall3 check started by `multicall_ready` reports here, with the chain it ran on
+    multicall_tx: std::sync::mpsc::Sender<(u64, Result<Multicall3Setup>)>,
+    multicall_rx: std::sync::mpsc::Receiver<(u64, Result<Multicall3Setup>)>,
 }
 
-impl<P: Provider + Clone> App<P> {
-    pub fn new(provider: P, store: DeploymentStore, signer_address: Address) -> Self {
+impl<P: Provider + Clone + 'static> App<P> {
+    pub fn new(
+        provider: P,
+        store: DeploymentStore,
+        signer: PrivateKeySigner,
+        endpoints: Endpoints,
+        rpc_log: RpcLog,
+        node_sender: NodeSender,
+    ) -> Self {
+        let signer_address = signer.address();
         let state = AppState {
             account: Some(signer_address),
             ..Default::default()
         };
 
-        Self {
+        let (compile_tx, compile_rx) = std::sync::mpsc::channel();
+        let (transaction_tx, transaction_rx) = std::sync::mpsc::channel();
+        let (dashboard_tx, dashboard_rx) = std::sync::mpsc::channel();
+        let (instances_tx, instances_rx) = std::sync::mpsc::channel();
+        let (multicall_tx, multicall_rx) = std::sync::mpsc::channel();
+
+        let node_account = store.config.node_account;
+        let mut app = Self {
             provider,
             store,
             state,
@@ -79,12 +460,44 @@ impl<P: Provider + Clone> App<P> {
             contract_path: None,
             address: None,
             signer_address,
+            key_address: signer_address,
+            signer,
+            signed_nonces: Default::default(),
+            node_sender,
             running: true,
             pending_action: PendingAction::None,
             edit_config_requested: false,
+            attach_requested: false,
+            attach_draft: None,
+            broadcast_requested: false,
+            broadcast_draft: None,
+            sign_requested: None,
+            sign_draft: None,
             pending_editor_content: None,
+            pending_editor_location: None,
             abi_cache: RefCell::new(HashMap::new()),
-        }
+            compile_tx,
+            compile_rx,
+            transaction_tx,
+            transaction_rx,
+            dashboard_tx,
+            dashboard_rx,
+            instances_tx,
+            instances_rx,
+            in_flight: HashMap::new(),
+            next_transaction_id: Arc::new(AtomicU64::new(0)),
+            active_endpoint: endpoints.active_index(),
+            endpoints,
+            rpc_log,
+            block_rx: None,
+            last_size_report: None,
+            pending_create2: None,
+            multicall_ready: None,
+            multicall_tx,
+            multicall_rx,
+        };
+        app.set_sender(node_account);
+        app
     }
 
     /// Attempt to connect to RPC, returns true if successful.
@@ -95,7 +508,9 @@ impl<P: Provider + Clone> App<P> {
             Ok(chain_id) => {
                 self.state.chain_id = Some(chain_id);
                 self.state.connection = ConnectionStatus::Connected;
+                self.multicall_ready = None;
                 self.state.connection_error = None;
+                self.prepare_node_account().await;
                 
                 // Fetch balance
                 if let Ok(balance) = self.provider.get_balance(self.signer_address).await {
@@ -103,6 +518,12 @@ impl<P: Provider + Clone> App<P> {
                 }
                 
                 log::info!("Connected to chain ID: {chain_id}");
+
+                match self.provider.get_block_by_number(BlockNumberOrTag::Earliest).await {
+                    Ok(genesis) => self.state.genesis_hash = genesis.map(|block| block.header.hash),
+                    Err(e) => log::warn!("Failed to fetch the genesis block: {e}"),
+                }
+                self.check_instances(chain_id);
                 
                 // Update connection card if it exists
                 self.update_connection_card();
@@ -118,6 +539,402 @@ impl<P: Provider + Clone> App<P> {
         }
     }
 
+    /// Send from the private key's account (None) or let the node sign for `node_account`
+    fn set_sender(&mut self, node_account: Option<NodeAccount>) {
+        let address = node_account.map_or(self.key_address, |account| account.address);
+        self.node_sender.set(node_account.map(|account| account.address));
+        self.signer_address = address;
+        self.state.account = Some(address);
+        self.state.node_account = node_account;
+    }
+
+    /// Make sure the node signs for the configured node account: impersonate it again (a
+    /// restarted dev node forgets) or check that it is still unlocked
+    async fn prepare_node_account(&mut self) {
+        let Some(account) = self.store.config.node_account else {
+            return;
+        };
+        if account.impersonated {
+            if let Err(e) = accounts::impersonate(&self.provider, account.address).await {
+                self.add_log_card(format!(
+                    "Failed: Impersonate {:?}\n\n{e:#}\n\nCtrl+P > Select account to send from another account",
+                    account.address
+                ));
+            }
+            return;
+        }
+        match self.provider.get_accounts().await {
+            Ok(unlocked) if !unlocked.contains(&account.address) => {
+                self.add_log_card(format!(
+                    "{:?} is not unlocked on the node; its transactions will be rejected\n\nCtrl+P > Select account to send from another account",
+                    account.address
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("[ACCOUNTS] eth_accounts failed: {e}"),
+        }
+    }
+
+    /// Switch the account transactions are sent from and remember it in the config
+    async fn use_account(&mut self, node_account: Option<NodeAccount>) {
+        if let Some(NodeAccount { address, impersonated: true }) = node_account {
+            if let Err(e) = accounts::impersonate(&self.provider, address).await {
+                self.add_log_card(format!("Failed: Impersonate {address:?}\n\n{e:#}"));
+                return;
+            }
+        }
+        self.set_sender(node_account);
+        self.store.config.node_account = node_account;
+        if let Err(e) = self.store.save() {
+            self.state.output.push_error(format!("Failed to save account: {e}"));
+        }
+        let signer = match node_account {
+            Some(account) if account.impersonated => "impersonated, signed by the node",
+            Some(_) => "unlocked on the node",
+            None => "signed with the private key",
+        };
+        self.add_log_card(format!("Sending from {:?} ({signer})", self.signer_address));
+        self.state.last_action = Some("Select account".into());
+        self.refresh_balance().await;
+    }
+
+    /// List the accounts to send from
+    async fn open_account_selector(&mut self) {
+        let accounts = accounts::choices(&self.provider, self.key_address).await;
+        let selected = accounts
+            .iter()
+            .position(|(choice, _)| match choice {
+                AccountChoice::PrivateKey(address) | AccountChoice::Node(address) => *address == self.signer_address,
+                AccountChoice::Impersonate => false,
+            })
+            .unwrap_or(accounts.len() - 1);
+        self.state.popup = PopupState::AccountSelector { accounts, selected };
+        self.state.focus = Focus::CommandPalette;
+    }
+
+    /// Follow the chain head in the background: a `newHeads` subscription over WebSocket
+    /// or IPC, polling over HTTP
+    fn start_block_watcher(&mut self) {
+        let (block_tx, block_rx) = std::sync::mpsc::channel();
+        tokio::spawn(block_watcher::watch(self.endpoints.clone(), block_tx));
+        self.block_rx = Some(block_rx);
+    }
+
+    /// Report a failover to another endpoint, which the provider does on its own
+    fn poll_endpoint(&mut self) {
+        let active = self.endpoints.active_index();
+        if active == self.active_endpoint {
+            return;
+        }
+        self.active_endpoint = active;
+        let endpoint = &self.endpoints.active().display;
+        let message = if active == 0 {
+            format!("RPC endpoint back to the primary, {endpoint}")
+        } else {
+            format!(
+                "RPC endpoint switched to fallback {active} of {}, {endpoint}\n\nCtrl+P > Reconnect goes back to the primary",
+                self.endpoints.len() - 1
+            )
+        };
+        self.add_log_card(message);
+        self.update_connection_card();
+    }
+
+    /// Drain block watcher events: show the head, and follow the connection going down
+    /// and coming back (the watcher resubscribes on its own)
+    async fn poll_blocks(&mut self) {
+        let Some(block_rx) = &self.block_rx else {
+            return;
+        };
+        let events: Vec<BlockEvent> = block_rx.try_iter().collect();
+        for event in events {
+            match event {
+                BlockEvent::Head { number, live } => {
+                    let new_block = self.state.block_number != Some(number);
+                    self.state.block_number = Some(number);
+                    self.state.live_blocks = live;
+                    if matches!(self.state.connection, ConnectionStatus::Disconnected) {
+                        self.try_connect().await;
+                    }
+                    if new_block {
+                        self.refresh_dashboard().await;
+                    }
+                }
+                BlockEvent::Lost(reason) => {
+                    self.state.live_blocks = false;
+                    if matches!(self.state.connection, ConnectionStatus::Connected) {
+                        log::warn!("Connection lost: {reason}");
+                        self.state.connection = ConnectionStatus::Disconnected;
+                        self.state.connection_error = Some(reason);
+                        self.update_connection_card();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Look for stored instances that are gone from the connected node: recorded before the
+    /// node was reset, or no code at the address. Instances recorded on this chain are
+    /// checked, and so are those recorded without a chain (loaded by address or from an old
+    /// config), which may be on any chain. The code lookups run in the background and are
+    /// picked up by `poll_instances`.
+    fn check_instances(&mut self, chain_id: u64) {
+        let mut dead = Vec::new();
+        let mut to_fetch = Vec::new();
+        for contract_id in self.store.all_contracts() {
+            for record in self.store.get_deployment_records(&contract_id) {
+                if !record.on_chain(chain_id) && record.chain_id.is_some() {
+                    continue;
+                }
+                let address = record.address;
+                let reset = self
+                    .state
+                    .genesis_hash
+                    .is_some_and(|genesis| record.predates_reset(chain_id, genesis));
+                if reset {
+                    dead.push((contract_id.name.clone(), address, "deployed before the node was reset"));
+                } else {
+                    to_fetch.push((contract_id.name.clone(), address));
+                }
+            }
+        }
+
+        let provider = self.provider.clone();
+        let updates = self.instances_tx.clone();
+        tokio::spawn(async move {
+            for (name, address) in to_fetch {
+                match provider.get_code_at(address).await {
+                    Ok(code) if code.is_empty() => dead.push((name, address, "no code at this address")),
+                    Ok(_) => {}
+                    Err(e) => log::warn!("[INSTANCES] Failed to fetch code at {address:?}: {e}"),
+                }
+            }
+            let _ = updates.send(InstanceCheck { chain_id, dead });
+        });
+    }
+
+    fn poll_instances(&mut self) {
+        let checks: Vec<InstanceCheck> = self.instances_rx.try_iter().collect();
+        for check in checks {
+            self.apply_instance_check(check);
+        }
+    }
+
+    /// Wait for the check started on connect, for headless commands that have no main loop
+    pub async fn wait_for_instance_check(&mut self) {
+        if !matches!(self.state.connection, ConnectionStatus::Connected) {
+            return;
+        }
+        loop {
+            if let Ok(check) = self.instances_rx.try_recv() {
+                self.apply_instance_check(check);
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+    }
+
+    /// Mark the instances found dead by `check_instances`, unless the app has connected to
+    /// another chain since
+    fn apply_instance_check(&mut self, check: InstanceCheck) {
+        let chain_id = check.chain_id;
+        if self.state.chain_id != Some(chain_id) {
+            return;
+        }
+        log::info!("[INSTANCES] {} dead instance(s) on chain {chain_id}", check.dead.len());
+        self.state.sidebar.dead_instances = check.dead.iter().map(|(_, address, _)| (chain_id, *address)).collect();
+        if check.dead.is_empty() {
+            return;
+        }
+        let report: Vec<String> = check
+            .dead
+            .iter()
+            .map(|(name, address, reason)| format!("{name} at {address:?}: {reason}"))
+            .collect();
+        self.add_log_card(format!(
+            "{} stale instance(s) on chain {chain_id}\n\n{}\n\nCtrl+P > Prune dead instances removes them",
+            report.len(),
+            report.join("\n")
+        ));
+    }
+
+    /// Register the contracts created by a `forge script --broadcast` run as instances on the
+    /// run's chain, matching each contract name to a source file in the forge project
+    fn import_broadcast(&mut self, path: &Path) {
+        match self.try_import_broadcast(path) {
+            Ok(summary) => self.add_log_card(summary),
+            Err(e) => self.add_log_card(format!("Failed: Import {}\n\n{e:#}", path.display())),
+        }
+    }
+
+    fn try_import_broadcast(&mut self, path: &Path) -> Result<String> {
+        let broadcast = Broadcast::load(path)?;
+        let root = broadcast::project_root(path)
+            .with_context(|| format!("No foundry.toml found above {}", path.display()))?;
+        let created = broadcast.created();
+        if created.is_empty() {
+            anyhow::bail!("The run did not create any contracts");
+        }
+
+        // The genesis hash is only known for the chain we are connected to
+        let on_connected_chain = self.state.chain_id == Some(broadcast.chain);
+        let genesis_hash = self.state.genesis_hash.filter(|_| on_connected_chain);
+        let deployed_at = broadcast.time();
+
+        let mut imported = Vec::new();
+        let mut skipped = Vec::new();
+        for created in created {
+            let name = created.contract_name;
+            let address = created.address;
+            let source = match broadcast::find_sources(&root, &name).as_slice() {
+                [source] => source.clone(),
+                [] => {
+                    skipped.push(format!("{name} at {address:?}: no source in the project defines it"));
+                    continue;
+                }
+                sources => {
+                    skipped.push(format!("{name} at {address:?}: defined in {} files", sources.len()));
+                    continue;
+                }
+            };
+
+            let contract_id = ContractId::new(source.clone(), name.clone());
+            // What was recorded for this chain stays; records on other chains are separate
+            let recorded = self
+                .store
+                .get_deployment(&contract_id, Some(broadcast.chain), address)
+                .is_some_and(|record| record.chain_id == Some(broadcast.chain));
+            if recorded {
+                skipped.push(format!("{name} at {address:?}: already recorded on chain {}", broadcast.chain));
+                continue;
+            }
+            self.store.record_deployment(
+                &contract_id,
+                DeploymentRecord {
+                    address,
+                    chain_id: Some(broadcast.chain),
+                    genesis_hash,
+                    target: None,
+                    settings: None,
+                    tx_hash: created.tx_hash,
+                    block_number: created.block_number,
+                    deployer: created.deployer,
+                    constructor_args: created.arguments,
+                    deployed_at: deployed_at.clone(),
+                },
+            );
+            self.state.sidebar.dead_instances.remove(&(broadcast.chain, address));
+            self.state.sidebar.expanded_contracts.insert((source.clone(), name.clone()));
+            let relative = source.strip_prefix(&root).unwrap_or(&source);
+            imported.push(format!("{name} at {address:?} ({})", relative.display()));
+        }
+        self.store.save()?;
+        log::info!("[BROADCAST] imported {} and skipped {} from {}", imported.len(), skipped.len(), path.display());
+
+        let mut summary = format!("Imported {} deployment(s) from {} (chain {})", imported.len(), path.display(), broadcast.chain);
+        if !on_connected_chain {
+            summary.push_str("\nNot the connected chain: these instances are only usable on that chain");
+        }
+        if !imported.is_empty() {
+            summary.push_str(&format!("\n\n{}", imported.join("\n")));
+        }
+        if !skipped.is_empty() {
+            summary.push_str(&format!("\n\nSkipped:\n{}", skipped.join("\n")));
+        }
+        Ok(summary)
+    }
+
+    /// Write the address book of every chain with recorded instances to `dir`, returning a
+    /// summary. Instances without a recorded chain, and dead ones, are left out.
+    pub fn export_address_book(&self, dir: &Path, formats: ExportFormats) -> Result<String> {
+        let mut entries = Vec::new();
+        let mut left_out = Vec::new();
+        for contract_id in self.store.all_contracts() {
+            let mut per_chain: HashMap<u64, Vec<DeploymentRecord>> = HashMap::new();
+            for record in self.store.get_deployment_records(&contract_id) {
+                let address = record.address;
+                match record.chain_id {
+                    Some(chain_id) if self.state.sidebar.dead_instances.contains(&(chain_id, address)) => {
+                        left_out.push(format!("{} at {address:?}: dead on the connected node", contract_id.name));
+                    }
+                    Some(chain_id) => per_chain.entry(chain_id).or_default().push(record.clone()),
+                    None => left_out.push(format!("{} at {address:?}: no chain recorded", contract_id.name)),
+                }
+            }
+            if per_chain.is_empty() {
+                continue;
+            }
+
+            let abi = self
+                .load_contract_abi_now(&contract_id.path)
+                .and_then(|abis| abis.into_iter().find(|(name, _)| *name == contract_id.name))
+                .map(|(_, abi)| (*abi).clone());
+            if abi.is_none() {
+                left_out.push(format!("{}: ABI unavailable (exported without it)", contract_id.name));
+            }
+            for (chain_id, deployments) in per_chain {
+                let entry = BookEntry {
+                    name: contract_id.name.clone(),
+                    source: contract_id.path.clone(),
+                    abi: abi.clone(),
+                    deployments,
+                };
+                entries.push((chain_id, entry));
+            }
+        }
+
+        let book = AddressBook::new(entries);
+        if book.is_empty() {
+            anyhow::bail!("No instances with a recorded chain to export");
+        }
+        let files = book.write(dir, formats)?;
+        log::info!("[EXPORT] address book written to {} ({} files)", dir.display(), files.len());
+
+        let mut summary = format!("Address book written to {}\n", dir.display());
+        for file in &files {
+            summary.push_str(&format!("\n{}", file.display()));
+        }
+        if !left_out.is_empty() {
+            summary.push_str(&format!("\n\nLeft out:\n{}", left_out.join("\n")));
+        }
+        Ok(summary)
+    }
+
+    /// Remove the instances marked dead on connect from the store and the sidebar
+    fn prune_dead_instances(&mut self) {
+        if self.state.sidebar.dead_instances.is_empty() {
+            self.state.output.push_info("No dead instances found on the connected node");
+            return;
+        }
+
+        // Only the records of the chain the instances were found dead on, and records
+        // without a chain, which were checked on it
+        let mut pruned = Vec::new();
+        for (chain_id, address) in self.state.sidebar.dead_instances.clone() {
+            for contract_id in self.store.all_contracts() {
+                let dead = self
+                    .store
+                    .get_deployment(&contract_id, Some(chain_id), address)
+                    .is_some();
+                if dead && self.store.remove_deployment(&contract_id, Some(chain_id), address) {
+                    pruned.push(format!("{} at {address:?}", contract_id.name));
+                }
+            }
+        }
+        for (_, address) in std::mem::take(&mut self.state.sidebar.dead_instances) {
+            self.state.sidebar.expanded_instances.remove(&address);
+            if self.address == Some(address) {
+                self.address = None;
+            }
+        }
+        if let Err(e) = self.store.save() {
+            self.state.output.push_error(format!("Failed to save config: {e}"));
+        }
+        let new_count = self.build_tree_nodes().len();
+        self.state.sidebar.selected = self.state.sidebar.selected.min(new_count.saturating_sub(1));
+        self.add_log_card(format!("Pruned {} dead instance(s)\n\n{}", pruned.len(), pruned.join("\n")));
+    }
+
     /// Add the connection card (should be called once at startup)
     pub fn add_connection_card(&mut self) {
         let card = crate::cards::Card::Connection {
@@ -125,6 +942,7 @@ impl<P: Provider + Clone> App<P> {
             account: self.signer_address,
             balance: self.state.balance.clone(),
             chain_id: self.state.chain_id,
+            endpoint: self.endpoint_label(),
             error: self.state.connection_error.clone(),
         };
         self.state.cards.cards.insert(0, card);
@@ -132,16 +950,28 @@ impl<P: Provider + Clone> App<P> {
 
     /// Update the connection card with current state (called after reconnection)
     fn update_connection_card(&mut self) {
-        if let Some(crate::cards::Card::Connection { connected, balance, chain_id, error, .. }) = 
+        let label = self.endpoint_label();
+        if let Some(crate::cards::Card::Connection { connected, account, balance, chain_id, endpoint, error }) = 
             self.state.cards.cards.first_mut() 
         {
+            *account = self.signer_address;
             *connected = matches!(self.state.connection, ConnectionStatus::Connected);
             *balance = self.state.balance.clone();
             *chain_id = self.state.chain_id;
+            *endpoint = label;
             *error = self.state.connection_error.clone();
         }
     }
 
+    /// Active endpoint for the connection card, marked when it is a fallback
+    fn endpoint_label(&self) -> String {
+        let endpoint = &self.endpoints.active().display;
+        match self.endpoints.active_index() {
+            0 => endpoint.clone(),
+            index => format!("{endpoint} (fallback {index} of {})", self.endpoints.len() - 1),
+        }
+    }
+
     pub fn set_contract(&mut self, contract: CompiledContract, path: PathBuf) {
         let contract_name = contract.name.clone();
         self.contract = Some(contract);
@@ -169,7 +999,7 @@ impl<P: Provider + Clone> App<P> {
         let canonical_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
         let nodes = self.build_tree_nodes();
         for (i, node) in nodes.iter().enumerate() {
-            if let TreeNode::Contract { path: node_path, name: node_name } = node {
+            if let TreeNode::Contract { path: node_path, name: node_name, .. } = node {
                 // Match both path AND contract name (node_path is already canonicalized)
                 if *node_path == canonical_path && node_name == contract_name {
                     self.state.sidebar.selected = i;
@@ -187,7 +1017,9 @@ impl<P: Provider + Clone> App<P> {
     fn select_instance_in_sidebar(&mut self, address: Address) {
         let nodes = self.build_tree_nodes();
         for (i, node) in nodes.iter().enumerate() {
-            if let TreeNode::DeployedInstance { address: node_address, .. } = node {
+            if let TreeNode::DeployedInstance { address: node_address, .. }
+            | TreeNode::AttachedInstance { address: node_address, .. } = node
+            {
                 if *node_address == address {
                     self.state.sidebar.selected = i;
                     // Adjust scroll if needed
@@ -230,36 +1062,64 @@ impl<P: Provider + Clone> App<P> {
         self.abi_cache.borrow_mut().clear();
     }
 
-    /// Load contract ABIs with caching
+    /// Load contract ABIs with caching. On a miss the file is compiled in the background
+    /// (`CompileOutcome::Load`) and None is returned until it is done.
     fn load_contract_abi_cached(&self, path: &PathBuf) -> Option<Vec<(String, Arc<JsonAbi>)>> {
-        // Check cache first
-        if let Some(cached) = self.abi_cache.borrow().get(path) {
-            return Some(cached.clone());
+        match self.abi_cache.borrow().get(path) {
+            Some(CachedAbis::Loaded(abis)) => return Some(abis.clone()),
+            Some(CachedAbis::Loading | CachedAbis::Failed) => return None,
+            None => {}
         }
 
-        // Load from disk and cache
-        match crate::compile::load_contract_abi(path) {
-            Ok(contracts) => {
-                let abi_list: Vec<(String, Arc<JsonAbi>)> = contracts
-                    .into_iter()
-                    .map(|(name, abi)| (name, Arc::new(abi)))
-                    .collect();
+        self.abi_cache.borrow_mut().insert(path.clone(), CachedAbis::Loading);
+        let label = format!("Compiling {}", path.display());
+        let tx = self.compile_tx.clone();
+        let path = path.clone();
+        tokio::task::spawn_blocking(move || {
+            let result = crate::compile::load_contract_abi(&path);
+            let _ = tx.send(CompileOutcome::Load { label, path, result, open: false });
+        });
+        None
+    }
+
+    /// Whether the ABIs of a source file are still being compiled for the cache
+    fn abi_loading(&self, path: &PathBuf) -> bool {
+        matches!(self.abi_cache.borrow().get(path), Some(CachedAbis::Loading))
+    }
+
+    /// Like `load_contract_abi_cached`, but compiles a missing file right away, for
+    /// headless commands that have no main loop to wait in
+    fn load_contract_abi_now(&self, path: &PathBuf) -> Option<Vec<(String, Arc<JsonAbi>)>> {
+        if let Some(CachedAbis::Loaded(abis)) = self.abi_cache.borrow().get(path) {
+            return Some(abis.clone());
+        }
+        let entry = Self::cached_abis(crate::compile::load_contract_abi(path).as_ref(), path);
+        self.abi_cache.borrow_mut().insert(path.clone(), entry.clone());
+        match entry {
+            CachedAbis::Loaded(abis) => Some(abis),
+            _ => None,
+        }
+    }
 
-                self.abi_cache.borrow_mut().insert(path.clone(), abi_list.clone());
-                Some(abi_list)
+    fn cached_abis(result: std::result::Result<&LoadedAbis, &anyhow::Error>, path: &Path) -> CachedAbis {
+        match result {
+            Ok(loaded) => CachedAbis::Loaded(
+                loaded.contracts.iter().map(|(name, abi)| (name.clone(), Arc::new(abi.clone()))).collect(),
+            ),
+            Err(e) => {
+                log::warn!("[ABI] Failed to load {}: {e:#}", path.display());
+                CachedAbis::Failed
             }
-            Err(_) => None,
         }
     }
 
     /// Build tree nodes with ABI caching for performance
     fn build_tree_nodes(&self) -> Vec<TreeNode> {
-        use crate::method_list::{self, MethodSelection};
-
         let mut nodes = Vec::new();
 
         // Always show "New contract" at top
         nodes.push(TreeNode::NewContract);
+        nodes.push(TreeNode::AttachBySignature);
 
         // Get all contracts and sort them for stable ordering
         let mut all_contracts: Vec<ContractId> = self.store.all_contracts();
@@ -295,14 +1155,16 @@ impl<P: Provider + Clone> App<P> {
             let contract_path = &contract_id.path;
             let name = &contract_id.name;
             let abi = self.get_abi_for_contract(&contract_id);
+            let loading = self.abi_loading(contract_path);
 
             nodes.push(TreeNode::Contract {
                 name: name.clone(),
                 path: contract_path.clone(),
+                loading,
             });
 
-            // Check if this contract is expanded (by path + name)
-            if self.state.sidebar.expanded_contracts.contains(&(contract_path.clone(), name.clone())) {
+            // Check if this contract is expanded (by path + name); its children wait for its ABI
+            if !loading && self.state.sidebar.expanded_contracts.contains(&(contract_path.clone(), name.clone())) {
                 // Add constructor and load options for all expanded contracts
                 let abi_clone = Arc::clone(&abi);
                 nodes.push(TreeNode::Constructor {
@@ -311,6 +1173,12 @@ impl<P: Provider + Clone> App<P> {
                     abi: abi_clone,
                 });
 
+                nodes.push(TreeNode::DeployBehindProxy {
+                    contract_name: name.clone(),
+                    contract_path: contract_path.clone(),
+                    abi: Arc::clone(&abi),
+                });
+
                 let abi_clone = Arc::clone(&abi);
                 nodes.push(TreeNode::LoadExistingInstance {
                     contract_name: name.clone(),
@@ -318,6 +1186,16 @@ impl<P: Provider + Clone> App<P> {
                     abi: abi_clone,
                 });
 
+                nodes.push(TreeNode::CompilerSettings {
+                    contract_name: name.clone(),
+                    contract_path: contract_path.clone(),
+                });
+
+                nodes.push(TreeNode::SizeReport {
+                    contract_name: name.clone(),
+                    contract_path: contract_path.clone(),
+                });
+
                 // Show deployed instances for this contract
                 let deployments = self.store.get_deployments(&contract_id);
                 for address in &deployments {
@@ -327,26 +1205,84 @@ impl<P: Provider + Clone> App<P> {
                         contract_path: contract_path.clone(),
                     });
 
-                    // Show methods if instance is expanded
+                    // Show instance actions and methods if instance is expanded
                     if self.state.sidebar.expanded_instances.contains(address) {
-                        let methods = method_list::list_methods(&abi, false);
-                        for method in methods {
-                            if let MethodSelection::Function(f) = method.selection {
-                                nodes.push(TreeNode::Method {
-                                    function: f,
-                                    tag: method.tag,
-                                    instance_address: *address,
-                                });
-                            }
+                        nodes.push(TreeNode::InstanceDetails {
+                            address: *address,
+                            contract_name: name.clone(),
+                            contract_path: contract_path.clone(),
+                        });
+
+                        nodes.push(TreeNode::VerifyBytecode {
+                            address: *address,
+                            contract_name: name.clone(),
+                            contract_path: contract_path.clone(),
+                        });
+
+                        let proxy = self.store.get_proxy(self.state.chain_id, *address);
+                        if let Some(info) = proxy.filter(|info| info.kind != ProxyKind::Beacon) {
+                            nodes.push(TreeNode::UpgradeImplementation {
+                                address: *address,
+                                kind: info.kind,
+                                contract_name: name.clone(),
+                                contract_path: contract_path.clone(),
+                            });
+                        }
+
+                        nodes.push(TreeNode::RawCall { address: *address });
+
+                        // Proxies expose the methods of their implementation
+                        let instance_abi = proxy
+                            .and_then(|info| self.implementation_abi(info))
+                            .unwrap_or_else(|| Arc::clone(&abi));
+                        if signing::offers_typed_data(&instance_abi) {
+                            nodes.push(TreeNode::SignTypedData { address: *address });
                         }
+                        push_getters_node(&mut nodes, &instance_abi, *address);
+                        push_method_nodes(&mut nodes, &instance_abi, *address);
                     }
                 }
             }
         }
 
+        // Contracts attached by address and signature, after the ones with sources
+        for (address, attached) in self.store.attached_contracts() {
+            nodes.push(TreeNode::AttachedInstance {
+                address,
+                name: attached.name.clone(),
+            });
+            if !self.state.sidebar.expanded_instances.contains(&address) {
+                continue;
+            }
+            nodes.push(TreeNode::RawCall { address });
+            if let Ok(abi) = attach::abi_from_signatures(&attached.signatures) {
+                if signing::offers_typed_data(&abi) {
+                    nodes.push(TreeNode::SignTypedData { address });
+                }
+                push_getters_node(&mut nodes, &abi, address);
+                push_method_nodes(&mut nodes, &abi, address);
+            }
+        }
+
         nodes
     }
 
+    /// ABI of a contract attached by signature
+    fn attached_abi(&self, address: Address) -> Option<Arc<JsonAbi>> {
+        let attached = self.store.get_attached(address)?;
+        attach::abi_from_signatures(&attached.signatures).ok().map(Arc::new)
+    }
+
+    /// ABI of a proxy's implementation, when the implementation was deployed from here or
+    /// is a known instance
+    fn implementation_abi(&self, info: &ProxyInfo) -> Option<Arc<JsonAbi>> {
+        let contract_id = self
+            .store
+            .implementation_contract(info.implementation)
+            .or_else(|| self.store.contract_for_address(info.implementation))?;
+        Some(self.get_abi_for_contract(&contract_id))
+    }
+
     /// Get ABI for a contract, using cache or current contract
     fn get_abi_for_contract(&self, contract_id: &ContractId) -> Arc<JsonAbi> {
         // Use current contract if this matches
@@ -375,6 +1311,8 @@ impl<P: Provider + Clone> App<P> {
         let mut terminal = crate::tui::setup()?;
         let mut output_area = ratatui::layout::Rect::default();
         
+        self.start_block_watcher();
+
         // Reconnection polling state
         let mut last_reconnect_attempt = std::time::Instant::now();
         let reconnect_interval = std::time::Duration::from_secs(5);
@@ -402,18 +1340,81 @@ impl<P: Provider + Clone> App<P> {
                 continue;
             }
 
-            // Check if we need to open the config editor
-            if self.edit_config_requested {
-                self.edit_config_requested = false;
+            // Check if we need to open a source file at a diagnostic location
+            if let Some((path, line, column)) = self.pending_editor_location.take() {
+                crate::tui::restore(&mut terminal)?;
 
-                // Restore terminal before launching editor
+                if let Err(e) = open_in_editor_at(&path, line, column) {
+                    self.state.output.push_error(format!("Editor error: {e}"));
+                }
+                // The source may have changed, so drop its cached ABI
+                self.abi_cache.borrow_mut().remove(&path);
+
+                terminal = crate::tui::setup()?;
+                continue;
+            }
+
+            // Check if we need to open the "Attach by signature" editor
+            if self.attach_requested {
+                self.attach_requested = false;
                 crate::tui::restore(&mut terminal)?;
 
-                // Open editor
-                self.open_config_in_editor()?;
+                let draft = self
+                    .attach_draft
+                    .take()
+                    .unwrap_or_else(|| attach::TEMPLATE.to_string());
+                match edit_text_in_editor(&draft) {
+                    Ok(text) => self.attach_from_text(text).await,
+                    Err(e) => self.state.output.push_error(format!("Editor error: {e}")),
+                }
 
-                // Reload store to pick up changes
-                match DeploymentStore::load() {
+                terminal = crate::tui::setup()?;
+                continue;
+            }
+
+            // Check if we need to open the "Broadcast raw tx" editor
+            if self.broadcast_requested {
+                self.broadcast_requested = false;
+                crate::tui::restore(&mut terminal)?;
+
+                let draft = self
+                    .broadcast_draft
+                    .take()
+                    .unwrap_or_else(|| raw_tx::TEMPLATE.to_string());
+                match edit_text_in_editor(&draft) {
+                    Ok(text) => self.broadcast_raw_text(text).await,
+                    Err(e) => self.state.output.push_error(format!("Editor error: {e}")),
+                }
+
+                terminal = crate::tui::setup()?;
+                continue;
+            }
+
+            // Check if we need to open a signing tool's editor
+            if let Some((tool, text)) = self.sign_requested.take() {
+                crate::tui::restore(&mut terminal)?;
+
+                match edit_text_in_editor(&text) {
+                    Ok(text) => self.finish_signing(tool, text),
+                    Err(e) => self.state.output.push_error(format!("Editor error: {e}")),
+                }
+
+                terminal = crate::tui::setup()?;
+                continue;
+            }
+
+            // Check if we need to open the config editor
+            if self.edit_config_requested {
+                self.edit_config_requested = false;
+
+                // Restore terminal before launching editor
+                crate::tui::restore(&mut terminal)?;
+
+                // Open editor
+                self.open_config_in_editor()?;
+
+                // Reload store to pick up changes
+                match DeploymentStore::load() {
                     Ok(store) => {
                         self.store = store;
                         // Reset sidebar selection to ensure it's valid after reload
@@ -427,12 +1428,13 @@ impl<P: Provider + Clone> App<P> {
                             .parse::<alloy::signers::local::PrivateKeySigner>()
                         {
                             let new_address = new_signer.address();
-                            if new_address != self.signer_address {
-                                self.signer_address = new_address;
-                                self.state.account = Some(new_address);
+                            if new_address != self.key_address {
+                                self.key_address = new_address;
+                                self.signer = new_signer;
                                 self.state.output.push_info("Account updated from config");
                             }
                         }
+                        self.set_sender(self.store.config.node_account);
                         
                         self.state.output.push_success("Config reloaded");
                         self.state.output.push_info("Note: RPC URL changes require restart");
@@ -450,8 +1452,17 @@ impl<P: Provider + Clone> App<P> {
                 continue;
             }
 
+            self.poll_compilations().await;
+            self.poll_transactions().await;
+            self.poll_blocks().await;
+            self.poll_dashboard().await;
+            self.poll_instances();
+            self.poll_multicall();
+            self.poll_endpoint();
+            self.state.tick = self.state.tick.wrapping_add(1);
+
             terminal.draw(|f| {
-                let layout = AppLayout::new(f.area(), self.state.debug_mode);
+                let layout = AppLayout::new(f.area(), self.state.debug_mode, self.state.rpc_inspector.is_some(), self.state.dashboard.is_some());
                 output_area = layout.output;
                 self.state.output_area_height = layout.output.height.saturating_sub(4);
                 self.render(f);
@@ -552,7 +1563,7 @@ impl<P: Provider + Clone> App<P> {
             return;
         }
 
-        let layout = AppLayout::new(frame.area(), self.state.debug_mode);
+        let layout = AppLayout::new(frame.area(), self.state.debug_mode, self.state.rpc_inspector.is_some(), self.state.dashboard.is_some());
 
         if let Some(debug_area) = layout.debug_bar {
             let debug = DebugBarWidget::new(&self.state);
@@ -566,9 +1577,24 @@ impl<P: Provider + Clone> App<P> {
         frame.render_widget(tree, layout.sidebar);
 
         let output = OutputArea::new(&self.state.output, &self.state.cards)
-            .focused(matches!(self.state.focus, Focus::Output));
+            .focused(matches!(self.state.focus, Focus::Output))
+            .tick(self.state.tick);
         frame.render_widget(output, layout.output);
 
+        if let (Some(inspector_area), Some(inspector)) = (layout.rpc_inspector, &self.state.rpc_inspector) {
+            let exchanges = self.rpc_log.snapshot(&inspector.filter);
+            let panel = RpcInspector::new(&exchanges, inspector)
+                .focused(matches!(self.state.focus, Focus::RpcInspector));
+            frame.render_widget(panel, inspector_area);
+        }
+
+        if let (Some(dashboard_area), Some(dashboard)) = (layout.dashboard, &self.state.dashboard) {
+            let panel = DashboardPanel::new(dashboard)
+                .focused(matches!(self.state.focus, Focus::Dashboard))
+                .tick(self.state.tick);
+            frame.render_widget(panel, dashboard_area);
+        }
+
         let status = StatusBarWidget::new(&self.state);
         frame.render_widget(status, layout.status_bar);
 
@@ -585,16 +1611,19 @@ impl<P: Provider + Clone> App<P> {
                 fields,
                 current,
                 bytecode_target,
+                call_options,
             } => {
                 let popup = ParameterPopup::new(method_name, params, fields, *current)
-                    .bytecode_target(*bytecode_target);
+                    .bytecode_target(*bytecode_target)
+                    .call_options(*call_options)
+                    .sign_only(self.can_sign_only());
                 frame.render_widget(popup, frame.area());
             }
-            PopupState::FilePicker { path, error } => {
-                self.render_file_picker(frame, path, error.as_deref());
+            PopupState::FilePicker { path, error, kind } => {
+                self.render_file_picker(frame, path, error.as_deref(), *kind);
             }
-            PopupState::AddressInput { address, error } => {
-                self.render_address_input(frame, address, error.as_deref());
+            PopupState::AddressInput { address, error, kind } => {
+                self.render_address_input(frame, address, error.as_deref(), *kind);
             }
             PopupState::ContractSelector { contracts, selected } => {
                 self.render_contract_selector(frame, contracts, *selected);
@@ -608,20 +1637,46 @@ impl<P: Provider + Clone> App<P> {
             PopupState::CopyMenu { card_index: _, options, selected } => {
                 self.render_copy_menu(frame, options, *selected);
             }
+            PopupState::Diagnostics { selected } => {
+                let panel = DiagnosticsPanel::new(&self.state.diagnostics, *selected);
+                frame.render_widget(panel, frame.area());
+            }
+            PopupState::CompilerSettings { contract_name, fields, current, .. } => {
+                let popup = CompilerSettingsPopup::new(contract_name, fields, *current);
+                frame.render_widget(popup, frame.area());
+            }
+            PopupState::ConfirmCreate2 { contract_name, predicted, salt } => {
+                self.render_confirm_create2(frame, contract_name, *predicted, *salt);
+            }
+            PopupState::ProxyKindSelector { contract_name, selected } => {
+                self.render_proxy_kind_selector(frame, contract_name, *selected);
+            }
+            PopupState::ImplementationSelector { proxy, contracts, selected } => {
+                self.render_implementation_selector(frame, *proxy, contracts, *selected);
+            }
+            PopupState::UpgradeCallSelector { contract_name, functions, selected } => {
+                self.render_upgrade_call_selector(frame, contract_name, functions, *selected);
+            }
+            PopupState::PendingTransactions { selected } => {
+                self.render_pending_transactions(frame, *selected);
+            }
+            PopupState::AccountSelector { accounts, selected } => {
+                self.render_account_selector(frame, accounts, *selected);
+            }
         }
     }
 
-    fn render_file_picker(&self, frame: &mut Frame, path: &str, error: Option<&str>) {
+    fn render_file_picker(&self, frame: &mut Frame, path: &str, error: Option<&str>, kind: FilePickerKind) {
         use crate::tui::widgets::{KeyboardHints, Popup};
 
         let area = frame.area();
-        let popup = Popup::new("Load Contract")
+        let popup = Popup::new(kind.title())
             .width_percent(60)
             .height_percent(40);
         let inner = popup.render_frame(area, frame.buffer_mut());
 
-        let input = AutocompleteInput::new("Path to .sol file", path)
-            .placeholder("./contracts/MyContract.sol")
+        let input = AutocompleteInput::new(kind.label(), path)
+            .placeholder(kind.placeholder())
             .error(error)
             .focused(true)
             .suggestions(&self.state.file_picker_suggestions)
@@ -646,16 +1701,16 @@ impl<P: Provider + Clone> App<P> {
         frame.render_widget(hints, hints_area);
     }
 
-    fn render_address_input(&self, frame: &mut Frame, address: &str, error: Option<&str>) {
+    fn render_address_input(&self, frame: &mut Frame, address: &str, error: Option<&str>, kind: AddressInputKind) {
         use crate::tui::widgets::{InputField, Popup};
 
         let area = frame.area();
-        let popup = Popup::new("Enter Address")
+        let popup = Popup::new(kind.title())
             .width_percent(60)
             .height_percent(20);
         let inner = popup.render_frame(area, frame.buffer_mut());
 
-        let input = InputField::new("Contract address", address)
+        let input = InputField::new(kind.label(), address)
             .placeholder("0x...")
             .error(error)
             .focused(true);
@@ -669,6 +1724,175 @@ impl<P: Provider + Clone> App<P> {
         frame.render_widget(input, field_area);
     }
 
+    fn render_confirm_create2(&self, frame: &mut Frame, contract_name: &str, predicted: Address, salt: B256) {
+        use crate::tui::widgets::{KeyboardHints, Popup};
+        use ratatui::text::{Line, Span};
+
+        let area = frame.area();
+        let popup = Popup::new("Confirm CREATE2 Deployment")
+            .width_percent(70)
+            .height_percent(30);
+        let inner = popup.render_frame(area, frame.buffer_mut());
+
+        let label_style = ratatui::style::Style::default().fg(crate::tui::theme::PRIMARY);
+        let lines = [
+            Line::from(vec![Span::styled("Contract:  ", label_style), Span::raw(contract_name)]),
+            Line::from(vec![Span::styled("Factory:   ", label_style), Span::raw(format!("{:?}", create2::FACTORY))]),
+            Line::from(vec![Span::styled("Salt:      ", label_style), Span::raw(format!("{salt}"))]),
+            Line::from(vec![
+                Span::styled("Address:   ", label_style),
+                Span::styled(format!("{predicted:?}"), crate::tui::theme::success_style()),
+            ]),
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            frame.buffer_mut().set_line(inner.x + 1, inner.y + 1 + i as u16, line, inner.width.saturating_sub(2));
+        }
+
+        let hints = KeyboardHints::new(vec![("Enter", "deploy"), ("Esc", "cancel")]);
+        let footer_y = inner.y + inner.height.saturating_sub(1);
+        let hints_area = ratatui::layout::Rect::new(inner.x + 1, footer_y, inner.width.saturating_sub(2), 1);
+        hints.render(hints_area, frame.buffer_mut());
+    }
+
+    fn render_proxy_kind_selector(&self, frame: &mut Frame, contract_name: &str, selected: usize) {
+        use crate::tui::widgets::{Popup, SelectableList};
+
+        let area = frame.area();
+        let title = format!("Deploy {contract_name} behind proxy");
+        let popup = Popup::new(&title)
+            .width_percent(50)
+            .height_percent(30);
+        let inner = popup.render_frame(area, frame.buffer_mut());
+
+        let kinds: Vec<String> = ProxyKind::DEPLOYABLE
+            .iter()
+            .map(|kind| match kind {
+                ProxyKind::Uups => "UUPS (ERC1967Proxy, upgrades through the implementation)".to_string(),
+                _ => format!("{kind} (upgrades through a ProxyAdmin owned by your account)"),
+            })
+            .collect();
+        let list = SelectableList::simple(&kinds, selected);
+        frame.render_widget(list, inner);
+    }
+
+    fn render_implementation_selector(&self, frame: &mut Frame, proxy: Address, contracts: &[(String, PathBuf)], selected: usize) {
+        use crate::tui::widgets::{Popup, SelectableList};
+
+        let area = frame.area();
+        let title = format!("New implementation for {proxy:?}");
+        let popup = Popup::new(&title)
+            .width_percent(60)
+            .height_percent(40);
+        let inner = popup.render_frame(area, frame.buffer_mut());
+
+        let items: Vec<String> = contracts
+            .iter()
+            .map(|(name, path)| format!("{name} ({})", path.display()))
+            .collect();
+        let list = SelectableList::simple(&items, selected);
+        frame.render_widget(list, inner);
+    }
+
+    fn render_upgrade_call_selector(&self, frame: &mut Frame, contract_name: &str, functions: &[Function], selected: usize) {
+        use crate::tui::widgets::{Popup, SelectableList};
+
+        let area = frame.area();
+        let title = format!("Call after upgrading to {contract_name}");
+        let popup = Popup::new(&title)
+            .width_percent(60)
+            .height_percent(40);
+        let inner = popup.render_frame(area, frame.buffer_mut());
+
+        let items: Vec<String> = std::iter::once("No call (empty data)".to_string())
+            .chain(functions.iter().map(|function| function.signature()))
+            .collect();
+        let list = SelectableList::simple(&items, selected);
+        frame.render_widget(list, inner);
+    }
+
+    fn render_pending_transactions(&self, frame: &mut Frame, selected: usize) {
+        use crate::tui::widgets::{KeyboardHints, Popup, SelectableList};
+
+        let area = frame.area();
+        let popup = Popup::new("Pending Transactions")
+            .width_percent(80)
+            .height_percent(50);
+        let inner = popup.render_frame(area, frame.buffer_mut());
+
+        let items: Vec<String> = self
+            .pending_transaction_ids()
+            .iter()
+            .map(|id| {
+                let in_flight = &self.in_flight[id];
+                let nonce = in_flight.sent.map(|sent| format!("#{}", sent.nonce)).unwrap_or_else(|| "#?".to_string());
+                let hash = in_flight.card_hash.map(|hash| format!("{hash:?}")).unwrap_or_else(|| "sending...".to_string());
+                let mut item = format!(
+                    "{nonce}  {}  {}s  {hash}",
+                    in_flight.purpose.title(),
+                    in_flight.sent_at.elapsed().as_secs()
+                );
+                if let Some(sent) = in_flight.sent {
+                    item.push_str(&format!("  ({})", sent.fees));
+                }
+                if !in_flight.cancel_hashes.is_empty() {
+                    item.push_str("  [cancelling]");
+                }
+                item
+            })
+            .collect();
+        let list_area = ratatui::layout::Rect::new(inner.x, inner.y, inner.width, inner.height.saturating_sub(1));
+        if items.is_empty() {
+            let empty = ratatui::widgets::Paragraph::new("  No transactions waiting for a receipt");
+            frame.render_widget(empty, list_area);
+        } else {
+            let list = SelectableList::simple(&items, selected.min(items.len() - 1));
+            frame.render_widget(list, list_area);
+        }
+
+        let hints = KeyboardHints::new(vec![
+            ("↑/↓", "navigate"),
+            ("s", "speed up"),
+            ("x", "cancel"),
+            ("Esc", "close"),
+        ]);
+        let hints_area = ratatui::layout::Rect::new(inner.x + 1, inner.y + inner.height - 1, inner.width.saturating_sub(2), 1);
+        frame.render_widget(hints, hints_area);
+    }
+
+    fn render_account_selector(&self, frame: &mut Frame, accounts: &[(AccountChoice, Option<U256>)], selected: usize) {
+        use crate::tui::widgets::{KeyboardHints, Popup, SelectableList};
+
+        let area = frame.area();
+        let popup = Popup::new("Select Account")
+            .width_percent(80)
+            .height_percent(50);
+        let inner = popup.render_frame(area, frame.buffer_mut());
+
+        let items: Vec<String> = accounts
+            .iter()
+            .map(|(choice, balance)| {
+                let balance = balance.map(|balance| format!("  {} ETH", format_ether(balance))).unwrap_or_default();
+                let current = |address: &Address| if *address == self.signer_address { "  (current)" } else { "" };
+                match choice {
+                    AccountChoice::PrivateKey(address) => {
+                        format!("{address:?}  private key{balance}{}", current(address))
+                    }
+                    AccountChoice::Node(address) => {
+                        format!("{address:?}  unlocked on the node{balance}{}", current(address))
+                    }
+                    AccountChoice::Impersonate => "Impersonate an address... (anvil, hardhat)".to_string(),
+                }
+            })
+            .collect();
+        let list_area = ratatui::layout::Rect::new(inner.x, inner.y, inner.width, inner.height.saturating_sub(1));
+        let list = SelectableList::simple(&items, selected);
+        frame.render_widget(list, list_area);
+
+        let hints = KeyboardHints::new(vec![("↑/↓", "navigate"), ("Enter", "send from"), ("Esc", "close")]);
+        let hints_area = ratatui::layout::Rect::new(inner.x + 1, inner.y + inner.height - 1, inner.width.saturating_sub(2), 1);
+        frame.render_widget(hints, hints_area);
+    }
+
     fn render_contract_selector(&self, frame: &mut Frame, contracts: &[String], selected: usize) {
         use crate::tui::widgets::{Popup, SelectableList};
 
@@ -683,8 +1907,12 @@ impl<P: Provider + Clone> App<P> {
     }
 
     fn update_file_picker_suggestions(&mut self, input: &str) {
+        let kind = match &self.state.popup {
+            PopupState::FilePicker { kind, .. } => *kind,
+            _ => FilePickerKind::Contract,
+        };
         let (dir, prefix) = parse_path_for_autocomplete(input);
-        self.state.file_picker_suggestions = scan_path_suggestions(&dir, &prefix);
+        self.state.file_picker_suggestions = scan_path_suggestions(&dir, &prefix, kind.extension());
         self.state.file_picker_selected_idx = 0;
     }
 
@@ -703,6 +1931,14 @@ impl<P: Provider + Clone> App<P> {
             PopupState::TracerMenu { .. } => "TracerMenu",
             PopupState::TracerConfig { .. } => "TracerConfig",
             PopupState::CopyMenu { .. } => "CopyMenu",
+            PopupState::Diagnostics { .. } => "Diagnostics",
+            PopupState::CompilerSettings { .. } => "CompilerSettings",
+            PopupState::AccountSelector { .. } => "AccountSelector",
+            PopupState::ConfirmCreate2 { .. } => "ConfirmCreate2",
+            PopupState::ProxyKindSelector { .. } => "ProxyKindSelector",
+            PopupState::ImplementationSelector { .. } => "ImplementationSelector",
+            PopupState::UpgradeCallSelector { .. } => "UpgradeCallSelector",
+            PopupState::PendingTransactions { .. } => "PendingTransactions",
         };
         log::trace!("[KEY] {} | focus={:?} popup={}", key_str, self.state.focus, popup_state);
 
@@ -722,12 +1958,20 @@ impl<P: Provider + Clone> App<P> {
                     };
                     return Ok(());
                 }
+                KeyCode::Char('r') => {
+                    self.toggle_rpc_inspector();
+                    return Ok(());
+                }
                 _ => {}
             }
         }
 
-        // Global card action shortcuts (work regardless of focus, but only when no popup is open)
-        if matches!(self.state.popup, PopupState::None) && !self.state.cards.cards.is_empty() {
+        // Global card action shortcuts (work regardless of focus, but only when no popup is open
+        // and neither the RPC inspector nor the dashboard, which have their own keys, is focused)
+        if matches!(self.state.popup, PopupState::None)
+            && !matches!(self.state.focus, Focus::RpcInspector | Focus::Dashboard)
+            && !self.state.cards.cards.is_empty()
+        {
             let card_index = self.state.cards.selected_index;
             if card_index < self.state.cards.cards.len() {
                 match key.code {
@@ -759,6 +2003,18 @@ impl<P: Provider + Clone> App<P> {
                             return Ok(());
                         }
                     }
+                    KeyCode::Char(key @ ('s' | 'x')) => {
+                        // Speed up / cancel shortcuts for pending Transaction cards
+                        let pending = matches!(
+                            &self.state.cards.cards[card_index],
+                            crate::cards::Card::Transaction { status: TransactionStatus::Pending, .. }
+                        );
+                        if pending {
+                            let action = if key == 's' { crate::cards::CardAction::SpeedUp } else { crate::cards::CardAction::Cancel };
+                            self.handle_card_action(card_index, action).await?;
+                            return Ok(());
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -775,6 +2031,14 @@ impl<P: Provider + Clone> App<P> {
             PopupState::TracerMenu { .. } => self.handle_tracer_menu_key(key).await?,
             PopupState::TracerConfig { .. } => self.handle_tracer_config_key(key).await?,
             PopupState::CopyMenu { .. } => self.handle_copy_menu_key(key).await?,
+            PopupState::Diagnostics { .. } => self.handle_diagnostics_key(key).await?,
+            PopupState::CompilerSettings { .. } => self.handle_compiler_settings_key(key)?,
+            PopupState::AccountSelector { .. } => self.handle_account_selector_key(key).await,
+            PopupState::ConfirmCreate2 { .. } => self.handle_confirm_create2_key(key).await,
+            PopupState::ProxyKindSelector { .. } => self.handle_proxy_kind_selector_key(key),
+            PopupState::ImplementationSelector { .. } => self.handle_implementation_selector_key(key),
+            PopupState::UpgradeCallSelector { .. } => self.handle_upgrade_call_selector_key(key),
+            PopupState::PendingTransactions { .. } => self.handle_pending_transactions_key(key),
         }
 
         Ok(())
@@ -784,11 +2048,15 @@ impl<P: Provider + Clone> App<P> {
         match self.state.focus {
             Focus::Sidebar => self.handle_sidebar_key(key).await?,
             Focus::Output => self.handle_output_key(key).await?,
+            Focus::RpcInspector => self.handle_rpc_inspector_key(key),
+            Focus::Dashboard => self.handle_dashboard_key(key).await,
             _ => {}
         }
         Ok(())
     }
 
+    // Bounds checks stay inside the arms so keys at the edge don't fall through to later arms
+    #[allow(clippy::collapsible_match)]
     async fn handle_sidebar_key(&mut self, key: KeyEvent) -> Result<()> {
         // Build tree to get current nodes (with caching)
         let nodes = self.build_tree_nodes();
@@ -813,12 +2081,12 @@ impl<P: Provider + Clone> App<P> {
             KeyCode::Left | KeyCode::Char('h') => {
                 if let Some(node) = nodes.get(self.state.sidebar.selected) {
                     match node {
-                        TreeNode::Contract { path, name } => {
+                        TreeNode::Contract { path, name, .. } => {
                             let canonical_path = path.canonicalize().unwrap_or_else(|_| path.clone());
                             self.state.sidebar.expanded_contracts.remove(&(canonical_path, name.clone()));
                             self.state.last_action = Some("Collapse contract".into());
                         }
-                        TreeNode::DeployedInstance { address, .. } => {
+                        TreeNode::DeployedInstance { address, .. } | TreeNode::AttachedInstance { address, .. } => {
                             self.state.sidebar.expanded_instances.remove(address);
                             self.state.last_action = Some("Collapse instance".into());
                         }
@@ -829,12 +2097,12 @@ impl<P: Provider + Clone> App<P> {
             KeyCode::Right | KeyCode::Char('l') => {
                 if let Some(node) = nodes.get(self.state.sidebar.selected) {
                     match node {
-                        TreeNode::Contract { path, name } => {
+                        TreeNode::Contract { path, name, .. } => {
                             let canonical_path = path.canonicalize().unwrap_or_else(|_| path.clone());
                             self.state.sidebar.expanded_contracts.insert((canonical_path, name.clone()));
                             self.state.last_action = Some("Expand contract".into());
                         }
-                        TreeNode::DeployedInstance { address, .. } => {
+                        TreeNode::DeployedInstance { address, .. } | TreeNode::AttachedInstance { address, .. } => {
                             self.state.sidebar.expanded_instances.insert(*address);
                             self.set_address(*address);
                             self.state.last_action = Some("Expand instance".into());
@@ -849,12 +2117,28 @@ impl<P: Provider + Clone> App<P> {
                     self.execute_tree_node(node.clone()).await?;
                 }
             }
+            KeyCode::Char('w') => {
+                // Watch the instance: its getters, refreshed on every block
+                if let Some(TreeNode::DeployedInstance { address, .. } | TreeNode::AttachedInstance { address, .. }) =
+                    nodes.get(self.state.sidebar.selected)
+                {
+                    self.toggle_dashboard(*address).await;
+                }
+            }
+            KeyCode::Char('o') => {
+                // Call with options: block, from and state overrides, or the popup of a
+                // transaction without parameters so it can be signed only
+                if let Some(TreeNode::Method { function, instance_address, .. }) = nodes.get(self.state.sidebar.selected) {
+                    self.state.last_action = Some(format!("Call with options: {}", function.name));
+                    self.start_call_function(function.clone(), *instance_address, true).await;
+                }
+            }
             KeyCode::Delete | KeyCode::Backspace => {
                 if let Some(node) = nodes.get(self.state.sidebar.selected) {
                     let removed = match node {
                         TreeNode::DeployedInstance { address, contract_name, contract_path, .. } => {
                             let contract_id = ContractId::new(contract_path.clone(), contract_name.clone());
-                            if self.store.remove_deployment(&contract_id, *address) {
+                            if self.store.remove_deployment(&contract_id, self.state.chain_id, *address) {
                                 self.state.output.push_info(format!("Removed deployment: {address:?}"));
                                 // Clear expanded state for this instance
                                 self.state.sidebar.expanded_instances.remove(address);
@@ -866,6 +2150,14 @@ impl<P: Provider + Clone> App<P> {
                                 false
                             }
                         }
+                        TreeNode::AttachedInstance { address, name } if self.store.detach(*address) => {
+                            self.state.output.push_info(format!("Detached {name} at {address:?}"));
+                            self.state.sidebar.expanded_instances.remove(address);
+                            if self.address == Some(*address) {
+                                self.address = None;
+                            }
+                            true
+                        }
                         TreeNode::Contract { path, name, .. } => {
                             let contract_id = ContractId::new(path.clone(), name.clone());
                             if self.store.remove_contract(&contract_id) {
@@ -945,13 +2237,20 @@ impl<P: Provider + Clone> App<P> {
                 }
             }
             KeyCode::Tab => {
-                self.state.focus = Focus::Sidebar;
+                self.state.focus = if self.state.rpc_inspector.is_some() {
+                    Focus::RpcInspector
+                } else if self.state.dashboard.is_some() {
+                    Focus::Dashboard
+                } else {
+                    Focus::Sidebar
+                };
             }
             _ => {}
         }
         Ok(())
     }
 
+    #[allow(clippy::collapsible_match)]
     async fn handle_command_palette_key(&mut self, key: KeyEvent) -> Result<()> {
         if let PopupState::CommandPalette { query, selected } = &mut self.state.popup {
             match key.code {
@@ -1015,9 +2314,15 @@ impl<P: Provider + Clone> App<P> {
     }
 
     async fn handle_parameter_popup_key(&mut self, key: KeyEvent) -> Result<()> {
+        // Ctrl+S submits like Enter but only signs the transaction
+        let sign_only = key.code == KeyCode::Char('s') && key.modifiers.contains(KeyModifiers::CONTROL);
+        if sign_only && !self.can_sign_only() {
+            return Ok(());
+        }
+
         // Handle Enter separately to avoid borrow checker issues
-        if key.code == KeyCode::Enter {
-            if let PopupState::ParameterPopup { params, fields, bytecode_target, method_name, .. } = &self.state.popup {
+        if key.code == KeyCode::Enter || sign_only {
+            if let PopupState::ParameterPopup { params, fields, bytecode_target, method_name, call_options, .. } = &self.state.popup {
                 // Log parameter submission
                 let field_values: Vec<_> = fields.iter().map(|f| f.value.as_str()).collect();
                 log::info!(
@@ -1028,29 +2333,100 @@ impl<P: Provider + Clone> App<P> {
                         PendingAction::None => "None".to_string(),
                         PendingAction::Deploy { contract_name, .. } => format!("Deploy({})", contract_name),
                         PendingAction::CallMethod { function, address } => format!("Call({} @ {:?})", function.name, address),
+                        PendingAction::RawCall => "RawCall".to_string(),
                     }
                 );
 
                 // Clone data we need for parsing
+                let title = method_name.clone();
                 let params_clone = params.clone();
                 let fields_clone = fields.clone();
                 let target = *bytecode_target;
+                let has_call_options = *call_options;
+
+                // Deploy popups end with the CREATE2 salt field (empty means plain CREATE).
+                // The factory's address derivation only holds for EVM bytecode, so a PVM
+                // instance could end up somewhere else than predicted.
+                let salt = match fields_clone.get(params_clone.len()) {
+                    Some(field) if target.is_some() && !field.value.trim().is_empty() => match target {
+                        Some(BytecodeTarget::Pvm) => {
+                            Err((params_clone.len(), "CREATE2 deployments are only supported for EVM".to_string()))
+                        }
+                        _ => create2::parse_salt(&field.value).map(Some).map_err(|e| (params_clone.len(), e)),
+                    },
+                    _ => Ok(None),
+                };
+
+                // View call popups end with the call option fields
+                let options = if has_call_options {
+                    let values: Vec<&str> =
+                        fields_clone.iter().skip(params_clone.len()).map(|f| f.value.as_str()).collect();
+                    CallOptions::parse(&values).map_err(|errors| {
+                        errors.into_iter().map(|(i, e)| (params_clone.len() + i, e)).collect::<Vec<_>>()
+                    })
+                } else {
+                    Ok(CallOptions::default())
+                };
 
-                let values = self.try_parse_params(&params_clone, &fields_clone);
+                let values = match (self.try_parse_params(&params_clone, &fields_clone), salt, options) {
+                    (Ok(args), Ok(salt), Ok(options)) => Ok((args, salt, options)),
+                    (args, salt, options) => Err(args
+                        .err()
+                        .unwrap_or_default()
+                        .into_iter()
+                        .chain(salt.err())
+                        .chain(options.err().unwrap_or_default())
+                        .collect::<Vec<_>>()),
+                };
                 match values {
-                    Ok(args) => {
+                    Ok((args, salt, options)) => {
                         let action = self.pending_action.clone();
                         self.state.popup = PopupState::None;
                         self.state.focus = Focus::Sidebar;
                         self.pending_action = PendingAction::None;
 
+                        if sign_only {
+                            self.sign_pending_action(action, args, salt, target.unwrap_or_default(), &title).await;
+                            return Ok(());
+                        }
+
                         match action {
-                            PendingAction::Deploy { contract_name, contract_path, abi } => {
+                            PendingAction::Deploy { contract_name, contract_path, abi, mode } => {
+                                let (args, plan) = match mode {
+                                    DeployMode::Instance => match salt {
+                                        Some(salt) => (args, DeployPlan::Create2(salt)),
+                                        None => (args, DeployPlan::Create),
+                                    },
+                                    DeployMode::Proxy(kind) => match proxy::initializer_data(&abi, &args) {
+                                        Ok(init_data) => (Vec::new(), DeployPlan::Proxy { kind, init_data }),
+                                        Err(e) => {
+                                            self.add_log_card(format!("Failed: Deploy {contract_name} behind proxy\n\nFailed to encode initialize(): {e}"));
+                                            return Ok(());
+                                        }
+                                    },
+                                    DeployMode::Upgrade { proxy, info, call, .. } => {
+                                        let (args, call_args) = split_constructor_args(&abi, args);
+                                        let data = match &call {
+                                            Some(function) => match function.abi_encode_input(&call_args) {
+                                                Ok(data) => Bytes::from(data),
+                                                Err(e) => {
+                                                    self.add_log_card(format!("Failed: Upgrade {proxy:?} to {contract_name}\n\nFailed to encode {}(): {e}", function.name));
+                                                    return Ok(());
+                                                }
+                                            },
+                                            None => Bytes::new(),
+                                        };
+                                        (args, DeployPlan::Upgrade { proxy, info, data })
+                                    }
+                                };
                                 // target is Some for deploy operations
-                                self.do_deploy(contract_name, contract_path, abi, args, target.unwrap_or_default()).await;
+                                self.do_deploy(contract_name, contract_path, abi, args, target.unwrap_or_default(), plan).await;
                             }
                             PendingAction::CallMethod { function, address } => {
-                                self.do_call_function(&function, address, args).await;
+                                self.do_call_function(&function, address, args, options).await;
+                            }
+                            PendingAction::RawCall => {
+                                self.do_raw_call(&title, args).await;
                             }
                             PendingAction::None => {}
                         }
@@ -1113,8 +2489,115 @@ impl<P: Provider + Clone> App<P> {
         Ok(())
     }
 
+    /// Whether the parameter popup's action is a single transaction that "Sign only" can
+    /// produce: a state-changing call, a raw call or a plain instance deployment
+    fn can_sign_only(&self) -> bool {
+        match &self.pending_action {
+            PendingAction::Deploy { mode, .. } => matches!(mode, DeployMode::Instance),
+            PendingAction::CallMethod { function, .. } => !matches!(
+                function.state_mutability,
+                alloy::json_abi::StateMutability::View | alloy::json_abi::StateMutability::Pure
+            ),
+            PendingAction::RawCall => true,
+            PendingAction::None => false,
+        }
+    }
+
+    /// Build the transaction the parameter popup would send and sign it instead
+    async fn sign_pending_action(
+        &mut self,
+        action: PendingAction,
+        args: Vec<DynSolValue>,
+        salt: Option<B256>,
+        target: BytecodeTarget,
+        title: &str,
+    ) {
+        match action {
+            PendingAction::Deploy { contract_name, contract_path, abi, .. } => {
+                // Only the implementation of an upgrade is signed, without the upgrade call
+                let (args, _) = split_constructor_args(&abi, args);
+                if salt.is_some() {
+                    self.add_log_card(format!(
+                        "Failed: Sign Deploy {contract_name}\n\nOnly plain CREATE deployments can be signed; clear the salt field."
+                    ));
+                    return;
+                }
+                self.do_deploy(contract_name, contract_path, abi, args, target, DeployPlan::SignOnly).await;
+            }
+            PendingAction::CallMethod { function, address } => match function.abi_encode_input(&args) {
+                Ok(calldata) => {
+                    let tx = TransactionRequest::default().to(address).input(calldata.into());
+                    let call_str = prompts::format_method_call(&function.name, &function.inputs, &args);
+                    self.sign_only(&format!("{call_str} @ {address:?}"), tx).await;
+                }
+                Err(e) => {
+                    self.add_log_card(format!("Failed: Sign {}\n\nFailed to encode function call: {e}", function.name));
+                }
+            },
+            PendingAction::RawCall => {
+                let [DynSolValue::Address(to), DynSolValue::Bytes(data), DynSolValue::Uint(value, _), _] = args.as_slice()
+                else {
+                    return;
+                };
+                let data = Bytes::from(data.clone());
+                let call_str = self.describe_calldata(title, *to, &data, *value);
+                let tx = TransactionRequest::default().to(*to).input(data.into()).value(*value);
+                self.sign_only(&format!("{call_str} @ {to:?}"), tx).await;
+            }
+            PendingAction::None => {}
+        }
+    }
+
+    /// Sign `tx` with the private key without sending it. Gas and fees come from the node,
+    /// the nonce follows the last transaction signed here when the node has not seen it yet;
+    /// the raw transaction is copied to the clipboard and saved under
+    /// ~/.evm-cli/signed for "Broadcast raw tx" or another machine.
+    async fn sign_only(&mut self, title: &str, tx: TransactionRequest) {
+        log::info!("[TX] Sign only: {title}");
+        if self.state.node_account.is_some() {
+            self.add_log_card(format!(
+                "Failed: Sign {title}\n\nThe node signs for {:?} only when sending. Ctrl+P > Select account to sign with the private key.",
+                self.signer_address
+            ));
+            return;
+        }
+        if matches!(self.state.connection, ConnectionStatus::Disconnected) {
+            self.add_log_card(format!("Cannot sign {title}: not connected to RPC"));
+            return;
+        }
+
+        let mut tx = tx.from(self.key_address);
+        if let Some(chain_id) = self.state.chain_id {
+            tx = tx.with_chain_id(chain_id);
+        }
+        let signed = match raw_tx::sign(&self.provider, EthereumWallet::from(self.signer.clone()), tx, &mut self.signed_nonces).await {
+            Ok(signed) => signed,
+            Err(e) => {
+                self.add_log_card(format!("Failed: Sign {title}\n\n{e:#}"));
+                return;
+            }
+        };
+
+        let mut message = format!("Signed {title} (not sent)\n\n{signed}\n");
+        match signed.save(&raw_tx::default_signed_dir()) {
+            Ok(path) => message.push_str(&format!("\nSaved to {}", path.display())),
+            Err(e) => message.push_str(&format!("\nNot saved: {e:#}")),
+        }
+        match copy_to_clipboard(&signed.raw_hex()) {
+            Ok(()) => message.push_str("\nCopied to clipboard"),
+            Err(e) => log::warn!("[TX] Failed to copy the raw transaction: {e}"),
+        }
+        message.push_str("\n\nCtrl+P > Broadcast raw tx sends it");
+        self.state.output.push_success(format!("Signed {title}: {:?}", signed.hash));
+        self.state.output.push_separator();
+        self.state.output.scroll_to_bottom();
+        self.add_log_card(message);
+    }
+
+    #[allow(clippy::collapsible_match)]
     async fn handle_file_picker_key(&mut self, key: KeyEvent) -> Result<()> {
-        if let PopupState::FilePicker { path, error } = &mut self.state.popup {
+        if let PopupState::FilePicker { path, error, kind } = &mut self.state.popup {
+            let kind = *kind;
             match key.code {
                 KeyCode::Esc => {
                     self.state.popup = PopupState::None;
@@ -1180,7 +2663,11 @@ impl<P: Provider + Clone> App<P> {
                     self.state.focus = Focus::Sidebar;
                     self.state.file_picker_suggestions.clear();
                     self.state.file_picker_selected_idx = 0;
-                    self.load_contract_from_path(file_path).await?;
+                    match kind {
+                        FilePickerKind::Contract => self.load_contract_from_path(file_path).await?,
+                        FilePickerKind::Manifest => self.start_manifest(file_path),
+                        FilePickerKind::Broadcast => self.import_broadcast(&file_path),
+                    }
                 }
                 KeyCode::Char(c) => {
                     path.push(c);
@@ -1201,7 +2688,7 @@ impl<P: Provider + Clone> App<P> {
     }
 
     async fn handle_address_input_key(&mut self, key: KeyEvent) -> Result<()> {
-        if let PopupState::AddressInput { address, error } = &mut self.state.popup {
+        if let PopupState::AddressInput { address, error, kind } = &mut self.state.popup {
             match key.code {
                 KeyCode::Esc => {
                     self.state.popup = PopupState::None;
@@ -1209,11 +2696,16 @@ impl<P: Provider + Clone> App<P> {
                 }
                 KeyCode::Enter => {
                     match address.parse::<Address>() {
+                        Ok(addr) if *kind == AddressInputKind::Impersonate => {
+                            self.state.popup = PopupState::None;
+                            self.state.focus = Focus::Sidebar;
+                            self.use_account(Some(NodeAccount { address: addr, impersonated: true })).await;
+                        }
                         Ok(addr) => {
                             log::info!("[ADDRESS] set_address: {:?} for contract {:?}", addr, self.contract.as_ref().map(|c| &c.name));
                             self.state.popup = PopupState::None;
                             self.state.focus = Focus::Sidebar;
-                            self.set_address(addr);
+                            self.load_existing_instance(addr).await;
                         }
                         Err(_) => {
                             log::warn!("[ADDRESS] Invalid address format: {}", address);
@@ -1235,75 +2727,664 @@ impl<P: Provider + Clone> App<P> {
         Ok(())
     }
 
-    async fn handle_contract_selector_key(&mut self, key: KeyEvent) -> Result<()> {
-        if let PopupState::ContractSelector { contracts, selected } = &mut self.state.popup {
-            match key.code {
-                KeyCode::Esc => {
-                    self.state.popup = PopupState::None;
-                    self.state.focus = Focus::Sidebar;
-                }
-                KeyCode::Up => {
-                    if *selected > 0 {
-                        *selected -= 1;
-                    }
-                }
-                KeyCode::Down => {
-                    if *selected + 1 < contracts.len() {
-                        *selected += 1;
-                    }
-                }
-                KeyCode::Enter => {
-                    if let Some(name) = contracts.get(*selected).cloned() {
-                        self.state.popup = PopupState::None;
-                        self.state.focus = Focus::Sidebar;
-                        self.select_compiled_contract(&name)?;
-                    }
+    /// Register an existing instance of the current contract and check whether it is a proxy
+    async fn load_existing_instance(&mut self, address: Address) {
+        self.set_address(address);
+        let (Some(contract), Some(path)) = (&self.contract, &self.contract_path) else {
+            return;
+        };
+        let path = path.canonicalize().unwrap_or_else(|_| path.clone());
+        let contract_id = ContractId::new(path, contract.name.clone());
+        // Recorded for this chain unless it already is; a record without a chain is taken over
+        let recorded = self
+            .store
+            .get_deployment(&contract_id, self.state.chain_id, address)
+            .is_some_and(|record| record.chain_id == self.state.chain_id);
+        if !recorded {
+            let record = DeploymentRecord {
+                chain_id: self.state.chain_id,
+                genesis_hash: self.state.genesis_hash,
+                ..DeploymentRecord::new(address)
+            };
+            self.store.record_deployment(&contract_id, record);
+        }
+
+        match proxy::detect(&self.provider, address).await {
+            Ok(info) => {
+                if let Some(info) = &info {
+                    self.add_log_card(format!("{address:?} is an ERC-1967 proxy\n\n{info}"));
                 }
-                _ => {}
+                self.store.set_proxy(self.state.chain_id, address, info);
             }
+            Err(e) => log::warn!("[PROXY] Failed to read ERC-1967 slots of {address:?}: {e}"),
         }
-        Ok(())
+        if let Err(e) = self.store.save() {
+            self.state.output.push_error(format!("Failed to save instance: {e}"));
+        }
+
+        if let Some(chain_id) = self.state.chain_id {
+            self.state.sidebar.dead_instances.remove(&(chain_id, address));
+        }
+        self.state.sidebar.expanded_instances.insert(address);
+        self.select_instance_in_sidebar(address);
     }
 
-    async fn execute_tree_node(&mut self, node: TreeNode) -> Result<()> {
-        log::info!("[ACTION] execute_tree_node: {}", node.label());
-        match node {
-            TreeNode::NewContract => {
-                self.state.popup = PopupState::FilePicker {
-                    path: String::new(),
-                    error: None,
-                };
-                self.state.focus = Focus::CommandPalette;
-                self.update_file_picker_suggestions("");
+    /// Attach the address and ABI typed in the "Attach by signature" editor
+    async fn attach_from_text(&mut self, text: String) {
+        let spec = match attach::parse(&text) {
+            Ok(Some(spec)) => spec,
+            Ok(None) => {
+                self.state.output.push_info("Attach cancelled");
+                return;
             }
-            TreeNode::Contract { path, name } => {
-                // Toggle expansion for this contract (path is already canonicalized from tree)
-                let key = (path.clone(), name.clone());
-                if self.state.sidebar.expanded_contracts.contains(&key) {
-                    self.state.sidebar.expanded_contracts.remove(&key);
-                } else {
-                    self.state.sidebar.expanded_contracts.insert(key);
+            Err(e) => {
+                self.add_log_card(format!(
+                    "Failed: Attach by signature\n\n{e:#}\n\nYour text is kept; choose \"Attach by signature...\" again to fix it."
+                ));
+                self.attach_draft = Some(text);
+                return;
+            }
+        };
+        let address = spec.address;
+
+        let functions = spec.abi.functions().count();
+        let events = spec.abi.events().count();
+        let mut message = format!("Attached {} at {address:?} ({functions} functions, {events} events)", spec.name);
+        if matches!(self.state.connection, ConnectionStatus::Connected) {
+            match self.provider.get_code_at(address).await {
+                Ok(code) if code.is_empty() => {
+                    message.push_str("\n\nThere is no code at this address on the connected node; calls will return nothing.");
                 }
+                Ok(_) => {}
+                Err(e) => log::warn!("[ATTACH] Failed to read code at {address:?}: {e}"),
             }
-            TreeNode::Constructor { contract_name, contract_path, abi } => {
+        }
+
+        self.store.attach(
+            address,
+            AttachedContract {
+                name: spec.name,
+                signatures: spec.signatures,
+            },
+        );
+        if let Err(e) = self.store.save() {
+            self.state.output.push_error(format!("Failed to save attached contract: {e}"));
+        }
+        self.add_log_card(message);
+
+        self.set_address(address);
+        self.state.sidebar.expanded_instances.insert(address);
+        self.select_instance_in_sidebar(address);
+    }
+
+    /// Broadcast the signed transaction pasted in the "Broadcast raw tx" editor
+    async fn broadcast_raw_text(&mut self, text: String) {
+        let signed = match raw_tx::parse(&text) {
+            Ok(Some(signed)) => signed,
+            Ok(None) => {
+                self.state.output.push_info("Broadcast cancelled");
+                return;
+            }
+            Err(e) => {
+                self.add_log_card(format!(
+                    "Failed: Broadcast raw tx\n\n{e:#}\n\nYour text is kept; choose \"Broadcast raw tx\" again to fix it."
+                ));
+                self.broadcast_draft = Some(text);
+                return;
+            }
+        };
+        if matches!(self.state.connection, ConnectionStatus::Disconnected) {
+            self.add_log_card("Cannot broadcast: not connected to RPC".to_string());
+            self.broadcast_draft = Some(text);
+            return;
+        }
+        if let (Some(signed_for), Some(connected)) = (signed.chain_id(), self.state.chain_id) {
+            if signed_for != connected {
+                self.add_log_card(format!(
+                    "Failed: Broadcast raw tx\n\nThe transaction is signed for chain {signed_for}, but the node is on chain {connected}."
+                ));
+                self.broadcast_draft = Some(text);
+                return;
+            }
+        }
+        self.spawn_raw_transaction(signed);
+    }
+
+    /// Open the editor of a signing tool on `template`, or on the text that last failed to parse
+    fn open_sign_tool(&mut self, tool: SignTool, template: String) {
+        let text = match self.sign_draft.take() {
+            Some((draft_tool, draft)) if draft_tool == tool => draft,
+            _ => template,
+        };
+        self.sign_requested = Some((tool, text));
+    }
+
+    /// Typed data editor text for the contract at `address`, from the structs in its ABI
+    async fn typed_data_template(&self, address: Address) -> String {
+        let abi = self.find_abi_for_address(address).unwrap_or_default();
+        let name = self
+            .store
+            .contract_for_address(address)
+            .map(|id| id.name)
+            .or_else(|| self.store.get_attached(address).map(|a| a.name.clone()))
+            .unwrap_or_default();
+        self.typed_data_template_for(&abi, Some(address), &name).await
+    }
+
+    /// Typed data editor text for `abi`, with the domain of the contract at `address`. The
+    /// permit owner is the private key's account, which is what signs.
+    async fn typed_data_template_for(&self, abi: &JsonAbi, address: Option<Address>, name: &str) -> String {
+        let owner = self.key_address;
+        let connected = matches!(self.state.connection, ConnectionStatus::Connected);
+        let (domain, nonce) = match address {
+            Some(address) if connected => (
+                signing::contract_domain(&self.provider, abi, address, self.state.chain_id, name).await,
+                signing::permit_nonce(&self.provider, abi, address, owner).await,
+            ),
+            _ => {
+                let mut domain = serde_json::Map::new();
+                domain.insert("name".into(), serde_json::json!(name));
+                domain.insert("version".into(), serde_json::json!("1"));
+                if let Some(chain_id) = self.state.chain_id {
+                    domain.insert("chainId".into(), serde_json::json!(chain_id.to_string()));
+                }
+                let contract = address.unwrap_or_default();
+                domain.insert("verifyingContract".into(), serde_json::json!(format!("{contract:?}")));
+                (domain, None)
+            }
+        };
+        signing::typed_data_template(abi, domain, owner, nonce)
+    }
+
+    /// Sign or verify what was written in a signing tool's editor
+    fn finish_signing(&mut self, tool: SignTool, text: String) {
+        let title = tool.title();
+        let parsed = match tool {
+            SignTool::Message => Ok(signing::parse_message(&text).map(|payload| (payload, None))),
+            SignTool::TypedData => signing::parse_typed_data(&text).map(|payload| payload.map(|payload| (payload, None))),
+            SignTool::Verify => signing::parse_verify(&text).map(|request| {
+                request.map(|request| (request.payload, Some((request.signature, request.expected))))
+            }),
+        };
+        let (payload, verify) = match parsed {
+            Ok(Some(parsed)) => parsed,
+            Ok(None) => {
+                self.state.output.push_info(format!("{title} cancelled"));
+                return;
+            }
+            Err(e) => {
+                self.add_log_card(format!(
+                    "Failed: {title}\n\n{e:#}\n\nYour text is kept; choose \"{title}\" again to fix it."
+                ));
+                self.sign_draft = Some((tool, text));
+                return;
+            }
+        };
+        match verify {
+            Some((signature, expected)) => self.verify_signature(payload, &signature, expected),
+            None => self.sign_payload(payload),
+        }
+    }
+
+    /// Sign a message or typed data with the private key and copy the signature
+    fn sign_payload(&mut self, payload: Payload) {
+        let scheme = match payload {
+            Payload::Message(_) => "personal_sign",
+            Payload::TypedData(_) => "EIP-712",
+        };
+        let signed = match signing::sign(&self.signer, payload) {
+            Ok(signed) => signed,
+            Err(e) => {
+                self.add_log_card(format!("Failed: Sign ({scheme})\n\n{e:#}"));
+                return;
+            }
+        };
+        let mut message = format!("Signed ({scheme}) by {:?}\n\n{signed}\n", self.key_address);
+        match copy_to_clipboard(&signed.signature_hex()) {
+            Ok(()) => message.push_str("\nSignature copied to clipboard"),
+            Err(e) => log::warn!("[SIGN] Failed to copy the signature: {e}"),
+        }
+        if self.state.node_account.is_some() {
+            message.push_str("\nSigned with the private key, not the selected node account");
+        }
+        self.state.output.push_success(format!("Signature: {}", signed.signature_hex()));
+        self.state.output.push_separator();
+        self.state.output.scroll_to_bottom();
+        self.add_log_card(message);
+    }
+
+    /// Recover who signed `payload` and compare it with the expected signer
+    fn verify_signature(&mut self, payload: Payload, signature: &alloy::primitives::Signature, expected: Option<Address>) {
+        let recovered = match signing::recover(signature, &payload) {
+            Ok(recovered) => recovered,
+            Err(e) => {
+                self.add_log_card(format!("Failed: Verify signature\n\n{e:#}"));
+                return;
+            }
+        };
+        let mut signer = format!("{recovered:?}");
+        if recovered == self.key_address {
+            signer.push_str(" (your account)");
+        } else if let Some(name) = self.store.contract_for_address(recovered).map(|id| id.name) {
+            signer.push_str(&format!(" ({name} contract)"));
+        }
+        let headline = match expected {
+            Some(expected) if expected == recovered => {
+                self.state.output.push_success(format!("Signature valid: signed by {recovered:?}"));
+                "Valid signature".to_string()
+            }
+            Some(expected) => {
+                self.state.output.push_error(format!("Signature not by {expected:?}: signed by {recovered:?}"));
+                format!("Failed: Verify signature\n\nNot signed by {expected:?}")
+            }
+            None => {
+                self.state.output.push_info(format!("Signed by {recovered:?}"));
+                "Recovered signer".to_string()
+            }
+        };
+        let hash = payload.hash().map(|hash| format!("{hash:?}")).unwrap_or_default();
+        self.state.output.push_separator();
+        self.state.output.scroll_to_bottom();
+        self.add_log_card(format!("{headline}\n\nSigner: {signer}\n{payload}\nHash: {hash}"));
+    }
+
+    /// Show where an instance came from, and its proxy slots if it is a proxy
+    fn show_instance_details(&mut self, address: Address, contract_name: &str, contract_path: PathBuf) {
+        let contract_id = ContractId::new(contract_path, contract_name.to_string());
+        let record = self
+            .store
+            .get_deployment(&contract_id, self.state.chain_id, address)
+            .cloned()
+            .unwrap_or_else(|| DeploymentRecord::new(address));
+        let mut details = format!("{contract_name} deployment\n\n{record}");
+        if let Some(info) = self.store.get_proxy(self.state.chain_id, address) {
+            details.push_str(&format!("\n\nERC-1967 proxy\n{info}"));
+        }
+        self.add_log_card(details);
+    }
+
+    fn handle_pending_transactions_key(&mut self, key: KeyEvent) {
+        let ids = self.pending_transaction_ids();
+        let PopupState::PendingTransactions { selected } = &mut self.state.popup else {
+            return;
+        };
+        // Transactions get confirmed while the list is open
+        *selected = (*selected).min(ids.len().saturating_sub(1));
+        match key.code {
+            KeyCode::Esc => {
+                self.state.popup = PopupState::None;
+                self.state.focus = Focus::Output;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                *selected = selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') if *selected + 1 < ids.len() => {
+                *selected += 1;
+            }
+            KeyCode::Char(key @ ('s' | 'x')) => {
+                if let Some(&id) = ids.get(*selected) {
+                    self.replace_transaction(id, key == 'x');
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Show or hide the RPC inspector; it takes focus when shown
+    fn toggle_rpc_inspector(&mut self) {
+        if self.state.rpc_inspector.take().is_some() {
+            if self.state.focus == Focus::RpcInspector {
+                self.state.focus = Focus::Sidebar;
+            }
+            self.state.last_action = Some("Close RPC inspector".into());
+        } else {
+            self.state.rpc_inspector = Some(RpcInspectorState::default());
+            self.state.focus = Focus::RpcInspector;
+            self.state.last_action = Some("Open RPC inspector".into());
+        }
+    }
+
+    /// Show the dashboard of the instance at `address`, or hide it if it is the one shown
+    async fn toggle_dashboard(&mut self, address: Address) {
+        if self.state.dashboard.as_ref().is_some_and(|d| d.address == address) {
+            self.close_dashboard();
+            return;
+        }
+        let abi = self.find_abi_for_address(address).unwrap_or_default();
+        let name = self
+            .store
+            .contract_for_address(address)
+            .map(|id| id.name)
+            .or_else(|| self.store.get_attached(address).map(|a| a.name.clone()))
+            .unwrap_or_else(|| "Unknown".to_string());
+        self.state.last_action = Some(format!("Open dashboard: {name}"));
+        self.state.dashboard = Some(Dashboard::new(address, name, &abi));
+        self.state.focus = Focus::Dashboard;
+        self.refresh_dashboard().await;
+    }
+
+    fn close_dashboard(&mut self) {
+        self.state.dashboard = None;
+        if self.state.focus == Focus::Dashboard {
+            self.state.focus = Focus::Sidebar;
+        }
+        self.state.last_action = Some("Close dashboard".into());
+    }
+
+    /// Read the dashboard's values again in the background. A refresh asked for while one
+    /// is running follows it, so a burst of blocks costs at most two reads.
+    async fn refresh_dashboard(&mut self) {
+        if !matches!(self.state.connection, ConnectionStatus::Connected) {
+            return;
+        }
+        match &mut self.state.dashboard {
+            None => return,
+            Some(dashboard) if dashboard.refreshing => {
+                dashboard.stale = true;
+                return;
+            }
+            Some(_) => {}
+        }
+        let batched = self.multicall_ready();
+        let Some(dashboard) = self.state.dashboard.as_mut() else {
+            return;
+        };
+        dashboard.refreshing = true;
+        dashboard.stale = false;
+        let provider = self.provider.clone();
+        let address = dashboard.address;
+        let getters = dashboard.getters.clone();
+        let updates = self.dashboard_tx.clone();
+        tokio::spawn(async move {
+            let reading = dashboard::read(&provider, address, &getters, batched).await;
+            let _ = updates.send(dashboard::Update { address, reading });
+        });
+    }
+
+    async fn poll_dashboard(&mut self) {
+        let updates: Vec<dashboard::Update> = self.dashboard_rx.try_iter().collect();
+        for update in updates {
+            let Some(dashboard) = self.state.dashboard.as_mut() else {
+                return;
+            };
+            // A refresh of a dashboard closed since
+            if dashboard.address != update.address {
+                continue;
+            }
+            dashboard.refreshing = false;
+            match update.reading {
+                Ok(reading) => dashboard.apply(reading),
+                Err(e) => {
+                    log::warn!("[DASHBOARD] refresh of {:?} failed: {e:#}", update.address);
+                    dashboard.error = Some(format!("{e:#}"));
+                }
+            }
+            if dashboard.stale {
+                self.refresh_dashboard().await;
+            }
+        }
+    }
+
+    async fn handle_dashboard_key(&mut self, key: KeyEvent) {
+        let Some(dashboard) = self.state.dashboard.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => dashboard.scroll = dashboard.scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') if dashboard.scroll + 1 < dashboard.rows.len() => dashboard.scroll += 1,
+            KeyCode::Char('r') => self.refresh_dashboard().await,
+            KeyCode::Tab => self.state.focus = Focus::Sidebar,
+            KeyCode::Esc => self.close_dashboard(),
+            _ => {}
+        }
+    }
+
+    fn handle_rpc_inspector_key(&mut self, key: KeyEvent) {
+        let Some(inspector) = self.state.rpc_inspector.as_mut() else {
+            return;
+        };
+
+        if inspector.editing_filter {
+            match key.code {
+                KeyCode::Char(c) => inspector.filter.push(c),
+                KeyCode::Backspace => {
+                    inspector.filter.pop();
+                }
+                KeyCode::Enter => inspector.editing_filter = false,
+                KeyCode::Esc => {
+                    inspector.filter.clear();
+                    inspector.editing_filter = false;
+                }
+                _ => {}
+            }
+            inspector.selected = None;
+            return;
+        }
+
+        let exchanges = self.rpc_log.snapshot(&inspector.filter);
+        let current = rpc_inspector::selected_index(inspector, &exchanges);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                if let Some(i) = current {
+                    inspector.selected = Some(exchanges[i.saturating_sub(1)].id);
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                // Moving past the newest follows new traffic again
+                inspector.selected = match current {
+                    Some(i) if i + 2 < exchanges.len() => Some(exchanges[i + 1].id),
+                    _ => None,
+                };
+            }
+            KeyCode::End | KeyCode::Char('G') => inspector.selected = None,
+            KeyCode::Char('/') => inspector.editing_filter = true,
+            KeyCode::Delete => {
+                inspector.selected = None;
+                self.rpc_log.clear();
+            }
+            KeyCode::Tab => {
+                self.state.focus = if self.state.dashboard.is_some() { Focus::Dashboard } else { Focus::Sidebar };
+            }
+            KeyCode::Esc => self.toggle_rpc_inspector(),
+            KeyCode::Char('c') => {
+                let Some(exchange) = current.map(|i| &exchanges[i]) else {
+                    return;
+                };
+                let command = rpc_log::replay_command(self.endpoints.active(), &exchange.request);
+                match copy_to_clipboard(&command) {
+                    Ok(_) => {
+                        // Not logged: headers may carry credentials
+                        log::info!("Copied {} request as a shell command", exchange.method);
+                        self.state.output.push_success(format!("Copied {} request", exchange.method));
+                    }
+                    Err(e) => {
+                        log::error!("Clipboard error: {e}");
+                        self.state.output.push_error(format!("Failed to copy: {e}"));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    async fn handle_account_selector_key(&mut self, key: KeyEvent) {
+        let PopupState::AccountSelector { accounts, selected } = &mut self.state.popup else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                self.state.popup = PopupState::None;
+                self.state.focus = Focus::Sidebar;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                *selected = selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') if *selected + 1 < accounts.len() => {
+                *selected += 1;
+            }
+            KeyCode::Enter => {
+                let choice = accounts[*selected].0;
+                self.state.popup = PopupState::None;
+                self.state.focus = Focus::Sidebar;
+                match choice {
+                    AccountChoice::PrivateKey(_) => self.use_account(None).await,
+                    AccountChoice::Node(address) => {
+                        self.use_account(Some(NodeAccount { address, impersonated: false })).await
+                    }
+                    AccountChoice::Impersonate => {
+                        self.state.popup = PopupState::AddressInput {
+                            address: String::new(),
+                            error: None,
+                            kind: AddressInputKind::Impersonate,
+                        };
+                        self.state.focus = Focus::CommandPalette;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_proxy_kind_selector_key(&mut self, key: KeyEvent) {
+        let PopupState::ProxyKindSelector { selected, .. } = &mut self.state.popup else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                self.state.popup = PopupState::None;
+                self.state.focus = Focus::Sidebar;
+                self.pending_action = PendingAction::None;
+            }
+            KeyCode::Up if *selected > 0 => {
+                *selected -= 1;
+            }
+            KeyCode::Down if *selected + 1 < ProxyKind::DEPLOYABLE.len() => {
+                *selected += 1;
+            }
+            KeyCode::Enter => {
+                let kind = ProxyKind::DEPLOYABLE[*selected];
+                let PendingAction::Deploy { abi, mode, .. } = &mut self.pending_action else {
+                    return;
+                };
+                *mode = DeployMode::Proxy(kind);
+
+                // The implementation is initialized through the proxy
+                let params = proxy::initializer(abi)
+                    .map(|function| function.inputs.clone())
+                    .unwrap_or_default();
+                let fields = params.iter().map(|_| FieldState::default()).collect();
+                self.state.popup = PopupState::ParameterPopup {
+                    method_name: "initialize".to_string(),
+                    params,
+                    fields,
+                    current: 0,
+                    bytecode_target: Some(BytecodeTarget::Evm),
+                    call_options: false,
+                };
+            }
+            _ => {}
+        }
+    }
+
+    #[allow(clippy::collapsible_match)]
+    async fn handle_contract_selector_key(&mut self, key: KeyEvent) -> Result<()> {
+        if let PopupState::ContractSelector { contracts, selected } = &mut self.state.popup {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.popup = PopupState::None;
+                    self.state.focus = Focus::Sidebar;
+                }
+                KeyCode::Up => {
+                    if *selected > 0 {
+                        *selected -= 1;
+                    }
+                }
+                KeyCode::Down => {
+                    if *selected + 1 < contracts.len() {
+                        *selected += 1;
+                    }
+                }
+                KeyCode::Enter => {
+                    if let Some(name) = contracts.get(*selected).cloned() {
+                        self.state.popup = PopupState::None;
+                        self.state.focus = Focus::Sidebar;
+                        self.select_compiled_contract(&name)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    async fn execute_tree_node(&mut self, node: TreeNode) -> Result<()> {
+        log::info!("[ACTION] execute_tree_node: {}", node.label());
+        match node {
+            TreeNode::NewContract => {
+                self.state.popup = PopupState::FilePicker {
+                    path: String::new(),
+                    error: None,
+                    kind: FilePickerKind::Contract,
+                };
+                self.state.focus = Focus::CommandPalette;
+                self.update_file_picker_suggestions("");
+            }
+            TreeNode::AttachBySignature => {
+                self.attach_requested = true;
+            }
+            TreeNode::Contract { path, name, .. } => {
+                // Toggle expansion for this contract (path is already canonicalized from tree)
+                let key = (path.clone(), name.clone());
+                if self.state.sidebar.expanded_contracts.contains(&key) {
+                    self.state.sidebar.expanded_contracts.remove(&key);
+                } else {
+                    self.state.sidebar.expanded_contracts.insert(key);
+                }
+            }
+            TreeNode::Constructor { contract_name, contract_path, abi } => {
                 // Deploy directly using node data - no need to set app state
                 self.start_deploy(contract_name, contract_path, abi).await;
             }
+            TreeNode::DeployBehindProxy { contract_name, contract_path, abi } => {
+                self.start_proxy_deploy(contract_name, contract_path, abi);
+            }
+            TreeNode::UpgradeImplementation { address, contract_name, contract_path, .. } => {
+                self.start_upgrade(address, contract_name, contract_path).await;
+            }
             TreeNode::LoadExistingInstance { contract_name, contract_path, abi } => {
                 // Set this as current contract for loading instance
                 let compiled = CompiledContract {
                     name: contract_name,
                     abi: (*abi).clone(),
                     bytecode: Vec::new(),
+                    diagnostics: Vec::new(),
                 };
                 self.set_contract(compiled, contract_path);
                 self.state.popup = PopupState::AddressInput {
                     address: String::new(),
                     error: None,
+                    kind: AddressInputKind::Instance,
                 };
                 self.state.focus = Focus::CommandPalette;
             }
-            TreeNode::DeployedInstance { address, .. } => {
+            TreeNode::CompilerSettings { contract_name, contract_path } => {
+                let contract_id = ContractId::new(contract_path.clone(), contract_name.clone());
+                let fields = self
+                    .store
+                    .get_compiler_settings(&contract_id)
+                    .to_fields()
+                    .into_iter()
+                    .map(|value| FieldState { value, error: None })
+                    .collect();
+                self.state.popup = PopupState::CompilerSettings {
+                    contract_name,
+                    contract_path,
+                    fields,
+                    current: 0,
+                };
+                self.state.focus = Focus::CommandPalette;
+            }
+            TreeNode::DeployedInstance { address, .. } | TreeNode::AttachedInstance { address, .. } => {
                 // Toggle expand only
                 if self.state.sidebar.expanded_instances.contains(&address) {
                     self.state.sidebar.expanded_instances.remove(&address);
@@ -1311,20 +3392,55 @@ impl<P: Provider + Clone> App<P> {
                     self.state.sidebar.expanded_instances.insert(address);
                 }
             }
+            TreeNode::SizeReport { contract_name, contract_path } => {
+                let label = format!("Measuring {contract_name} (EVM + PVM)");
+                self.state.compiling.push(label.clone());
+                let settings = self
+                    .store
+                    .get_compiler_settings(&ContractId::new(contract_path.clone(), contract_name.clone()));
+                let tx = self.compile_tx.clone();
+                tokio::task::spawn_blocking(move || {
+                    let report = SizeReport::build(&contract_path, &contract_name, &settings);
+                    let _ = tx.send(CompileOutcome::SizeReport { label, report });
+                });
+            }
+            TreeNode::InstanceDetails { address, contract_name, contract_path } => {
+                self.show_instance_details(address, &contract_name, contract_path);
+            }
+            TreeNode::VerifyBytecode { address, contract_name, contract_path } => {
+                self.verify_bytecode(address, contract_name, contract_path).await;
+            }
             TreeNode::Method {
                 function,
                 instance_address,
                 ..
             } => {
                 // Call directly using node data - no need to set app state
-                self.start_call_function(function, instance_address).await;
+                self.start_call_function(function, instance_address, false).await;
+            }
+            TreeNode::RawCall { address } => {
+                self.start_raw_call("Raw call", Some(address), "0x", false);
+            }
+            TreeNode::SignTypedData { address } => {
+                let template = self.typed_data_template(address).await;
+                self.open_sign_tool(SignTool::TypedData, template);
+            }
+            TreeNode::ReadGetters { address, .. } => {
+                self.read_getters(address).await;
+            }
+            TreeNode::Fallback { instance_address, .. } => {
+                self.start_raw_call("fallback()", Some(instance_address), "0x", true);
+            }
+            TreeNode::Receive { instance_address } => {
+                // receive() only runs for empty calldata
+                self.start_raw_call("receive()", Some(instance_address), "", true);
             }
         }
         Ok(())
     }
 
     async fn execute_command(&mut self, command_idx: usize) -> Result<()> {
-        let command_names = ["Edit config", "Clear output", "Open Logs", "Clear Logs", "Reconnect", "Toggle Debug", "Reset", "Quit"];
+        let command_names = ["Edit config", "Clear output", "Open Logs", "Clear Logs", "Reconnect", "Toggle Debug", "Compiler diagnostics", "Clear build cache", "Export size report", "Export address book", "Run deployment manifest", "Import forge broadcast", "Prune dead instances", "Raw call", "Pending transactions", "RPC inspector", "Select account", "Broadcast raw tx", "Sign message", "Sign typed data", "Verify signature", "Reset", "Quit"];
         let cmd_name = command_names.get(command_idx).unwrap_or(&"Unknown");
         log::info!("[COMMAND] execute_command: {} (idx={})", cmd_name, command_idx);
         match command_idx {
@@ -1363,7 +3479,9 @@ impl<P: Provider + Clone> App<P> {
                 }
             }
             4 => {
+                self.endpoints.reset();
                 self.try_connect().await;
+                self.poll_endpoint();
             }
             5 => {
                 self.state.debug_mode = !self.state.debug_mode;
@@ -1371,9 +3489,92 @@ impl<P: Provider + Clone> App<P> {
                 self.state.last_action = Some(format!("Debug {}", status));
             }
             6 => {
-                self.clear_state();
+                self.show_diagnostics();
             }
             7 => {
+                match crate::compile_cache::CompileCache::default_location().clear() {
+                    Ok(removed) => {
+                        self.abi_cache.borrow_mut().clear();
+                        self.state.output.push_success(format!("Build cache cleared ({removed} entries)"));
+                    }
+                    Err(e) => {
+                        self.state.output.push_error(format!("Failed to clear build cache: {e}"));
+                    }
+                }
+            }
+            8 => match &self.last_size_report {
+                Some(report) => match report.export_markdown(&size_report::default_reports_dir()) {
+                    Ok(path) => {
+                        self.add_log_card(format!("Size report written to {}", path.display()));
+                    }
+                    Err(e) => {
+                        self.add_log_card(format!("Failed to export size report: {e}"));
+                    }
+                },
+                None => {
+                    self.add_log_card(
+                        "No size report yet. Run \"Size report (EVM vs PVM)\" on a contract first.".to_string(),
+                    );
+                }
+            },
+            9 => match self.export_address_book(&address_book::default_export_dir(), ExportFormats::ALL) {
+                Ok(summary) => self.add_log_card(summary),
+                Err(e) => self.add_log_card(format!("Failed: Export address book\n\n{e:#}")),
+            },
+            10 => {
+                self.state.popup = PopupState::FilePicker {
+                    path: String::new(),
+                    error: None,
+                    kind: FilePickerKind::Manifest,
+                };
+                self.state.focus = Focus::CommandPalette;
+                self.update_file_picker_suggestions("");
+            }
+            11 => {
+                self.state.popup = PopupState::FilePicker {
+                    path: String::new(),
+                    error: None,
+                    kind: FilePickerKind::Broadcast,
+                };
+                self.state.focus = Focus::CommandPalette;
+                self.update_file_picker_suggestions("");
+            }
+            12 => {
+                self.prune_dead_instances();
+            }
+            13 => {
+                self.start_raw_call("Raw call", None, "0x", false);
+            }
+            14 => {
+                self.state.popup = PopupState::PendingTransactions { selected: 0 };
+                self.state.focus = Focus::CommandPalette;
+            }
+            15 => {
+                self.toggle_rpc_inspector();
+            }
+            16 => {
+                self.open_account_selector().await;
+            }
+            17 => {
+                self.broadcast_requested = true;
+            }
+            18 => {
+                self.open_sign_tool(SignTool::Message, signing::MESSAGE_TEMPLATE.to_string());
+            }
+            19 => {
+                let template = match self.address {
+                    Some(address) => self.typed_data_template(address).await,
+                    None => self.typed_data_template_for(&JsonAbi::new(), None, "").await,
+                };
+                self.open_sign_tool(SignTool::TypedData, template);
+            }
+            20 => {
+                self.open_sign_tool(SignTool::Verify, signing::VERIFY_TEMPLATE.to_string());
+            }
+            21 => {
+                self.clear_state();
+            }
+            22 => {
                 self.running = false;
             }
             _ => {}
@@ -1383,8 +3584,27 @@ impl<P: Provider + Clone> App<P> {
 
     async fn load_contract_from_path(&mut self, path: PathBuf) -> Result<()> {
         log::info!("[LOAD] load_contract_from_path: {:?}", path);
-        match crate::compile::load_contract_abi(&path) {
-            Ok(contracts) => {
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        let label = format!("Compiling {file_name}");
+        self.state.compiling.push(label.clone());
+
+        let tx = self.compile_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let result = crate::compile::load_contract_abi(&path);
+            let _ = tx.send(CompileOutcome::Load { label, path, result, open: true });
+        });
+        Ok(())
+    }
+
+    /// Finish loading a contract file once its ABIs have been compiled
+    fn finish_load_contract(&mut self, path: PathBuf, result: Result<LoadedAbis>) {
+        match result {
+            Ok(loaded) => {
+                let contracts = loaded.contracts;
+                self.report_build_warnings(loaded.diagnostics, &path.display().to_string());
                 if contracts.len() == 1 {
                     let (name, abi) = contracts.into_iter().next().unwrap();
                     // Create a contract with just ABI (bytecode will be compiled on demand)
@@ -1392,6 +3612,7 @@ impl<P: Provider + Clone> App<P> {
                         name,
                         abi,
                         bytecode: Vec::new(), // Will be filled during deploy
+                        diagnostics: Vec::new(),
                     };
                     self.set_contract(contract, path);
                 } else {
@@ -1406,23 +3627,178 @@ impl<P: Provider + Clone> App<P> {
                 }
             }
             Err(e) => {
-                let error_msg = format!("Failed to load contract: {e}");
-                log::error!("{error_msg}");
-                self.state.output.push_error(error_msg);
+                let error_msg = format!("Failed to load contract: {e}");
+                log::error!("{error_msg}");
+                self.state.output.push_error(&error_msg);
+                self.add_log_card(format!("Failed: Load {}\n\n{error_msg}", path.display()));
+                self.report_build_error(&e);
+            }
+        }
+    }
+
+    /// Drain finished background compilations and continue the work that was waiting on them
+    async fn poll_compilations(&mut self) {
+        while let Ok(outcome) = self.compile_rx.try_recv() {
+            let label = match &outcome {
+                CompileOutcome::Load { label, .. }
+                | CompileOutcome::Deploy { label, .. }
+                | CompileOutcome::Manifest { label, .. }
+                | CompileOutcome::SizeReport { label, .. }
+                | CompileOutcome::Verify { label, .. } => label,
+            };
+            if let Some(pos) = self.state.compiling.iter().position(|l| l == label) {
+                self.state.compiling.remove(pos);
+            }
+
+            match outcome {
+                CompileOutcome::Load { path, result, open, .. } => {
+                    let entry = Self::cached_abis(result.as_ref(), &path);
+                    self.abi_cache.borrow_mut().insert(path.clone(), entry);
+                    if open {
+                        self.finish_load_contract(path, result);
+                    }
+                }
+                CompileOutcome::Deploy { contract_name, contract_path, args, target, settings, plan, result, proxy_code, .. } => {
+                    self.deploy_compiled(contract_name, contract_path, args, target, settings, plan, *result, proxy_code.map(|code| *code)).await;
+                }
+                CompileOutcome::Manifest { path, manifest, settings, compiled, .. } => {
+                    let title = format!("Manifest {}", path.display());
+                    match self.manifest_run(path, *manifest, settings, compiled) {
+                        Ok(run) => self.spawn_flow(title, run),
+                        Err(e) => self.add_log_card(format!("Failed: {title}\n\n{e:#}")),
+                    }
+                }
+                CompileOutcome::SizeReport { report, .. } => {
+                    self.add_log_card(report.to_text());
+                    self.last_size_report = Some(report);
+                }
+                CompileOutcome::Verify { address, contract_name, target, onchain, result, .. } => {
+                    self.finish_verify_bytecode(address, &contract_name, target, &onchain, result);
+                }
+            }
+        }
+    }
+
+    /// Load a manifest and compile all of its contracts in the background
+    fn start_manifest(&mut self, path: PathBuf) {
+        let (manifest, settings) = match self.load_manifest(&path) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                self.add_log_card(format!("Failed: Manifest {}\n\n{e:#}", path.display()));
+                return;
+            }
+        };
+        let path = path.canonicalize().unwrap_or(path);
+        self.state.output.push_normal(format!("\nRunning manifest {}...", path.display()));
+
+        let label = format!("Compiling manifest ({} contracts)", manifest.deployments.len());
+        self.state.compiling.push(label.clone());
+        let tx = self.compile_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let compiled = manifest.compile(&settings);
+            let _ = tx.send(CompileOutcome::Manifest {
+                label,
+                path,
+                manifest: Box::new(manifest),
+                settings,
+                compiled,
+            });
+        });
+    }
+
+    /// Run a manifest without the TUI (`evm-cli --manifest`), printing the output panel
+    pub async fn run_manifest_headless(&mut self, path: &Path) -> Result<()> {
+        let (manifest, settings) = self.load_manifest(path)?;
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+        let (manifest, settings, compiled) = tokio::task::spawn_blocking(move || {
+            let compiled = manifest.compile(&settings);
+            (manifest, settings, compiled)
+        })
+        .await?;
+        let result = match self.manifest_run(path, manifest, settings, compiled) {
+            Ok(run) => {
+                // No main loop here, so handle the run's transactions until it is done
+                let mut run = tokio::spawn(run);
+                let result = loop {
+                    tokio::select! {
+                        joined = &mut run => break joined?,
+                        _ = tokio::time::sleep(std::time::Duration::from_millis(50)) => self.poll_transactions().await,
+                    }
+                };
+                self.poll_transactions().await;
+                result
             }
+            Err(e) => Err(e),
+        };
+
+        for line in &self.state.output.lines {
+            println!("{}", line.text);
         }
+        println!("{}", result?);
         Ok(())
     }
 
+    /// Parse a manifest and look up each step's compiler settings
+    fn load_manifest(&self, path: &Path) -> Result<(Manifest, Vec<CompilerSettings>)> {
+        let manifest = Manifest::load(path)?;
+        let settings = manifest
+            .deployments
+            .iter()
+            .map(|step| {
+                self.store
+                    .get_compiler_settings(&ContractId::new(step.path.clone(), step.contract.clone()))
+            })
+            .collect();
+        Ok((manifest, settings))
+    }
+
+    /// Check that every contract of a manifest compiled and look up the steps this manifest
+    /// already completed on the current chain. Returns the run, to go in the background.
+    fn manifest_run(
+        &mut self,
+        path: PathBuf,
+        manifest: Manifest,
+        settings: Vec<CompilerSettings>,
+        compiled: Vec<Result<CompiledContract>>,
+    ) -> Result<impl Future<Output = Result<String>> + Send + 'static> {
+        let chain_id = match (self.state.connection, self.state.chain_id) {
+            (ConnectionStatus::Connected, Some(chain_id)) => chain_id,
+            _ => anyhow::bail!("Not connected to RPC"),
+        };
+
+        let mut steps = Vec::with_capacity(manifest.deployments.len());
+        for ((step, compiled), settings) in manifest.deployments.iter().zip(compiled).zip(settings) {
+            let target = manifest.step_target(step);
+            let compiled = match compiled {
+                Ok(compiled) => compiled,
+                Err(e) => {
+                    self.report_build_error(&e);
+                    return Err(e.context(format!("{}: compiling {} ({target}) failed", step.name, step.contract)));
+                }
+            };
+            steps.push(ManifestStepRun {
+                deployed: self.store.get_manifest_deployment(chain_id, &path, &step.name),
+                calls_sent: self.store.get_manifest_calls(chain_id, &path, &step.name),
+                step: step.clone(),
+                target,
+                settings,
+                compiled,
+            });
+        }
+        Ok(run_manifest(self.flow_sender(), chain_id, path, steps, self.signer_address))
+    }
+
     fn select_compiled_contract(&mut self, name: &str) -> Result<()> {
         // Load just the ABI for the selected contract
         if let Some(path) = self.contract_path.clone() {
-            let contracts = crate::compile::load_contract_abi(&path)?;
+            let contracts = crate::compile::load_contract_abi(&path)?.contracts;
             if let Some((_, abi)) = contracts.into_iter().find(|(n, _)| n == name) {
                 let contract = CompiledContract {
                     name: name.to_string(),
                     abi,
                     bytecode: Vec::new(), // Will be filled during deploy
+                    diagnostics: Vec::new(),
                 };
                 self.set_contract(contract, path);
             }
@@ -1440,13 +3816,15 @@ impl<P: Provider + Clone> App<P> {
             .map(|ctor| ctor.inputs.clone())
             .unwrap_or_default();
 
-        let fields: Vec<FieldState> = params.iter().map(|_| FieldState::default()).collect();
+        // One field per parameter, plus the optional CREATE2 salt
+        let fields: Vec<FieldState> = (0..=params.len()).map(|_| FieldState::default()).collect();
 
         // Always show popup with target selector for deploy operations
         self.pending_action = PendingAction::Deploy {
             contract_name,
             contract_path,
             abi,
+            mode: DeployMode::Instance,
         };
         self.state.popup = PopupState::ParameterPopup {
             method_name: "constructor".to_string(),
@@ -1454,15 +3832,200 @@ impl<P: Provider + Clone> App<P> {
             fields,
             current: 0,
             bytecode_target: Some(BytecodeTarget::Evm), // Default to EVM
+            call_options: false,
+        };
+    }
+
+    /// Ask for the proxy kind; the implementation's `initialize` parameters come next
+    fn start_proxy_deploy(&mut self, contract_name: String, contract_path: PathBuf, abi: Arc<JsonAbi>) {
+        if abi.constructor.as_ref().is_some_and(|ctor| !ctor.inputs.is_empty()) {
+            self.add_log_card(format!(
+                "Failed: Deploy {contract_name} behind proxy\n\nThe constructor takes parameters, but a proxy only runs initialize(). Move the setup into an initializer."
+            ));
+            return;
+        }
+
+        self.state.output.push_normal(format!("\nPreparing to deploy {contract_name} behind a proxy..."));
+        self.pending_action = PendingAction::Deploy {
+            contract_name: contract_name.clone(),
+            contract_path,
+            abi,
+            mode: DeployMode::Proxy(ProxyKind::Uups),
+        };
+        self.state.popup = PopupState::ProxyKindSelector {
+            contract_name,
+            selected: 0,
+        };
+        self.state.focus = Focus::CommandPalette;
+    }
+
+    /// Re-read the proxy's slots, then ask which loaded contract becomes the new
+    /// implementation (`handle_implementation_selector_key`), starting at the proxy's own
+    async fn start_upgrade(&mut self, proxy_address: Address, contract_name: String, contract_path: PathBuf) {
+        if matches!(self.state.connection, ConnectionStatus::Disconnected) {
+            self.add_log_card("Cannot upgrade: not connected to RPC".to_string());
+            return;
+        }
+
+        let info = match proxy::detect(&self.provider, proxy_address).await {
+            Ok(Some(info)) => info,
+            Ok(None) => {
+                self.store.set_proxy(self.state.chain_id, proxy_address, None);
+                let _ = self.store.save();
+                self.add_log_card(format!("Failed: Upgrade {contract_name}\n\n{proxy_address:?} is no longer an ERC-1967 proxy"));
+                return;
+            }
+            Err(e) => {
+                self.add_log_card(format!("Failed: Upgrade {contract_name}\n\n{e:#}"));
+                return;
+            }
+        };
+        let target = match self.provider.get_code_at(proxy_address).await {
+            Ok(code) if !code.is_empty() => verify::detect_target(&code),
+            _ => BytecodeTarget::Evm,
+        };
+
+        self.state.output.push_normal(format!("\nPreparing to upgrade {contract_name} at {proxy_address:?} ({info})..."));
+        self.store.set_proxy(self.state.chain_id, proxy_address, Some(info.clone()));
+
+        let mut contracts: Vec<(String, PathBuf)> = self
+            .store
+            .all_contracts()
+            .into_iter()
+            .filter(|contract_id| !self.abi_loading(&contract_id.path))
+            .map(|contract_id| (contract_id.name, contract_id.path))
+            .collect();
+        contracts.sort();
+        let selected = contracts
+            .iter()
+            .position(|(name, path)| *name == contract_name && *path == contract_path)
+            .unwrap_or(0);
+        let abi = self.get_abi_for_contract(&ContractId::new(contract_path.clone(), contract_name.clone()));
+        self.pending_action = PendingAction::Deploy {
+            contract_name,
+            contract_path,
+            abi,
+            mode: DeployMode::Upgrade { proxy: proxy_address, info, target, call: None },
+        };
+        self.state.popup = PopupState::ImplementationSelector { proxy: proxy_address, contracts, selected };
+        self.state.focus = Focus::CommandPalette;
+    }
+
+    /// The chosen contract becomes the one deployed; next, ask what the proxy calls after
+    /// the upgrade. Only state-changing functions are offered.
+    fn handle_implementation_selector_key(&mut self, key: KeyEvent) {
+        let PopupState::ImplementationSelector { contracts, selected, .. } = &mut self.state.popup else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                self.state.popup = PopupState::None;
+                self.state.focus = Focus::Sidebar;
+                self.pending_action = PendingAction::None;
+            }
+            KeyCode::Up if *selected > 0 => {
+                *selected -= 1;
+            }
+            KeyCode::Down if *selected + 1 < contracts.len() => {
+                *selected += 1;
+            }
+            KeyCode::Enter => {
+                let Some((name, path)) = contracts.get(*selected).cloned() else {
+                    return;
+                };
+                let new_abi = self.get_abi_for_contract(&ContractId::new(path.clone(), name.clone()));
+                let functions = new_abi
+                    .functions()
+                    .filter(|function| !matches!(function.state_mutability, StateMutability::View | StateMutability::Pure))
+                    .cloned()
+                    .collect();
+                let PendingAction::Deploy { contract_name, contract_path, abi, .. } = &mut self.pending_action else {
+                    return;
+                };
+                *contract_name = name.clone();
+                *contract_path = path;
+                *abi = new_abi;
+                self.state.popup = PopupState::UpgradeCallSelector { contract_name: name, functions, selected: 0 };
+            }
+            _ => {}
+        }
+    }
+
+    /// Ask for the new implementation's constructor parameters followed by those of the
+    /// call chosen to run after the upgrade. The target defaults to the one the proxy is
+    /// running on.
+    fn handle_upgrade_call_selector_key(&mut self, key: KeyEvent) {
+        let PopupState::UpgradeCallSelector { functions, selected, .. } = &mut self.state.popup else {
+            return;
         };
+        match key.code {
+            KeyCode::Esc => {
+                self.state.popup = PopupState::None;
+                self.state.focus = Focus::Sidebar;
+                self.pending_action = PendingAction::None;
+            }
+            KeyCode::Up if *selected > 0 => {
+                *selected -= 1;
+            }
+            KeyCode::Down if *selected < functions.len() => {
+                *selected += 1;
+            }
+            KeyCode::Enter => {
+                let chosen = selected.checked_sub(1).and_then(|i| functions.get(i)).cloned();
+                let PendingAction::Deploy { abi, mode: DeployMode::Upgrade { target, call, .. }, .. } = &mut self.pending_action else {
+                    return;
+                };
+                let mut params = abi
+                    .constructor
+                    .as_ref()
+                    .map(|ctor| ctor.inputs.clone())
+                    .unwrap_or_default();
+                let method_name = match &chosen {
+                    Some(function) => {
+                        // Labelled with the function, to tell them from the constructor's
+                        params.extend(function.inputs.iter().enumerate().map(|(i, param)| {
+                            let name = if param.name.is_empty() { format!("arg{i}") } else { param.name.clone() };
+                            Param { name: format!("{}: {name}", function.name), ..param.clone() }
+                        }));
+                        format!("constructor, then {}", function.name)
+                    }
+                    None => "constructor".to_string(),
+                };
+                let bytecode_target = Some(*target);
+                *call = chosen;
+                let fields = params.iter().map(|_| FieldState::default()).collect();
+                self.state.popup = PopupState::ParameterPopup {
+                    method_name,
+                    params,
+                    fields,
+                    current: 0,
+                    bytecode_target,
+                    call_options: false,
+                };
+            }
+            _ => {}
+        }
     }
 
-    async fn start_call_function(&mut self, func: Function, address: Address) {
+    /// Call `func`, asking for its parameters first. View calls also take a block, `from` and
+    /// state overrides; `with_options` opens the popup even when there are no parameters, for
+    /// the call options or for "Sign only".
+    async fn start_call_function(&mut self, func: Function, address: Address, with_options: bool) {
         log::info!("[ACTION] start_call_function: {}() at {:?}", func.name, address);
-        if !func.inputs.is_empty() {
-            let fields: Vec<FieldState> = func
-                .inputs
-                .iter()
+        if self.state.sidebar.is_dead(&address) {
+            self.add_log_card(format!(
+                "Failed: {}() at {address:?}\n\nThis instance is gone from the connected node. Redeploy it, or remove it with Ctrl+P > Prune dead instances.",
+                func.name
+            ));
+            return;
+        }
+        let is_view = matches!(
+            func.state_mutability,
+            alloy::json_abi::StateMutability::View | alloy::json_abi::StateMutability::Pure
+        );
+        if !func.inputs.is_empty() || with_options {
+            let option_fields = if is_view { CALL_OPTION_FIELDS.len() } else { 0 };
+            let fields: Vec<FieldState> = (0..func.inputs.len() + option_fields)
                 .map(|_| FieldState::default())
                 .collect();
 
@@ -1476,12 +4039,127 @@ impl<P: Provider + Clone> App<P> {
                 fields,
                 current: 0,
                 bytecode_target: None, // No target selector for calls
+                call_options: is_view,
             };
             return;
         }
 
         // No parameters - call directly
-        self.do_call_function(&func, address, vec![]).await;
+        self.do_call_function(&func, address, vec![], CallOptions::default()).await;
+    }
+
+    /// Open the raw call popup. `send` picks a transaction over `eth_call` by default.
+    fn start_raw_call(&mut self, title: &str, to: Option<Address>, data: &str, send: bool) {
+        log::info!("[ACTION] start_raw_call: {title} at {to:?}");
+        let params = RAW_CALL_PARAMS
+            .iter()
+            .map(|param| alloy::json_abi::Param::parse(param).expect("valid raw call parameter"))
+            .collect();
+        let values = [
+            to.map(|address| format!("{address:?}")).unwrap_or_default(),
+            data.to_string(),
+            "0".to_string(),
+            send.to_string(),
+        ];
+        let fields: Vec<FieldState> = values
+            .into_iter()
+            .map(|value| FieldState { value, error: None })
+            .collect();
+        // Start on the first field worth editing: the address when missing, the value for a
+        // plain transfer to receive() (no calldata), else the calldata
+        let current = if to.is_none() { 0 } else if data.is_empty() { 2 } else { 1 };
+
+        self.pending_action = PendingAction::RawCall;
+        self.state.popup = PopupState::ParameterPopup {
+            method_name: title.to_string(),
+            params,
+            fields,
+            current,
+            bytecode_target: None,
+            call_options: false,
+        };
+    }
+
+    /// Send calldata and value from the raw call popup as `eth_call` or as a transaction
+    async fn do_raw_call(&mut self, title: &str, args: Vec<DynSolValue>) -> bool {
+        let [DynSolValue::Address(to), DynSolValue::Bytes(data), DynSolValue::Uint(value, _), DynSolValue::Bool(send)] =
+            args.as_slice()
+        else {
+            return false;
+        };
+        let (to, value, send) = (*to, *value, *send);
+        let data = Bytes::from(data.clone());
+        let call_str = self.describe_calldata(title, to, &data, value);
+        log::info!("[CALL] do_raw_call: {call_str} at {to:?} (send={send})");
+
+        if matches!(self.state.connection, ConnectionStatus::Disconnected) {
+            self.add_log_card(format!("Cannot send {title}: not connected to RPC"));
+            return false;
+        }
+
+        let tx = TransactionRequest::default()
+            .from(self.signer_address)
+            .to(to)
+            .input(data.into())
+            .value(value);
+
+        if send {
+            let contract_name = self
+                .store
+                .contract_for_address(to)
+                .map(|id| id.name)
+                .or_else(|| self.store.get_attached(to).map(|a| a.name.clone()))
+                .unwrap_or_else(|| "Unknown".to_string());
+            let title = title.to_string();
+            self.spawn_transaction(tx, TxPurpose::Call { address: to, contract_name, title, call_str });
+            return true;
+        }
+
+        let succeeded = match self.provider.call(tx).await {
+            Ok(result) => {
+                let result_str = if result.is_empty() {
+                    "(no return data)".to_string()
+                } else {
+                    format!("0x{}", hex::encode(&result))
+                };
+                self.state.output.push(format!("{call_str} @ {to:?}"), OutputStyle::Highlight);
+                self.state.output.push_success(format!("Result: {result_str}"));
+                self.add_call_card(self.signer_address, to, call_str, result_str);
+                true
+            }
+            Err(e) => {
+                let error_msg = format!("Call to {to:?} failed: {e}");
+                self.state.output.push_error(&error_msg);
+                self.add_log_card(format!("Failed: {call_str} @ {to:?}\n\n{error_msg}"));
+                false
+            }
+        };
+
+        self.state.output.push_separator();
+        self.state.output.scroll_to_bottom();
+        succeeded
+    }
+
+    /// Readable form of raw calldata: the decoded call when its selector is in the ABI known
+    /// for `to`, else the title and hex, with the value when there is one
+    fn describe_calldata(&self, title: &str, to: Address, data: &[u8], value: U256) -> String {
+        let decoded = data.get(..4).and_then(|selector| {
+            let abi = self.find_abi_for_address(to)?;
+            let function = abi.functions().find(|f| f.selector().as_slice() == selector)?;
+            let args = function.abi_decode_input(&data[4..]).ok()?;
+            Some(prompts::format_method_call(&function.name, &function.inputs, &args))
+        });
+        let mut call_str = decoded.unwrap_or_else(|| {
+            if data.is_empty() {
+                title.to_string()
+            } else {
+                format!("{title} 0x{}", hex::encode(data))
+            }
+        });
+        if !value.is_zero() {
+            call_str.push_str(&format!(" {{value: {} ETH}}", format_ether(value)));
+        }
+        call_str
     }
 
     fn try_parse_params(
@@ -1507,152 +4185,760 @@ impl<P: Provider + Clone> App<P> {
         }
     }
 
-    async fn do_deploy(&mut self, contract_name: String, contract_path: PathBuf, _abi: Arc<JsonAbi>, args: Vec<DynSolValue>, target: BytecodeTarget) {
-        log::info!(
-            "[DEPLOY] do_deploy: {} from {:?} with args {:?} target={:?}",
-            contract_name,
-            contract_path,
-            args,
-            target
+    async fn do_deploy(&mut self, contract_name: String, contract_path: PathBuf, _abi: Arc<JsonAbi>, args: Vec<DynSolValue>, target: BytecodeTarget, plan: DeployPlan) {
+        log::info!(
+            "[DEPLOY] do_deploy: {} from {:?} with args {:?} target={:?}",
+            contract_name,
+            contract_path,
+            args,
+            target
+        );
+        // Check connection status
+        if matches!(self.state.connection, ConnectionStatus::Disconnected) {
+            self.add_log_card("Cannot deploy: not connected to RPC".to_string());
+            return;
+        }
+        
+        // Compile on demand for the selected target, off the UI thread
+        self.state.output.push_info(format!("Compiling {contract_name} for {target}..."));
+        let label = format!("Compiling {contract_name} ({target})");
+        self.state.compiling.push(label.clone());
+
+        let contract_id = ContractId::new(contract_path.clone(), contract_name.clone());
+        let settings = self.store.get_compiler_settings(&contract_id);
+
+        let tx = self.compile_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let result =
+                crate::compile::compile_contract(&contract_path, &contract_name, target, &settings);
+            let proxy_code = match &plan {
+                DeployPlan::Proxy { kind, .. } => Some(Box::new(proxy::compile_proxy(*kind, target))),
+                _ => None,
+            };
+            let _ = tx.send(CompileOutcome::Deploy {
+                label,
+                contract_name,
+                contract_path,
+                args,
+                target,
+                settings,
+                plan,
+                result: Box::new(result),
+                proxy_code,
+            });
+        });
+    }
+
+    /// Fetch the code at `address` and compile the local runtime bytecode to compare it with.
+    /// The target is inferred from the on-chain code (PVM blobs carry a magic prefix).
+    async fn verify_bytecode(&mut self, address: Address, contract_name: String, contract_path: PathBuf) {
+        if matches!(self.state.connection, ConnectionStatus::Disconnected) {
+            self.add_log_card("Cannot verify bytecode: not connected to RPC".to_string());
+            return;
+        }
+
+        let onchain = match self.provider.get_code_at(address).await {
+            Ok(code) => code,
+            Err(e) => {
+                self.add_log_card(format!("Failed: Verify {contract_name} at {address:?}\n\n{e}"));
+                return;
+            }
+        };
+        if onchain.is_empty() {
+            self.add_log_card(format!(
+                "Verify {contract_name} at {address:?}\n\n{}",
+                VerifyOutcome::NoCode
+            ));
+            return;
+        }
+
+        // Rebuild the way it was deployed when that was recorded; instances loaded by
+        // address fall back to the bytecode's own format and the current settings
+        let contract_id = ContractId::new(contract_path.clone(), contract_name.clone());
+        let record = self.store.get_deployment(&contract_id, self.state.chain_id, address);
+        let target = record
+            .and_then(|record| record.target)
+            .unwrap_or_else(|| verify::detect_target(&onchain));
+        let settings = record
+            .and_then(|record| record.settings.clone())
+            .unwrap_or_else(|| self.store.get_compiler_settings(&contract_id));
+        let label = format!("Verifying {contract_name} ({target})");
+        self.state.compiling.push(label.clone());
+
+        let tx = self.compile_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let result = crate::compile::compile_runtime_code(&contract_path, &contract_name, target, &settings);
+            let _ = tx.send(CompileOutcome::Verify {
+                label,
+                address,
+                contract_name,
+                target,
+                onchain,
+                result,
+            });
+        });
+    }
+
+    fn finish_verify_bytecode(
+        &mut self,
+        address: Address,
+        contract_name: &str,
+        target: BytecodeTarget,
+        onchain: &[u8],
+        result: Result<RuntimeCode>,
+    ) {
+        let local = match result {
+            Ok(local) => local,
+            Err(e) => {
+                self.add_log_card(format!("Failed: Verify {contract_name} at {address:?}\n\nCompilation failed: {e}"));
+                self.report_build_error(&e);
+                return;
+            }
+        };
+
+        let outcome = verify::compare(onchain, &local, target);
+        log::info!("[VERIFY] {contract_name} at {address:?} ({target}): {outcome:?}");
+        self.state.last_action = Some(format!("Verify {contract_name}: {outcome:?}"));
+        self.add_log_card(format!("Verify {contract_name} at {address:?} ({target})\n\n{outcome}"));
+    }
+
+    /// Second half of a deployment: append the constructor arguments and send, stage a
+    /// CREATE2 deployment for confirmation, or go through the proxy flows
+    #[allow(clippy::too_many_arguments)]
+    async fn deploy_compiled(
+        &mut self,
+        contract_name: String,
+        contract_path: PathBuf,
+        args: Vec<DynSolValue>,
+        target: BytecodeTarget,
+        settings: CompilerSettings,
+        plan: DeployPlan,
+        result: Result<CompiledContract>,
+        proxy_code: Option<Result<ProxyCode>>,
+    ) {
+        let compiled = match result {
+            Ok(c) => c,
+            Err(e) => {
+                let error_msg = format!("Compilation failed: {e}");
+                log::error!("{error_msg}");
+                self.state.output.push_error(&error_msg);
+                self.state.output.push_separator();
+                self.state.output.scroll_to_bottom();
+                self.add_log_card(format!("Failed: Compile {contract_name} ({target})\n\n{error_msg}"));
+                self.report_build_error(&e);
+                return;
+            }
+        };
+
+        self.state.output.push_success(format!("Compilation successful ({target})"));
+        self.report_build_warnings(compiled.diagnostics.clone(), &format!("{contract_name} ({target})"));
+
+        let mut deploy_data = compiled.bytecode.clone();
+        let constructor_args = compiled
+            .abi
+            .constructor
+            .as_ref()
+            .map(|ctor| prompts::format_named_args(&ctor.inputs, &args))
+            .unwrap_or_default();
+
+        if !args.is_empty() {
+            let encoded = DynSolValue::Tuple(args).abi_encode_params();
+            deploy_data.extend(encoded);
+        }
+
+        let tx = TransactionRequest::default().with_deploy_code(deploy_data.clone());
+        match plan {
+            DeployPlan::Create => {
+                let role = DeploymentRole::Instance;
+                self.spawn_transaction(tx, TxPurpose::Deploy { contract_name, contract_path, target, settings, constructor_args, role });
+            }
+            DeployPlan::Create2(salt) => {
+                self.prepare_create2(PendingCreate2 {
+                    predicted: create2::predict_address(salt, &deploy_data),
+                    contract_name,
+                    contract_path,
+                    target,
+                    settings,
+                    salt,
+                    init_code: deploy_data,
+                    constructor_args,
+                });
+            }
+            DeployPlan::Proxy { kind, init_data } => {
+                let code = match proxy_code {
+                    Some(Ok(code)) => code,
+                    Some(Err(e)) => {
+                        self.add_log_card(format!("Failed: Deploy {contract_name} behind {kind} proxy\n\nCompiling the proxy failed: {e}"));
+                        self.report_build_error(&e);
+                        return;
+                    }
+                    None => return,
+                };
+                let title = format!("Deploy {contract_name} behind {kind} proxy");
+                let flow = deploy_behind_proxy(
+                    self.flow_sender(),
+                    tx,
+                    contract_name,
+                    contract_path,
+                    target,
+                    settings,
+                    kind,
+                    init_data,
+                    code,
+                    self.signer_address,
+                );
+                self.spawn_flow(title, flow);
+            }
+            DeployPlan::Upgrade { proxy, info, data } => {
+                let title = format!("Upgrade {proxy:?} to {contract_name}");
+                let flow = upgrade_proxy(self.flow_sender(), tx, contract_name, contract_path, target, settings, proxy, info, data);
+                self.spawn_flow(title, flow);
+            }
+            DeployPlan::SignOnly => {
+                self.sign_only(&format!("Deploy {contract_name}"), tx).await;
+            }
+        }
+    }
+
+    /// Make sure the CREATE2 factory exists and the predicted address is free in the
+    /// background, then ask for confirmation (`confirm_create2`)
+    fn prepare_create2(&mut self, pending: PendingCreate2) {
+        let allow_install = create2::is_local_node(&self.store.config.rpc_url, self.state.chain_id);
+        let provider = self.provider.clone();
+        let funder = self.signer_address;
+        let updates = self.transaction_tx.clone();
+        self.state.output.push("Checking the CREATE2 factory...", OutputStyle::Waiting);
+        tokio::spawn(async move {
+            let update = match check_create2(&provider, funder, allow_install, &pending).await {
+                Ok(factory) => TxUpdate::Create2Ready { pending: Box::new(pending), factory },
+                Err(e) => TxUpdate::FlowDone {
+                    title: format!("Deploy {} (CREATE2)", pending.contract_name),
+                    result: Err(format!("{e:#}")),
+                },
+            };
+            let _ = updates.send(update);
+        });
+    }
+
+    fn confirm_create2(&mut self, pending: PendingCreate2, factory: create2::FactorySetup) {
+        if factory != create2::FactorySetup::AlreadyDeployed {
+            log::info!("[CREATE2] factory installed: {factory:?}");
+            self.add_log_card(format!("Installed the CREATE2 factory at {:?} on this node", create2::FACTORY));
+        }
+        self.state.output.push_info(format!("CREATE2 address for {}: {:?}", pending.contract_name, pending.predicted));
+        self.state.output.scroll_to_bottom();
+        self.state.popup = PopupState::ConfirmCreate2 {
+            contract_name: pending.contract_name.clone(),
+            predicted: pending.predicted,
+            salt: pending.salt,
+        };
+        self.state.focus = Focus::CommandPalette;
+        self.pending_create2 = Some(pending);
+    }
+
+    async fn handle_confirm_create2_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.pending_create2 = None;
+                self.state.popup = PopupState::None;
+                self.state.focus = Focus::Sidebar;
+                self.state.output.push_info("CREATE2 deployment cancelled");
+            }
+            KeyCode::Enter => {
+                self.state.popup = PopupState::None;
+                self.state.focus = Focus::Sidebar;
+                if let Some(pending) = self.pending_create2.take() {
+                    let tx = TransactionRequest::default()
+                        .to(create2::FACTORY)
+                        .input(create2::factory_calldata(pending.salt, &pending.init_code).into());
+                    self.spawn_transaction(
+                        tx,
+                        TxPurpose::Deploy {
+                            contract_name: pending.contract_name,
+                            contract_path: pending.contract_path,
+                            target: pending.target,
+                            settings: pending.settings,
+                            constructor_args: pending.constructor_args,
+                            role: DeploymentRole::Create2(pending.predicted),
+                        },
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Register a transaction as in flight, returning its id and the request ready to send
+    fn track_transaction(&mut self, mut tx: TransactionRequest, purpose: TxPurpose) -> (u64, TransactionRequest, Watched) {
+        if let Some(chain_id) = self.state.chain_id {
+            tx = tx.with_chain_id(chain_id);
+        }
+        let id = self.next_transaction_id.fetch_add(1, Ordering::Relaxed);
+        let watched = Watched::default();
+        self.in_flight.insert(
+            id,
+            InFlight {
+                purpose,
+                request: tx.clone(),
+                watched: watched.clone(),
+                card_hash: None,
+                sent: None,
+                cancel_hashes: Vec::new(),
+                sent_at: std::time::Instant::now(),
+                done: None,
+            },
         );
-        // Check connection status
-        if matches!(self.state.connection, ConnectionStatus::Disconnected) {
-            self.add_log_card("Cannot deploy: not connected to RPC".to_string());
-            return;
+        self.state.pending_transactions = self.in_flight.len();
+        (id, tx, watched)
+    }
+
+    /// Send `tx` on a background task; its progress is picked up by `poll_transactions`
+    /// so the UI keeps running and several transactions can be in flight at once
+    fn spawn_transaction(&mut self, tx: TransactionRequest, purpose: TxPurpose) {
+        let (id, tx, watched) = self.track_transaction(tx, purpose);
+        let policy = ReceiptPolicy::from_config(&self.store.config);
+        tokio::spawn(submit_transaction(self.provider.clone(), tx, None, id, self.transaction_tx.clone(), watched, policy));
+    }
+
+    /// What a background flow sends its transactions with
+    fn flow_sender(&self) -> FlowSender<P> {
+        FlowSender {
+            provider: self.provider.clone(),
+            updates: self.transaction_tx.clone(),
+            next_id: self.next_transaction_id.clone(),
+            chain_id: self.state.chain_id,
+            policy: ReceiptPolicy::from_config(&self.store.config),
         }
-        
-        // Compile on demand for the selected target
-        self.state.output.push_info(format!("Compiling {contract_name} for {target}..."));
+    }
 
-        let compiled = match crate::compile::compile_contract(&contract_path, &contract_name, target) {
-            Ok(c) => c,
-            Err(e) => {
-                let error_msg = format!("Compilation failed: {e}");
-                log::error!("{error_msg}");
-                self.state.output.push_error(error_msg);
-                self.state.output.push_separator();
-                self.state.output.scroll_to_bottom();
-                return;
+    /// Run a multi-step flow on a background task. Its transactions and its outcome are
+    /// picked up by `poll_transactions` like any other.
+    fn spawn_flow(&self, title: String, flow: impl Future<Output = Result<String>> + Send + 'static) {
+        let updates = self.transaction_tx.clone();
+        tokio::spawn(async move {
+            let result = flow.await.map_err(|e| format!("{e:#}"));
+            let _ = updates.send(TxUpdate::FlowDone { title, result });
+        });
+    }
+
+    /// Broadcast a transaction signed beforehand with `eth_sendRawTransaction`; it is
+    /// tracked like any other call transaction
+    fn spawn_raw_transaction(&mut self, signed: SignedTx) {
+        let purpose = match (signed.to(), signed.created_address()) {
+            (Some(to), _) => {
+                let contract_name = self
+                    .store
+                    .contract_for_address(to)
+                    .map(|id| id.name)
+                    .or_else(|| self.store.get_attached(to).map(|a| a.name.clone()))
+                    .unwrap_or_else(|| "Unknown".to_string());
+                let call_str = self.describe_calldata("Raw tx", to, signed.input(), signed.value());
+                TxPurpose::Call { address: to, contract_name, title: "Raw tx".to_string(), call_str }
             }
+            (None, created) => TxPurpose::Call {
+                address: created.unwrap_or_default(),
+                contract_name: "Unknown".to_string(),
+                title: "Raw tx".to_string(),
+                call_str: format!("Contract creation ({} bytes of init code)", signed.input().len()),
+            },
         };
+        log::info!("[TX] Broadcasting raw transaction {:?} from {:?}", signed.hash, signed.from);
+        let (id, tx, watched) = self.track_transaction(signed.request(), purpose);
+        let policy = ReceiptPolicy::from_config(&self.store.config);
+        tokio::spawn(submit_transaction(
+            self.provider.clone(),
+            tx,
+            Some(signed.raw),
+            id,
+            self.transaction_tx.clone(),
+            watched,
+            policy,
+        ));
+    }
 
-        self.state.output.push_success(format!("Compilation successful ({target})"));
-
-        let mut deploy_data = compiled.bytecode.clone();
-
-        if !args.is_empty() {
-            let encoded = DynSolValue::Tuple(args).abi_encode_params();
-            deploy_data.extend(encoded);
+    /// Drain progress of background transactions
+    async fn poll_transactions(&mut self) {
+        while let Ok(update) = self.transaction_rx.try_recv() {
+            self.apply_transaction_update(update).await;
         }
+    }
 
-        let mut tx = TransactionRequest::default().with_deploy_code(deploy_data.clone());
-        if let Some(chain_id) = self.state.chain_id {
-            tx = tx.with_chain_id(chain_id);
-        }
+    /// In-flight transactions, oldest first
+    fn pending_transaction_ids(&self) -> Vec<u64> {
+        let mut ids: Vec<u64> = self.in_flight.keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
 
-        self.state.output.push(
-            format!("Deploying {contract_name} contract..."),
-            OutputStyle::Waiting
-        );
+    /// Send a replacement for in-flight transaction `id` with the same nonce and higher
+    /// fees: the same request to speed it up, or a zero-value transfer to yourself to
+    /// cancel it. Once cancelled, speeding up bumps the cancellation.
+    fn replace_transaction(&mut self, id: u64, cancel: bool) {
+        let action = if cancel { "cancel" } else { "speed up" };
+        let Some(in_flight) = self.in_flight.get(&id) else {
+            self.add_log_card(format!("Cannot {action}: the transaction is no longer pending"));
+            return;
+        };
+        let title = in_flight.purpose.title();
+        let Some(sent) = in_flight.sent else {
+            self.add_log_card(format!("Cannot {action} {title}: its nonce is not known (the node did not return the transaction)"));
+            return;
+        };
+        if let Some(from) = in_flight.request.from.filter(|from| *from != self.signer_address) {
+            self.add_log_card(format!("Cannot {action} {title}: it was signed by {from:?}, not the current account"));
+            return;
+        }
+        let cancel = cancel || !in_flight.cancel_hashes.is_empty();
+
+        log::info!("[TX] {action} {title} (nonce {})", sent.nonce);
+        tokio::spawn(send_replacement(
+            self.provider.clone(),
+            in_flight.request.clone(),
+            self.signer_address,
+            sent,
+            cancel,
+            id,
+            self.transaction_tx.clone(),
+            in_flight.watched.clone(),
+        ));
+        let doing = if cancel { "Cancelling" } else { "Speeding up" };
+        self.state.output.push(format!("{doing} {title} (nonce {})...", sent.nonce), OutputStyle::Waiting);
+        self.state.output.push_separator();
+        self.state.output.scroll_to_bottom();
+    }
 
-        let pending = match self.provider.send_transaction(tx.clone()).await {
-            Ok(p) => p,
-            Err(e) => {
-                let error_msg = format!("Deployment failed: {e}");
+    /// Report a transaction's progress in the output and its card, and what background
+    /// flows ask to record
+    async fn apply_transaction_update(&mut self, update: TxUpdate) {
+        match update {
+            TxUpdate::Tracked { id, request, purpose, watched, done } => {
+                self.in_flight.insert(
+                    id,
+                    InFlight {
+                        purpose: *purpose,
+                        request: *request,
+                        watched,
+                        card_hash: None,
+                        sent: None,
+                        cancel_hashes: Vec::new(),
+                        sent_at: std::time::Instant::now(),
+                        done: Some(done),
+                    },
+                );
+                self.state.pending_transactions = self.in_flight.len();
+            }
+            TxUpdate::Sent { id, hash, sent } => {
+                let Some(in_flight) = self.in_flight.get_mut(&id) else {
+                    return;
+                };
+                in_flight.card_hash = Some(hash);
+                in_flight.sent = sent;
+                let (title, contract_name, address, compiler) = match &in_flight.purpose {
+                    TxPurpose::Call { address, contract_name, call_str, .. } => {
+                        self.state.output.push_success(format!("Transaction: {hash:?}"));
+                        self.state.output.push(format!("{call_str} @ {address:?}"), OutputStyle::Highlight);
+                        (call_str.clone(), contract_name.clone(), Some(*address), None)
+                    }
+                    TxPurpose::Deploy { contract_name, target, settings, role, .. } => {
+                        self.state.output.push(format!("Deploying {contract_name} contract..."), OutputStyle::Waiting);
+                        self.state.output.push_success(format!("Transaction: {hash:?}"));
+                        let address = match role {
+                            DeploymentRole::Create2(address) => Some(*address),
+                            _ => None,
+                        };
+                        let compiler = format!("{target}, {}", settings.summary(*target));
+                        (role.card_title(contract_name), contract_name.clone(), address, Some(compiler))
+                    }
+                };
+                if let Some(sent) = sent {
+                    self.state.output.push_info(format!("Nonce {}, {}", sent.nonce, sent.fees));
+                }
+                self.state.output.push("Waiting for confirmation...", OutputStyle::Waiting);
+                self.state.output.push_separator();
+                self.state.output.scroll_to_bottom();
+                self.add_transaction_card(
+                    hash,
+                    TransactionStatus::Pending,
+                    title,
+                    None,
+                    contract_name,
+                    address,
+                    None,
+                    compiler,
+                    sent.map(|sent| sent.nonce),
+                );
+            }
+            TxUpdate::Replaced { id, hash, fees, cancel } => {
+                let Some(in_flight) = self.in_flight.get_mut(&id) else {
+                    return;
+                };
+                if cancel {
+                    in_flight.cancel_hashes.push(hash);
+                }
+                if let Some(sent) = &mut in_flight.sent {
+                    sent.fees = fees;
+                }
+                let previous = in_flight.card_hash.replace(hash);
+                let title = in_flight.purpose.title();
+                if let Some(previous) = previous {
+                    self.retarget_transaction_card(previous, hash);
+                }
+                let what = if cancel { "Cancellation" } else { "Speed-up" };
+                self.state.output.push_success(format!("{what} sent for {title}: {hash:?}"));
+                self.state.output.push_info(format!("{fees}"));
+                self.state.output.push_separator();
+                self.state.output.scroll_to_bottom();
+            }
+            TxUpdate::ReplaceFailed { id, cancel, error } => {
+                let title = self.in_flight.get(&id).map(|in_flight| in_flight.purpose.title()).unwrap_or_default();
+                let action = if cancel { "Cancel" } else { "Speed up" };
+                let error_msg = format!("{action} failed: {error}");
                 self.state.output.push_error(&error_msg);
                 self.state.output.push_separator();
                 self.state.output.scroll_to_bottom();
-                // Add a log card so the error is visible in the card view
-                self.add_log_card(format!("Failed: Deploy {contract_name}\n\n{error_msg}"));
-                return;
+                self.add_log_card(format!("Failed: {action} {title}\n\n{error_msg}"));
             }
-        };
-
-        let tx_hash = *pending.tx_hash();
-        self.state.output.push_success(format!("Transaction: {tx_hash:?}"));
-        self.state.output.push("Waiting for confirmation...", OutputStyle::Waiting);
-
-        let receipt = match pending.get_receipt().await {
-            Ok(r) => r,
-            Err(e) => {
-                let error_msg = format!("Failed to get receipt: {e}");
+            TxUpdate::Failed { id, hash, error } => {
+                let Some(mut in_flight) = self.in_flight.remove(&id) else {
+                    return;
+                };
+                self.state.pending_transactions = self.in_flight.len();
+                let purpose = in_flight.purpose;
+                let error_msg = match hash {
+                    Some(_) => format!("Failed to get receipt: {error}"),
+                    None if matches!(purpose, TxPurpose::Deploy { .. }) => format!("Deployment failed: {error}"),
+                    None => format!("Transaction failed: {error}"),
+                };
+                let hash = in_flight.card_hash.or(hash);
+                let tx_suffix = hash.map(|hash| format!(" (tx: {hash:?})")).unwrap_or_default();
+                let failed = match &purpose {
+                    TxPurpose::Call { address, title, .. } => format!("{title} @ {address:?}"),
+                    TxPurpose::Deploy { contract_name, .. } => format!("Deploy {contract_name}"),
+                };
+                if let Some(hash) = hash {
+                    self.update_transaction_card(hash, TransactionStatus::Failed, None, None, Some(error_msg.clone()));
+                }
                 self.state.output.push_error(&error_msg);
                 self.state.output.push_separator();
                 self.state.output.scroll_to_bottom();
                 // Add a log card so the error is visible in the card view
-                self.add_log_card(format!("Failed: Deploy {contract_name} (tx: {tx_hash:?})\n\n{error_msg}"));
-                return;
+                self.add_log_card(format!("Failed: {failed}{tx_suffix}\n\n{error_msg}"));
+                if let Some(done) = in_flight.done.take() {
+                    let _ = done.send(TxResult::NotConfirmed);
+                }
             }
-        };
+            TxUpdate::Confirmed { id, receipt, revert_reason } => {
+                let Some(mut in_flight) = self.in_flight.remove(&id) else {
+                    return;
+                };
+                let done = in_flight.done.take();
+                self.state.pending_transactions = self.in_flight.len();
+                // The card follows whichever of the original and its replacements got mined
+                if let Some(card_hash) = in_flight.card_hash {
+                    self.retarget_transaction_card(card_hash, receipt.transaction_hash);
+                }
+
+                let result = if in_flight.cancel_hashes.contains(&receipt.transaction_hash) {
+                    self.finish_cancellation(&receipt, &in_flight.purpose)
+                } else {
+                    match in_flight.purpose {
+                        TxPurpose::Call { address, call_str, .. } => {
+                            self.finish_call_transaction(&receipt, revert_reason, address, call_str)
+                        }
+                        TxPurpose::Deploy { contract_name, contract_path, target, settings, constructor_args, role } => {
+                            self.finish_deployment(&receipt, revert_reason, contract_name, contract_path, target, settings, constructor_args, role)
+                        }
+                    }
+                };
+                self.state.output.push_separator();
+                self.state.output.scroll_to_bottom();
+
+                // Refresh balance and the dashboard after transaction
+                self.refresh_balance().await;
+                self.refresh_dashboard().await;
+                if let Some(done) = done {
+                    let _ = done.send(result);
+                }
+            }
+            TxUpdate::FlowDone { title, result } => match result {
+                Ok(summary) => self.add_log_card(summary),
+                Err(e) => self.add_log_card(format!("Failed: {title}\n\n{e}")),
+            },
+            TxUpdate::ProxyUpgraded { proxy, info } => {
+                self.store.set_proxy(self.state.chain_id, proxy, Some(info));
+                if let Err(e) = self.store.save() {
+                    self.state.output.push_error(format!("Failed to save proxy: {e}"));
+                }
+            }
+            TxUpdate::ManifestStep { chain_id, manifest, step, contract_id, address, calls } => {
+                // Deployed by this run or an earlier one, only listed again for the latter
+                // Recorded for this chain unless it already is; a record without a chain is taken over
+                let recorded = self.store.get_deployment(&contract_id, Some(chain_id), address).is_some_and(|record| record.chain_id == Some(chain_id));
+                if !recorded {
+                    let genesis_hash = if self.state.chain_id == Some(chain_id) { self.state.genesis_hash } else { None };
+                    let record = DeploymentRecord { chain_id: Some(chain_id), genesis_hash, ..DeploymentRecord::new(address) };
+                    self.store.record_deployment(&contract_id, record);
+                }
+                self.store.set_manifest_deployment(chain_id, &manifest, &step, address);
+                self.store.set_manifest_calls(chain_id, &manifest, &step, calls);
+                if let Err(e) = self.store.save() {
+                    self.state.output.push_error(format!("Failed to save manifest run: {e}"));
+                }
+            }
+            TxUpdate::Create2Ready { pending, factory } => self.confirm_create2(*pending, factory),
+        }
+    }
+
+    /// A cancellation was mined, so the original transaction never ran
+    fn finish_cancellation(&mut self, receipt: &TransactionReceipt, purpose: &TxPurpose) -> TxResult {
+        let tx_hash = receipt.transaction_hash;
+        let gas_used = receipt.gas_used.separated_string();
+        self.state.output.push(purpose.title(), OutputStyle::Highlight);
+        self.state.output.push_info(format!("Transaction: {tx_hash:?}"));
+        self.state.output.push_error("Cancelled: a zero-value transfer to yourself was mined with its nonce");
+        self.state.output.push_info(format!("Gas used: {gas_used}"));
+        self.update_transaction_card(tx_hash, TransactionStatus::Cancelled, Some(gas_used), None, None);
+        TxResult::Cancelled
+    }
+
+    /// Point the transaction card showing `old` at its replacement `new`
+    fn retarget_transaction_card(&mut self, old: TxHash, new: TxHash) {
+        for card in &mut self.state.cards.cards {
+            if let crate::cards::Card::Transaction { hash, .. } = card {
+                if *hash == old {
+                    *hash = new;
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Set the outcome of the transaction card for `hash`, wherever it is in the list
+    fn update_transaction_card(
+        &mut self,
+        tx_hash: TxHash,
+        new_status: TransactionStatus,
+        gas: Option<String>,
+        address: Option<Address>,
+        error: Option<String>,
+    ) {
+        for card in &mut self.state.cards.cards {
+            if let crate::cards::Card::Transaction { hash, status, gas_used, contract_address, error_message, .. } = card {
+                if *hash == tx_hash {
+                    *status = new_status;
+                    *gas_used = gas.or(gas_used.take());
+                    *contract_address = address.or(*contract_address);
+                    *error_message = error;
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Report a mined deployment. Instances and proxies are registered in the sidebar
+    /// along with the deployment's metadata.
+    #[allow(clippy::too_many_arguments)]
+    fn finish_deployment(
+        &mut self,
+        receipt: &TransactionReceipt,
+        revert_reason: Option<String>,
+        contract_name: String,
+        contract_path: PathBuf,
+        target: BytecodeTarget,
+        settings: CompilerSettings,
+        constructor_args: Vec<String>,
+        role: DeploymentRole,
+    ) -> TxResult {
+        let tx_hash = receipt.transaction_hash;
+        let gas_used = receipt.gas_used.separated_string();
+        self.state.output.push(role.card_title(&contract_name), OutputStyle::Highlight);
+        self.state.output.push_info(format!("Transaction: {tx_hash:?}"));
 
-        let address = match receipt.contract_address {
+        let create2_address = match role {
+            DeploymentRole::Create2(address) => Some(address),
+            _ => None,
+        };
+        let address = match create2_address.or(receipt.contract_address) {
             Some(a) => a,
             None => {
                 let error_msg = "No contract address in receipt";
+                self.update_transaction_card(tx_hash, TransactionStatus::Failed, Some(gas_used), None, Some(error_msg.to_string()));
                 self.state.output.push_error(error_msg);
-                self.state.output.push_separator();
-                self.state.output.scroll_to_bottom();
                 // Add a log card so the error is visible in the card view
                 self.add_log_card(format!("Failed: Deploy {contract_name} (tx: {tx_hash:?})\n\n{error_msg}"));
-                return;
+                return TxResult::NotConfirmed;
             }
         };
 
-        // Create a transaction card with actual status and gas from receipt
-        let (status, error_message) = if receipt.status() {
-            (crate::cards::TransactionStatus::Success, None)
-        } else {
-            // Deployment reverted - try to get the revert reason
-            let error_msg = self.get_revert_reason(&tx, receipt.block_number).await;
-            (crate::cards::TransactionStatus::Failed, Some(error_msg))
-        };
-        let gas_used = receipt.gas_used.separated_string();
-        self.add_transaction_card(
-            tx_hash,
-            status,
-            format!("Deploy {contract_name}"),
-            Some(gas_used.clone()),
-            contract_name.to_string(),
-            Some(address),
-            error_message.clone(),
-        );
-
         if !receipt.status() {
-            let error_display = error_message.as_deref().unwrap_or("Unknown reason");
+            // Deployment reverted - the reason was replayed before reporting the receipt
+            let error_display = revert_reason.as_deref().unwrap_or("Unknown reason").to_string();
+            self.update_transaction_card(tx_hash, TransactionStatus::Failed, Some(gas_used), Some(address), revert_reason);
             self.state.output.push_error(format!("Deployment reverted: {error_display}"));
-            self.state.output.push_separator();
-            self.state.output.scroll_to_bottom();
-            return;
+            return TxResult::Reverted;
         }
 
+        self.update_transaction_card(tx_hash, TransactionStatus::Success, Some(gas_used), Some(address), None);
         self.state.output.push_success(format!("Deployed at: {address:?}"));
 
+        let contract_id = ContractId::new(contract_path.clone(), contract_name.to_string());
+        match role {
+            DeploymentRole::Implementation => {
+                // Not an instance of its own, but its proxy's ABI comes from it
+                self.store.record_implementation(address, &contract_id);
+                if let Err(e) = self.store.save() {
+                    self.state.output.push_error(format!("Failed to save deployment: {e}"));
+                }
+                return TxResult::Deployed(address);
+            }
+            DeploymentRole::ProxyAdmin => return TxResult::Deployed(address),
+            _ => {}
+        }
+
         self.set_address(address);
 
-        let contract_id = ContractId::new(contract_path.clone(), contract_name.to_string());
-        self.store.add_deployment(&contract_id, address);
+        self.store.record_deployment(
+            &contract_id,
+            DeploymentRecord {
+                address,
+                chain_id: self.state.chain_id,
+                genesis_hash: self.state.genesis_hash,
+                target: Some(target),
+                settings: Some(settings),
+                tx_hash: Some(tx_hash),
+                block_number: receipt.block_number,
+                deployer: Some(receipt.from),
+                constructor_args,
+                deployed_at: Some(chrono::Utc::now().to_rfc3339()),
+            },
+        );
+        if let DeploymentRole::Proxy { kind, implementation, admin } = role {
+            self.store.set_proxy(self.state.chain_id, address, Some(ProxyInfo { kind, implementation, admin, beacon: None }));
+        }
         if let Err(e) = self.store.save() {
             self.state.output.push_error(format!("Failed to save deployment: {e}"));
         }
 
         // Expand and select the newly deployed instance in the sidebar
+        if let Some(chain_id) = self.state.chain_id {
+            self.state.sidebar.dead_instances.remove(&(chain_id, address));
+        }
         self.state.sidebar.expanded_instances.insert(address);
         self.select_instance_in_sidebar(address);
 
-        self.state.output.push_separator();
-        self.state.output.scroll_to_bottom();
-
-        // Refresh balance after transaction
-        self.refresh_balance().await;
+        TxResult::Deployed(address)
     }
 
+    /// Call or send `func`, reporting the result in the output. Transactions are sent in
+    /// the background. Returns whether it succeeded (or was sent).
     async fn do_call_function(
         &mut self,
         func: &Function,
         address: Address,
         args: Vec<DynSolValue>,
-    ) {
-        let contract_name = self.contract.as_ref().map(|c| c.name.as_str()).unwrap_or("Unknown");
+        options: CallOptions,
+    ) -> bool {
+        let contract_name = self.contract.as_ref().map(|c| c.name.clone()).unwrap_or_else(|| "Unknown".to_string());
         log::info!(
             "[CALL] do_call_function: {}.{}({:?}) at {:?}",
             contract_name,
@@ -1669,7 +4955,7 @@ impl<P: Provider + Clone> App<P> {
         // Check connection status for state-changing functions
         if !is_view && matches!(self.state.connection, ConnectionStatus::Disconnected) {
             self.add_log_card(format!("Cannot call {}: not connected to RPC", func.name));
-            return;
+            return false;
         }
 
         let calldata = match func.abi_encode_input(&args) {
@@ -1678,146 +4964,248 @@ impl<P: Provider + Clone> App<P> {
                 self.state.output.push_error(format!("Failed to encode function call: {e}"));
                 self.state.output.push_separator();
                 self.state.output.scroll_to_bottom();
-                return;
+                return false;
             }
         };
 
-        if is_view {
+        if !is_view {
             let tx = TransactionRequest::default()
                 .to(address)
                 .input(calldata.into());
+            self.spawn_transaction(tx, TxPurpose::call(func, address, &args, contract_name));
+            return true;
+        }
 
-            let result = match self.provider.call(tx).await {
-                Ok(r) => r,
-                Err(e) => {
-                    self.state.output.push_error(format!("Call to {contract_name} {address:?} failed: {e}"));
-                    self.state.output.push_separator();
-                    self.state.output.scroll_to_bottom();
-                    return;
-                }
-            };
-
-            let decoded = match func.abi_decode_output(&result) {
-                Ok(d) => d,
-                Err(e) => {
-                    self.state.output.push_error(format!("Failed to decode return value: {e}"));
-                    self.state.output.push_separator();
-                    self.state.output.scroll_to_bottom();
-                    return;
-                }
-            };
+        let mut tx = TransactionRequest::default()
+            .to(address)
+            .input(calldata.into());
+        if let Some(from) = options.from {
+            tx = tx.from(from);
+        }
+        let mut call = self.provider.call(tx).overrides_opt(options.overrides.clone());
+        if let Some(block) = options.block {
+            call = call.block(block);
+        }
 
-            let result_str = match decoded.as_slice() {
-                [] => "(no return value)".to_string(),
-                [single] => prompts::format_return_value(single),
-                multiple => {
-                    let formatted: Vec<_> =
-                        multiple.iter().map(prompts::format_return_value).collect();
-                    format!("({})", formatted.join(", "))
-                }
-            };
+        let result = match call.await {
+            Ok(r) => r,
+            Err(e) => {
+                self.state.output.push_error(format!("Call to {contract_name} {address:?} failed: {e}"));
+                self.state.output.push_separator();
+                self.state.output.scroll_to_bottom();
+                return false;
+            }
+        };
 
-            let call_str = prompts::format_method_call(&func.name, &func.inputs, &args);
-            self.state.output.push(format!("{call_str} @ {address:?}"), OutputStyle::Highlight);
-            self.state.output.push_success(format!("Result: {result_str}"));
+        self.show_call_result(func, address, &args, &options, &result)
+    }
 
-            // Add a call card for view/pure calls
-            if let Some(from_addr) = self.address {
-                self.add_call_card(from_addr, address, call_str.clone(), result_str.clone());
-            } else {
-                // Fallback to log card if no account is available
-                self.add_log_card(format!("{} completed successfully", func.name));
+    /// Decode the return data of a view call and show it in the output and a call card
+    fn show_call_result(
+        &mut self,
+        func: &Function,
+        address: Address,
+        args: &[DynSolValue],
+        options: &CallOptions,
+        result: &[u8],
+    ) -> bool {
+        let decoded = match func.abi_decode_output(result) {
+            Ok(d) => d,
+            Err(e) => {
+                self.state.output.push_error(format!("Failed to decode return value: {e}"));
+                self.state.output.push_separator();
+                self.state.output.scroll_to_bottom();
+                return false;
             }
+        };
+
+        let result_str = prompts::format_return_values(&decoded);
+
+        let mut call_str = prompts::format_method_call(&func.name, &func.inputs, args);
+        if let Some(context) = options.describe() {
+            call_str.push_str(&format!(" [{context}]"));
+        }
+        self.state.output.push(format!("{call_str} @ {address:?}"), OutputStyle::Highlight);
+        self.state.output.push_success(format!("Result: {result_str}"));
+
+        // Add a call card for view/pure calls
+        if let Some(from_addr) = self.address {
+            self.add_call_card(from_addr, address, call_str.clone(), result_str.clone());
         } else {
-            let mut tx = TransactionRequest::default()
-                .to(address)
-                .input(calldata.into());
-            if let Some(chain_id) = self.state.chain_id {
-                tx = tx.with_chain_id(chain_id);
-            }
+            // Fallback to log card if no account is available
+            self.add_log_card(format!("{} completed successfully", func.name));
+        }
 
-            self.state.output.push(
-                format!("Sending transaction to {contract_name} {address:?}..."),
-                OutputStyle::Waiting
-            );
+        self.state.output.push_separator();
+        self.state.output.scroll_to_bottom();
+        true
+    }
+
+    /// Run view calls in one `eth_call` to Multicall3, or one by one where it is not
+    /// available. Each call is shown on its own, as if it had been made alone.
+    async fn do_call_functions(&mut self, calls: Vec<BatchedCall>, options: CallOptions) -> bool {
+        if calls.len() < 2 || !self.multicall_ready() {
+            return self.call_one_by_one(calls, options).await;
+        }
 
-            let pending = match self.provider.send_transaction(tx.clone()).await {
-                Ok(p) => p,
+        let mut encoded = Vec::with_capacity(calls.len());
+        for call in &calls {
+            match call.function.abi_encode_input(&call.args) {
+                Ok(data) => encoded.push((call.address, Bytes::from(data))),
                 Err(e) => {
-                    let error_msg = format!("Transaction failed: {e}");
-                    self.state.output.push_error(&error_msg);
+                    self.state.output.push_error(format!("Failed to encode {}: {e}", call.function.name));
                     self.state.output.push_separator();
                     self.state.output.scroll_to_bottom();
-                    // Add a log card so the error is visible in the card view
-                    self.add_log_card(format!("Failed: {} @ {address:?}\n\n{error_msg}", func.name));
-                    return;
+                    return false;
                 }
-            };
+            }
+        }
 
-            let tx_hash = *pending.tx_hash();
-            self.state.output.push_success(format!("Transaction: {tx_hash:?}"));
+        let mut tx = TransactionRequest::default()
+            .to(multicall::ADDRESS)
+            .input(multicall::encode(&encoded).into());
+        if let Some(from) = options.from {
+            tx = tx.from(from);
+        }
+        let mut call = self.provider.call(tx).overrides_opt(options.overrides.clone());
+        if let Some(block) = options.block {
+            call = call.block(block);
+        }
+        let outcomes = match call.await {
+            Ok(data) => multicall::decode(&data, calls.len()),
+            Err(e) => Err(e.into()),
+        };
+        let outcomes = match outcomes {
+            Ok(outcomes) => outcomes,
+            Err(e) => {
+                // E.g. a block from before Multicall3 was deployed
+                log::warn!("[MULTICALL] batch of {} calls failed, calling one by one: {e:#}", calls.len());
+                return self.call_one_by_one(calls, options).await;
+            }
+        };
+        log::info!("[MULTICALL] {} calls in one eth_call", calls.len());
+
+        let mut succeeded = true;
+        for (call, outcome) in calls.iter().zip(outcomes) {
+            match outcome {
+                Ok(data) => succeeded &= self.show_call_result(&call.function, call.address, &call.args, &options, &data),
+                Err(revert) => {
+                    let call_str = prompts::format_method_call(&call.function.name, &call.function.inputs, &call.args);
+                    self.state.output.push(format!("{call_str} @ {:?}", call.address), OutputStyle::Highlight);
+                    self.state.output.push_error(format!("Call reverted: {}", multicall::revert_message(&revert)));
+                    self.state.output.push_separator();
+                    succeeded = false;
+                }
+            }
+        }
+        self.state.output.scroll_to_bottom();
+        succeeded
+    }
 
-            let call_str = prompts::format_method_call(&func.name, &func.inputs, &args);
-            self.state.output.push(format!("{call_str} @ {address:?}"), OutputStyle::Highlight);
+    async fn call_one_by_one(&mut self, calls: Vec<BatchedCall>, options: CallOptions) -> bool {
+        let mut succeeded = true;
+        for call in calls {
+            succeeded &= self.do_call_function(&call.function, call.address, call.args, options.clone()).await;
+        }
+        succeeded
+    }
 
-            self.state.output.push("Waiting for confirmation...", OutputStyle::Waiting);
+    /// Whether view calls can be batched through Multicall3. The first time on a connection
+    /// this starts a background check, which installs it on local nodes, and calls go one
+    /// by one until `poll_multicall` has the answer.
+    fn multicall_ready(&mut self) -> bool {
+        if let Some(ready) = self.multicall_ready {
+            return ready;
+        }
+        let (ConnectionStatus::Connected, Some(chain_id)) = (self.state.connection, self.state.chain_id) else {
+            return false;
+        };
+        self.multicall_ready = Some(false);
+        let allow_install = create2::is_local_node(&self.store.config.rpc_url, Some(chain_id));
+        let provider = self.provider.clone();
+        let updates = self.multicall_tx.clone();
+        tokio::spawn(async move {
+            let setup = multicall::ensure_deployed(&provider, allow_install).await;
+            let _ = updates.send((chain_id, setup));
+        });
+        false
+    }
 
-            let receipt = match pending.get_receipt().await {
-                Ok(r) => r,
+    fn poll_multicall(&mut self) {
+        let checks: Vec<(u64, Result<Multicall3Setup>)> = self.multicall_rx.try_iter().collect();
+        for (chain_id, setup) in checks {
+            // A check from before a reconnection to another chain
+            if self.state.chain_id != Some(chain_id) || self.multicall_ready.is_none() {
+                continue;
+            }
+            let ready = match setup {
+                Ok(Multicall3Setup::AlreadyDeployed) => true,
+                Ok(setup) => {
+                    log::info!("[MULTICALL] Multicall3 installed: {setup:?}");
+                    self.add_log_card(format!("Installed Multicall3 at {:?} on this node", multicall::ADDRESS));
+                    true
+                }
                 Err(e) => {
-                    let error_msg = format!("Failed to get receipt: {e}");
-                    self.state.output.push_error(&error_msg);
-                    self.state.output.push_separator();
-                    self.state.output.scroll_to_bottom();
-                    // Add a log card so the error is visible in the card view
-                    self.add_log_card(format!("Failed: {} @ {address:?} (tx: {tx_hash:?})\n\n{error_msg}", func.name));
-                    return;
+                    log::info!("[MULTICALL] view calls are not batched: {e:#}");
+                    false
                 }
             };
+            self.multicall_ready = Some(ready);
+        }
+    }
 
-            // Create a transaction card with actual status and gas from receipt
-            let (status, error_message) = if receipt.status() {
-                (crate::cards::TransactionStatus::Success, None)
-            } else {
-                // Transaction reverted - try to get the revert reason by simulating the call
-                let error_msg = self.get_revert_reason(&tx, receipt.block_number).await;
-                (crate::cards::TransactionStatus::Failed, Some(error_msg))
-            };
-            let gas_used = receipt.gas_used.separated_string();
-            self.add_transaction_card(
-                tx_hash,
-                status,
-                call_str.clone(),
-                Some(gas_used.clone()),
-                contract_name.to_string(),
-                Some(address),
-                error_message.clone(),
-            );
-
-            if receipt.status() {
-                self.state.output.push_success("Status: Success");
-            } else {
-                let error_display = error_message.as_deref().unwrap_or("Unknown reason");
-                self.state.output.push_error(format!("Transaction reverted: {error_display}"));
-            }
+    /// Call every getter of the instance at `address` in one batch
+    async fn read_getters(&mut self, address: Address) {
+        let Some(abi) = self.find_abi_for_address(address) else {
+            return;
+        };
+        let calls = method_list::getters(&abi)
+            .into_iter()
+            .map(|function| BatchedCall { function: function.clone(), address, args: Vec::new() })
+            .collect();
+        self.do_call_functions(calls, CallOptions::default()).await;
+    }
 
-            self.state.output.push_info(format!("Gas used: {gas_used}"));
+    /// Report a mined call transaction: status, gas and decoded logs
+    fn finish_call_transaction(
+        &mut self,
+        receipt: &TransactionReceipt,
+        revert_reason: Option<String>,
+        address: Address,
+        call_str: String,
+    ) -> TxResult {
+        let tx_hash = receipt.transaction_hash;
+        let gas_used = receipt.gas_used.separated_string();
+        self.state.output.push(format!("{call_str} @ {address:?}"), OutputStyle::Highlight);
+        self.state.output.push_info(format!("Transaction: {tx_hash:?}"));
 
-            // Display logs if any
-            let logs = receipt.inner.logs();
-            if !logs.is_empty() {
-                self.state.output.push_info(format!("Logs ({})", logs.len()));
-                for (i, log) in logs.iter().enumerate() {
-                    self.display_log(i, log);
-                }
-            }
+        let succeeded = receipt.status();
+        if succeeded {
+            self.state.output.push_success("Status: Success");
+            self.update_transaction_card(tx_hash, TransactionStatus::Success, Some(gas_used.clone()), None, None);
+        } else {
+            // Transaction reverted - the reason was replayed before reporting the receipt
+            let error_display = revert_reason.as_deref().unwrap_or("Unknown reason");
+            self.state.output.push_error(format!("Transaction reverted: {error_display}"));
+            self.update_transaction_card(tx_hash, TransactionStatus::Failed, Some(gas_used.clone()), None, revert_reason);
+        }
+
+        self.state.output.push_info(format!("Gas used: {gas_used}"));
 
-            // Refresh balance after transaction
-            self.refresh_balance().await;
+        // Display logs if any
+        let logs = receipt.inner.logs();
+        if !logs.is_empty() {
+            self.state.output.push_info(format!("Logs ({})", logs.len()));
+            for (i, log) in logs.iter().enumerate() {
+                self.display_log(i, log);
+            }
         }
 
-        self.state.output.push_separator();
-        self.state.output.scroll_to_bottom();
+        if succeeded {
+            TxResult::Succeeded
+        } else {
+            TxResult::Reverted
+        }
     }
 
     /// Display a log entry, attempting to decode it with known ABIs
@@ -1873,54 +5261,19 @@ impl<P: Provider + Clone> App<P> {
 
     /// Try to find an ABI for a given address by checking known deployments
     fn find_abi_for_address(&self, address: Address) -> Option<Arc<JsonAbi>> {
-        // Check all contracts in the store
-        for contract_id in self.store.all_contracts() {
-            let deployments = self.store.get_deployments(&contract_id);
-            if deployments.contains(&address) {
-                // This contract has this address
-                return Some(self.get_abi_for_contract(&contract_id));
-            }
-        }
-        None
-    }
-
-    /// Try to get the revert reason for a failed transaction by simulating the call
-    async fn get_revert_reason(&self, tx: &TransactionRequest, block_number: Option<u64>) -> String {
-        // Use the block number from the receipt to simulate at the same state
-        let block_id = block_number.map(alloy::eips::BlockId::number);
-        
-        // Try to call the transaction to get the revert reason
-        let result = match block_id {
-            Some(block) => self.provider.call(tx.clone()).block(block).await,
-            None => self.provider.call(tx.clone()).await,
-        };
-        
-        match result {
-            Ok(_) => "Transaction reverted (no revert reason available)".to_string(),
-            Err(e) => {
-                let error_str = e.to_string();
-                // Try to extract a meaningful error message
-                // Common patterns: "execution reverted: <reason>", "revert: <reason>"
-                if let Some(pos) = error_str.find("execution reverted:") {
-                    let reason = error_str[pos + 19..].trim();
-                    if reason.is_empty() {
-                        "Execution reverted".to_string()
-                    } else {
-                        reason.to_string()
-                    }
-                } else if let Some(pos) = error_str.find("revert:") {
-                    let reason = error_str[pos + 7..].trim();
-                    if reason.is_empty() {
-                        "Execution reverted".to_string()
-                    } else {
-                        reason.to_string()
-                    }
-                } else if error_str.contains("reverted") || error_str.contains("revert") {
-                    error_str
-                } else {
-                    format!("Execution reverted: {error_str}")
-                }
+        let abi = self
+            .store
+            .contract_for_address(address)
+            .map(|contract_id| self.get_abi_for_contract(&contract_id))
+            .or_else(|| self.attached_abi(address));
+
+        // A proxy emits its implementation's events plus its own ERC-1967 events
+        match self.store.get_proxy(self.state.chain_id, address) {
+            Some(info) => {
+                let abi = self.implementation_abi(info).or(abi).unwrap_or_default();
+                Some(Arc::new(proxy::with_proxy_events(&abi)))
             }
+            None => abi,
         }
     }
 
@@ -2075,6 +5428,16 @@ impl<P: Provider + Clone> App<P> {
                     self.state.focus = Focus::Output;
                 }
             }
+            crate::cards::CardAction::SpeedUp | crate::cards::CardAction::Cancel => {
+                if let crate::cards::Card::Transaction { hash, .. } = card {
+                    let hash = *hash;
+                    let cancel = action == crate::cards::CardAction::Cancel;
+                    match self.in_flight.iter().find(|(_, in_flight)| in_flight.card_hash == Some(hash)) {
+                        Some((&id, _)) => self.replace_transaction(id, cancel),
+                        None => self.add_log_card(format!("Transaction {hash:?} is no longer pending")),
+                    }
+                }
+            }
         }
         Ok(())
     }
@@ -2164,6 +5527,144 @@ impl<P: Provider + Clone> App<P> {
         Ok(())
     }
 
+    async fn handle_diagnostics_key(&mut self, key: KeyEvent) -> Result<()> {
+        let count = self.state.diagnostics.len();
+        match key.code {
+            KeyCode::Esc => {
+                self.state.popup = PopupState::None;
+                self.state.focus = Focus::Sidebar;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                if let PopupState::Diagnostics { selected } = &mut self.state.popup {
+                    *selected = selected.saturating_sub(1);
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if let PopupState::Diagnostics { selected } = &mut self.state.popup {
+                    if *selected + 1 < count {
+                        *selected += 1;
+                    }
+                }
+            }
+            KeyCode::Enter | KeyCode::Char('e') => {
+                if let PopupState::Diagnostics { selected } = self.state.popup {
+                    match self.state.diagnostics.get(selected) {
+                        Some(Diagnostic { file: Some(file), line, column, .. }) => {
+                            self.pending_editor_location =
+                                Some((file.clone(), line.unwrap_or(1), column.unwrap_or(1)));
+                        }
+                        Some(_) => {
+                            self.state.last_action = Some("Diagnostic has no source location".into());
+                        }
+                        None => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_compiler_settings_key(&mut self, key: KeyEvent) -> Result<()> {
+        let PopupState::CompilerSettings { contract_name, contract_path, fields, current } =
+            &mut self.state.popup
+        else {
+            return Ok(());
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                self.state.popup = PopupState::None;
+                self.state.focus = Focus::Sidebar;
+            }
+            KeyCode::Tab | KeyCode::Down => {
+                *current = (*current + 1) % fields.len().max(1);
+            }
+            KeyCode::BackTab | KeyCode::Up => {
+                *current = current.checked_sub(1).unwrap_or(fields.len().saturating_sub(1));
+            }
+            KeyCode::Char(c) => {
+                if let Some(field) = fields.get_mut(*current) {
+                    field.value.push(c);
+                    field.error = None;
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(field) = fields.get_mut(*current) {
+                    field.value.pop();
+                    field.error = None;
+                }
+            }
+            KeyCode::Enter => {
+                let values: Vec<&str> = fields.iter().map(|f| f.value.as_str()).collect();
+                match CompilerSettings::from_fields(&values) {
+                    Ok(settings) => {
+                        log::info!("[SETTINGS] {contract_name}: {settings:?}");
+                        let contract_id = ContractId::new(contract_path.clone(), contract_name.clone());
+                        self.state.last_action = Some(format!(
+                            "Compiler settings for {contract_name}: {}",
+                            settings.summary(BytecodeTarget::Pvm)
+                        ));
+                        self.store.set_compiler_settings(&contract_id, settings);
+                        if let Err(e) = self.store.save() {
+                            self.add_log_card(format!("Failed to save compiler settings: {e}"));
+                        }
+                        self.state.popup = PopupState::None;
+                        self.state.focus = Focus::Sidebar;
+                    }
+                    Err(errors) => {
+                        for (i, err) in errors {
+                            if let Some(field) = fields.get_mut(i) {
+                                field.error = Some(err);
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Open the diagnostics panel for the last build, focused on the first error
+    fn show_diagnostics(&mut self) {
+        let selected = self
+            .state
+            .diagnostics
+            .iter()
+            .position(|d| d.severity == Severity::Error)
+            .unwrap_or(0);
+        self.state.popup = PopupState::Diagnostics { selected };
+        self.state.focus = Focus::CommandPalette;
+    }
+
+    /// Show the diagnostics of a failed build, if the error carries any
+    fn report_build_error(&mut self, error: &anyhow::Error) {
+        if let Some(build_error) = error.downcast_ref::<BuildError>() {
+            self.state.diagnostics = build_error.diagnostics.clone();
+            if !self.state.diagnostics.is_empty() {
+                self.show_diagnostics();
+            }
+        }
+    }
+
+    /// Record the diagnostics of a successful build and surface warnings as a log card
+    fn report_build_warnings(&mut self, diagnostics: Vec<Diagnostic>, what: &str) {
+        self.state.diagnostics = diagnostics;
+        if self.state.diagnostics.is_empty() {
+            return;
+        }
+
+        let summary = diagnostics::summary(&self.state.diagnostics);
+        self.state.output.push_info(format!("Compiled {what} with {summary}"));
+        let mut message = format!("Compiled {what} with {summary}\n");
+        for diagnostic in self.state.diagnostics.iter().take(3) {
+            message.push_str(&format!("\n{diagnostic}"));
+        }
+        message.push_str("\n\nCtrl+P > Compiler diagnostics to view all");
+        self.add_log_card(message);
+    }
+
     fn render_copy_menu(&self, frame: &mut Frame, options: &[crate::cards::CopyOption], selected: usize) {
         use crate::tui::theme;
         use crate::tui::widgets::{Popup, SelectableList};
@@ -2511,21 +6012,25 @@ impl<P: Provider + Clone> App<P> {
     fn add_transaction_card(
         &mut self,
         hash: alloy::primitives::TxHash,
-        status: crate::cards::TransactionStatus,
+        status: TransactionStatus,
         function: String,
         gas: Option<String>,
         contract_name: String,
         contract_address: Option<Address>,
         error_message: Option<String>,
+        compiler: Option<String>,
+        nonce: Option<u64>,
     ) {
         let card = crate::cards::Card::Transaction {
             hash,
             status,
+            nonce,
             function_name: function,
             gas_used: gas,
             contract_name,
             contract_address,
             error_message,
+            compiler,
         };
         self.state.cards.cards.push(card);
         self.state.cards.selected_index = self.state.cards.cards.len() - 1;
@@ -2595,6 +6100,523 @@ fn copy_to_clipboard(text: &str) -> std::result::Result<(), String> {
         .map_err(|e| e.to_string())
 }
 
+/// Send `tx` (or `raw`, the same transaction already signed), wait for a receipt of it or
+/// of a replacement and replay it for the revert reason if it failed, reporting each step
+/// to `updates`
+async fn submit_transaction<P: Provider>(
+    provider: P,
+    tx: TransactionRequest,
+    raw: Option<Bytes>,
+    id: u64,
+    updates: std::sync::mpsc::Sender<TxUpdate>,
+    watched: Watched,
+    policy: ReceiptPolicy,
+) {
+    let sent = match &raw {
+        Some(raw) => provider.send_raw_transaction(raw).await,
+        None => provider.send_transaction(tx.clone()).await,
+    };
+    let hash = match sent {
+        Ok(pending) => *pending.tx_hash(),
+        Err(e) => {
+            let _ = updates.send(TxUpdate::Failed { id, hash: None, error: e.to_string() });
+            return;
+        }
+    };
+    watched.lock().expect("watched hashes lock").push(hash);
+    // Nonce and fees were filled in by the provider; the node knows what they ended up as
+    let sent = match provider.get_transaction_by_hash(hash).await {
+        Ok(sent) => sent.map(|sent| SentTx::of(&sent)),
+        Err(e) => {
+            log::warn!("[TX] fetching {hash:?}: {e}");
+            None
+        }
+    };
+    let _ = updates.send(TxUpdate::Sent { id, hash, sent });
+
+    let update = match pending_tx::wait_for_receipt(&provider, &watched, policy).await {
+        Ok(receipt) => {
+            let revert_reason = if receipt.status() {
+                None
+            } else {
+                Some(revert_reason(&provider, &tx, receipt.block_number).await)
+            };
+            TxUpdate::Confirmed { id, receipt: Box::new(receipt), revert_reason }
+        }
+        Err(error) => TxUpdate::Failed { id, hash: Some(hash), error },
+    };
+    let _ = updates.send(update);
+}
+
+/// Send a replacement for the transaction `sent` from `request`, with fees bumped over its
+/// latest ones and over the network's current price, and add it to the `watched` hashes
+#[allow(clippy::too_many_arguments)]
+async fn send_replacement<P: Provider>(
+    provider: P,
+    request: TransactionRequest,
+    from: Address,
+    sent: SentTx,
+    cancel: bool,
+    id: u64,
+    updates: std::sync::mpsc::Sender<TxUpdate>,
+    watched: Watched,
+) {
+    let network = match sent.fees {
+        Fees::Eip1559 { .. } => provider.estimate_eip1559_fees().await.ok().map(|estimate| Fees::Eip1559 {
+            max_fee_per_gas: estimate.max_fee_per_gas,
+            max_priority_fee_per_gas: estimate.max_priority_fee_per_gas,
+        }),
+        Fees::Legacy { .. } => provider.get_gas_price().await.ok().map(|gas_price| Fees::Legacy { gas_price }),
+    };
+    let fees = sent.fees.bump(network);
+    let tx = pending_tx::replacement(&request, from, sent, fees, cancel);
+
+    let update = match provider.send_transaction(tx).await {
+        Ok(pending) => {
+            let hash = *pending.tx_hash();
+            watched.lock().expect("watched hashes lock").push(hash);
+            TxUpdate::Replaced { id, hash, fees, cancel }
+        }
+        Err(e) => TxUpdate::ReplaceFailed { id, cancel, error: e.to_string() },
+    };
+    let _ = updates.send(update);
+}
+
+/// Sends the transactions of a multi-step flow (proxy deployments and upgrades, manifests)
+/// one after the other from a background task. Each one is tracked and reported like any
+/// other transaction; the flow goes on once the main loop has handled its receipt.
+struct FlowSender<P> {
+    provider: P,
+    updates: std::sync::mpsc::Sender<TxUpdate>,
+    next_id: Arc<AtomicU64>,
+    chain_id: Option<u64>,
+    policy: ReceiptPolicy,
+}
+
+impl<P: Provider + Clone> FlowSender<P> {
+    /// Send `tx` and wait until its receipt has been handled
+    async fn send(&self, mut tx: TransactionRequest, purpose: TxPurpose) -> TxResult {
+        if let Some(chain_id) = self.chain_id {
+            tx = tx.with_chain_id(chain_id);
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let watched = Watched::default();
+        let (done, result) = tokio::sync::oneshot::channel();
+        let tracked = TxUpdate::Tracked {
+            id,
+            request: Box::new(tx.clone()),
+            purpose: Box::new(purpose),
+            watched: watched.clone(),
+            done,
+        };
+        if self.updates.send(tracked).is_err() {
+            return TxResult::NotConfirmed;
+        }
+        submit_transaction(self.provider.clone(), tx, None, id, self.updates.clone(), watched, self.policy).await;
+        result.await.unwrap_or(TxResult::NotConfirmed)
+    }
+
+    /// Send a deployment, returning the new address if it succeeded
+    async fn deploy(&self, tx: TransactionRequest, purpose: TxPurpose) -> Option<Address> {
+        match self.send(tx, purpose).await {
+            TxResult::Deployed(address) => Some(address),
+            _ => None,
+        }
+    }
+
+    /// Send `function` with `args` to `address`, returning whether it succeeded
+    async fn call(&self, function: &Function, address: Address, args: Vec<DynSolValue>, contract_name: &str) -> Result<bool> {
+        let calldata = function
+            .abi_encode_input(&args)
+            .with_context(|| format!("Failed to encode {}()", function.name))?;
+        let tx = TransactionRequest::default().to(address).input(calldata.into());
+        let purpose = TxPurpose::call(function, address, &args, contract_name.to_string());
+        Ok(matches!(self.send(tx, purpose).await, TxResult::Succeeded))
+    }
+}
+
+/// Deploy the implementation, the proxy admin for transparent proxies (owned by `owner`),
+/// then the proxy itself, which is what gets added to the sidebar
+#[allow(clippy::too_many_arguments)]
+async fn deploy_behind_proxy<P: Provider + Clone>(
+    flow: FlowSender<P>,
+    implementation_tx: TransactionRequest,
+    contract_name: String,
+    contract_path: PathBuf,
+    target: BytecodeTarget,
+    settings: CompilerSettings,
+    kind: ProxyKind,
+    init_data: Bytes,
+    code: ProxyCode,
+    owner: Address,
+) -> Result<String> {
+    let purpose = TxPurpose::Deploy {
+        contract_name: contract_name.clone(),
+        contract_path: contract_path.clone(),
+        target,
+        settings,
+        constructor_args: Vec::new(),
+        role: DeploymentRole::Implementation,
+    };
+    let implementation = flow
+        .deploy(implementation_tx, purpose)
+        .await
+        .context("The implementation was not deployed")?;
+
+    let admin = match code.admin {
+        Some(admin_code) => {
+            let tx = TransactionRequest::default().with_deploy_code(proxy::admin_deploy_data(&admin_code, owner));
+            let purpose = TxPurpose::Deploy {
+                contract_name: proxy::ADMIN_CONTRACT.to_string(),
+                contract_path: PathBuf::new(),
+                target,
+                settings: CompilerSettings::default(),
+                constructor_args: Vec::new(),
+                role: DeploymentRole::ProxyAdmin,
+            };
+            Some(flow.deploy(tx, purpose).await.context("The proxy admin was not deployed")?)
+        }
+        None => None,
+    };
+
+    let tx = TransactionRequest::default()
+        .with_deploy_code(proxy::proxy_deploy_data(&code.proxy, kind, implementation, admin, &init_data));
+    let mut proxy_args = vec![format!("implementation: {implementation:?}")];
+    proxy_args.extend(admin.map(|admin| format!("admin: {admin:?}")));
+    proxy_args.push(format!("data: {init_data}"));
+    let purpose = TxPurpose::Deploy {
+        contract_name: contract_name.clone(),
+        contract_path,
+        target,
+        settings: CompilerSettings::default(),
+        constructor_args: proxy_args,
+        role: DeploymentRole::Proxy { kind, implementation, admin },
+    };
+    let proxy_address = flow.deploy(tx, purpose).await.context("The proxy was not deployed")?;
+
+    let mut summary = format!("{contract_name} deployed behind a {kind} proxy at {proxy_address:?}\n\nImplementation: {implementation:?}");
+    if let Some(admin) = admin {
+        summary.push_str(&format!("\nProxy admin:    {admin:?}"));
+    }
+    Ok(summary)
+}
+
+/// Deploy a new implementation, point `proxy_address` at it (running `data` right after)
+/// and record the slots it ends up with
+#[allow(clippy::too_many_arguments)]
+async fn upgrade_proxy<P: Provider + Clone>(
+    flow: FlowSender<P>,
+    implementation_tx: TransactionRequest,
+    contract_name: String,
+    contract_path: PathBuf,
+    target: BytecodeTarget,
+    settings: CompilerSettings,
+    proxy_address: Address,
+    info: ProxyInfo,
+    data: Bytes,
+) -> Result<String> {
+    let purpose = TxPurpose::Deploy {
+        contract_name: contract_name.clone(),
+        contract_path,
+        target,
+        settings,
+        constructor_args: Vec::new(),
+        role: DeploymentRole::Implementation,
+    };
+    let implementation = flow
+        .deploy(implementation_tx, purpose)
+        .await
+        .context("The new implementation was not deployed")?;
+
+    let (function, to, args) = proxy::upgrade_call(proxy_address, &info, implementation, &data)?;
+    let to_name = if to == proxy_address { contract_name.as_str() } else { proxy::ADMIN_CONTRACT };
+    if !flow.call(&function, to, args, to_name).await? {
+        anyhow::bail!("{}() failed, so {proxy_address:?} still runs {:?}", function.name, info.implementation);
+    }
+
+    let updated = proxy::detect(&flow.provider, proxy_address)
+        .await
+        .context("Upgraded, but reading the proxy back failed")?;
+    match updated {
+        Some(updated) if updated.implementation == implementation => {
+            let _ = flow.updates.send(TxUpdate::ProxyUpgraded { proxy: proxy_address, info: updated });
+            Ok(format!("{contract_name} at {proxy_address:?} now runs {implementation:?}"))
+        }
+        _ => anyhow::bail!("{proxy_address:?} still points at its previous implementation"),
+    }
+}
+
+/// A compiled manifest step, with the address this manifest deployed it at before on the
+/// current chain and how many of its calls were sent there
+struct ManifestStepRun {
+    step: manifest::Step,
+    target: BytecodeTarget,
+    settings: CompilerSettings,
+    compiled: CompiledContract,
+    deployed: Option<Address>,
+    calls_sent: usize,
+}
+
+/// Deploy each step of a manifest in order, reusing steps deployed before (as long as code
+/// is still there), then send the step's calls. The deployment and each call are recorded
+/// once confirmed, so a run that failed resumes at the failed call. Stops at the first
+/// failure. Returns a summary of the deployed addresses.
+async fn run_manifest<P: Provider + Clone>(
+    flow: FlowSender<P>,
+    chain_id: u64,
+    path: PathBuf,
+    steps: Vec<ManifestStepRun>,
+    deployer: Address,
+) -> Result<String> {
+    let mut addresses = HashMap::from([(manifest::DEPLOYER_REF.to_string(), deployer)]);
+    // Contract name and ABI of each step, for the calls
+    let mut contracts: HashMap<String, (String, JsonAbi)> = HashMap::new();
+    let mut summary = Vec::new();
+
+    for ManifestStepRun { step, target, settings, compiled, deployed, calls_sent } in steps {
+        contracts.insert(step.name.clone(), (step.contract.clone(), compiled.abi.clone()));
+        let progress = |address, calls| TxUpdate::ManifestStep {
+            chain_id,
+            manifest: path.clone(),
+            step: step.name.clone(),
+            contract_id: ContractId::new(step.path.clone(), step.contract.clone()),
+            address,
+            calls,
+        };
+
+        let existing = match deployed {
+            Some(address) if !flow.provider.get_code_at(address).await?.is_empty() => Some(address),
+            _ => None,
+        };
+        let (address, calls_sent) = match existing {
+            Some(address) => {
+                let _ = flow.updates.send(progress(address, calls_sent));
+                summary.push(format!("{}: {address:?} (existing)", step.name));
+                (address, calls_sent)
+            }
+            None => {
+                let address = deploy_manifest_step(&flow, &step, target, settings, &compiled, &addresses).await?;
+                let _ = flow.updates.send(progress(address, 0));
+                summary.push(format!("{}: {address:?} (deployed)", step.name));
+                (address, 0)
+            }
+        };
+        addresses.insert(step.name.clone(), address);
+
+        for (index, call) in step.calls.iter().enumerate().skip(calls_sent) {
+            let to_name = call.to.as_deref().unwrap_or(&step.name);
+            let to = addresses[to_name];
+            let (contract_name, abi) = &contracts[to_name];
+            let function = manifest::find_function(abi, &call.method, call.args.len())
+                .with_context(|| format!("{}: calling {to_name}", step.name))?;
+            let args = manifest::resolve_args(&call.args, &function.inputs, &addresses)
+                .with_context(|| format!("{}: invalid arguments for {}()", step.name, function.name))?;
+            if !flow.call(function, to, args, contract_name).await? {
+                anyhow::bail!("{}: {}() on {to_name} failed", step.name, function.name);
+            }
+            let _ = flow.updates.send(progress(address, index + 1));
+        }
+    }
+
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    Ok(format!("Manifest {file_name} (chain {chain_id})\n\n{}", summary.join("\n")))
+}
+
+/// Deploy a manifest step with its constructor arguments resolved against the earlier steps
+async fn deploy_manifest_step<P: Provider + Clone>(
+    flow: &FlowSender<P>,
+    step: &manifest::Step,
+    target: BytecodeTarget,
+    settings: CompilerSettings,
+    compiled: &CompiledContract,
+    addresses: &HashMap<String, Address>,
+) -> Result<Address> {
+    let params = compiled
+        .abi
+        .constructor
+        .as_ref()
+        .map(|ctor| ctor.inputs.clone())
+        .unwrap_or_default();
+    let args = manifest::resolve_args(&step.args, &params, addresses)
+        .with_context(|| format!("{}: invalid constructor arguments", step.name))?;
+    let constructor_args = prompts::format_named_args(&params, &args);
+    let mut deploy_data = compiled.bytecode.clone();
+    if !args.is_empty() {
+        deploy_data.extend(DynSolValue::Tuple(args).abi_encode_params());
+    }
+
+    let tx = TransactionRequest::default().with_deploy_code(deploy_data);
+    let purpose = TxPurpose::Deploy {
+        contract_name: step.contract.clone(),
+        contract_path: step.path.clone(),
+        target,
+        settings,
+        constructor_args,
+        role: DeploymentRole::Instance,
+    };
+    flow.deploy(tx, purpose)
+        .await
+        .with_context(|| format!("{}: deploying {} failed", step.name, step.contract))
+}
+
+/// Make sure the CREATE2 factory exists and nothing is deployed at the predicted address yet
+async fn check_create2<P: Provider>(
+    provider: &P,
+    funder: Address,
+    allow_install: bool,
+    pending: &PendingCreate2,
+) -> Result<create2::FactorySetup> {
+    // The factory's address derivation only holds for EVM bytecode
+    if pending.target != BytecodeTarget::Evm {
+        anyhow::bail!("CREATE2 deployments are only supported for EVM, not {}", pending.target);
+    }
+    let factory = create2::ensure_factory(provider, funder, allow_install).await?;
+    let predicted = pending.predicted;
+    if !provider.get_code_at(predicted).await?.is_empty() {
+        anyhow::bail!(
+            "Code already exists at {predicted:?} for salt {}. Use another salt or load the existing instance.",
+            pending.salt
+        );
+    }
+    Ok(factory)
+}
+
+/// Try to get the revert reason for a failed transaction by simulating the call
+async fn revert_reason<P: Provider>(provider: &P, tx: &TransactionRequest, block_number: Option<u64>) -> String {
+    // Use the block number from the receipt to simulate at the same state
+    let block_id = block_number.map(alloy::eips::BlockId::number);
+    
+    // Try to call the transaction to get the revert reason
+    let result = match block_id {
+        Some(block) => provider.call(tx.clone()).block(block).await,
+        None => provider.call(tx.clone()).await,
+    };
+    
+    match result {
+        Ok(_) => "Transaction reverted (no revert reason available)".to_string(),
+        Err(e) => {
+            let error_str = e.to_string();
+            // Try to extract a meaningful error message
+            // Common patterns: "execution reverted: <reason>", "revert: <reason>"
+            if let Some(pos) = error_str.find("execution reverted:") {
+                let reason = error_str[pos + 19..].trim();
+                if reason.is_empty() {
+                    "Execution reverted".to_string()
+                } else {
+                    reason.to_string()
+                }
+            } else if let Some(pos) = error_str.find("revert:") {
+                let reason = error_str[pos + 7..].trim();
+                if reason.is_empty() {
+                    "Execution reverted".to_string()
+                } else {
+                    reason.to_string()
+                }
+            } else if error_str.contains("reverted") || error_str.contains("revert") {
+                error_str
+            } else {
+                format!("Execution reverted: {error_str}")
+            }
+        }
+    }
+}
+
+/// "Read all getters" for an instance with at least two getters to batch
+fn push_getters_node(nodes: &mut Vec<TreeNode>, abi: &JsonAbi, address: Address) {
+    let count = method_list::getters(abi).len();
+    if count > 1 {
+        nodes.push(TreeNode::ReadGetters { address, count });
+    }
+}
+
+/// Method nodes of an expanded instance, including fallback() and receive()
+fn push_method_nodes(nodes: &mut Vec<TreeNode>, abi: &JsonAbi, address: Address) {
+    use crate::method_list::MethodSelection;
+
+    for method in method_list::list_methods(abi, false) {
+        nodes.push(match method.selection {
+            MethodSelection::Function(function) => TreeNode::Method {
+                function,
+                tag: method.tag,
+                instance_address: address,
+            },
+            MethodSelection::Fallback { payable } => TreeNode::Fallback {
+                payable,
+                instance_address: address,
+            },
+            MethodSelection::Receive => TreeNode::Receive { instance_address: address },
+            MethodSelection::Constructor => continue,
+        });
+    }
+}
+
+/// Open `content` in `$EDITOR` (or `$VISUAL`, falling back to vi) and return the saved text
+fn edit_text_in_editor(content: &str) -> Result<String> {
+    use std::process::Command;
+
+    let editor = std::env::var("EDITOR")
+        .or_else(|_| std::env::var("VISUAL"))
+        .unwrap_or_else(|_| "vi".to_string());
+
+    let path = std::env::temp_dir().join(format!(
+        "evm-cli-edit-{}.txt",
+        chrono::Local::now().timestamp_millis()
+    ));
+    std::fs::write(&path, content)
+        .with_context(|| format!("Failed to create temp file: {}", path.display()))?;
+
+    let status = Command::new(&editor)
+        .arg(&path)
+        .status()
+        .with_context(|| format!("Failed to open editor: {editor}"));
+    let text = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+
+    if !status?.success() {
+        anyhow::bail!("Editor exited with error status");
+    }
+    text.with_context(|| format!("Failed to read {}", path.display()))
+}
+
+/// Open `path` in `$EDITOR` (or `$VISUAL`, falling back to vi) positioned at `line`:`column`
+fn open_in_editor_at(path: &Path, line: usize, column: usize) -> Result<()> {
+    use std::process::Command;
+
+    let editor = std::env::var("EDITOR")
+        .or_else(|_| std::env::var("VISUAL"))
+        .unwrap_or_else(|_| "vi".to_string());
+
+    let status = Command::new(&editor)
+        .args(editor_location_args(&editor, path, line, column))
+        .status()
+        .with_context(|| format!("Failed to open {} with {editor}", path.display()))?;
+
+    if !status.success() {
+        anyhow::bail!("Editor exited with error status");
+    }
+
+    Ok(())
+}
+
+/// Command line arguments that make `editor` open `path` at the given position.
+/// Most terminal editors (vi, vim, nvim, nano, emacs, micro, helix) accept `+line`.
+fn editor_location_args(editor: &str, path: &Path, line: usize, column: usize) -> Vec<String> {
+    let program = Path::new(editor)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let path = path.display().to_string();
+
+    match program.as_str() {
+        "code" | "codium" | "cursor" => {
+            vec!["--wait".into(), "-g".into(), format!("{path}:{line}:{column}")]
+        }
+        "subl" | "zed" => vec!["--wait".into(), format!("{path}:{line}:{column}")],
+        "hx" | "helix" => vec![format!("{path}:{line}:{column}")],
+        _ => vec![format!("+{line}"), path],
+    }
+}
+
 fn parse_value(input: &str, type_str: &str) -> std::result::Result<DynSolValue, String> {
     let sol_type: DynSolType = type_str
         .parse()
@@ -2700,3 +6722,271 @@ fn format_key_event(key: &KeyEvent) -> String {
     parts.push(&key_str);
     parts.join("+")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::mock_provider;
+    use tempfile::TempDir;
+
+    fn test_app(dir: &TempDir) -> App<impl Provider + Clone + 'static> {
+        let (provider, _) = mock_provider();
+        let store = DeploymentStore::load_from(Some(dir.path().join("config.json"))).unwrap();
+        let endpoints = Endpoints::from_config(&store.config).unwrap();
+        App::new(provider, store, PrivateKeySigner::random(), endpoints, RpcLog::default(), NodeSender::default())
+    }
+
+    /// Receipt of a successful deployment at `address`
+    fn deployment_receipt(address: Address) -> TransactionReceipt {
+        serde_json::from_value(serde_json::json!({
+            "type": "0x2",
+            "status": "0x1",
+            "cumulativeGasUsed": "0x5208",
+            "logs": [],
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "transactionHash": B256::repeat_byte(0x01),
+            "transactionIndex": "0x0",
+            "blockHash": B256::repeat_byte(0x02),
+            "blockNumber": "0x7",
+            "gasUsed": "0x5208",
+            "effectiveGasPrice": "0x1",
+            "from": Address::repeat_byte(0xee),
+            "to": null,
+            "contractAddress": address,
+        }))
+        .unwrap()
+    }
+
+    /// A forge project with `Token` in src/Token.sol and a run of `forge script` that
+    /// deployed it on `chain_id`, returning the run file
+    fn write_broadcast(root: &Path, chain_id: u64) -> PathBuf {
+        std::fs::write(root.join("foundry.toml"), "[profile.default]\n").unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/Token.sol"), "contract Token {}").unwrap();
+        let dir = root.join(format!("broadcast/Deploy.s.sol/{chain_id}"));
+        std::fs::create_dir_all(&dir).unwrap();
+        let run = serde_json::json!({
+            "transactions": [{
+                "hash": B256::repeat_byte(chain_id as u8),
+                "transactionType": "CREATE",
+                "contractName": "Token",
+                "contractAddress": Address::repeat_byte(0x11),
+                "transaction": { "from": Address::repeat_byte(0xee) },
+            }],
+            "receipts": [{ "transactionHash": B256::repeat_byte(chain_id as u8), "blockNumber": "0x1", "status": "0x1" }],
+            "timestamp": 1700000000,
+            "chain": chain_id,
+        });
+        let path = dir.join("run-latest.json");
+        std::fs::write(&path, run.to_string()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_import_broadcast_per_chain() {
+        let dir = TempDir::new().unwrap();
+        let mut app = test_app(&dir);
+        let project = dir.path().join("project");
+        std::fs::create_dir_all(&project).unwrap();
+        let local = write_broadcast(&project, 31337);
+        let sepolia = write_broadcast(&project, 11155111);
+
+        app.try_import_broadcast(&local).unwrap();
+        app.try_import_broadcast(&sepolia).unwrap();
+        // Imported again: kept as recorded
+        let summary = app.try_import_broadcast(&local).unwrap();
+        assert!(summary.contains("already recorded on chain 31337"), "{summary}");
+
+        let token = ContractId::new(project.canonicalize().unwrap().join("src/Token.sol"), "Token".to_string());
+        let address = Address::repeat_byte(0x11);
+        let records = app.store.get_deployment_records(&token);
+        assert_eq!(records.len(), 2);
+        for chain_id in [31337, 11155111] {
+            let record = app.store.get_deployment(&token, Some(chain_id), address).unwrap();
+            assert_eq!(record.chain_id, Some(chain_id));
+            assert_eq!(record.tx_hash, Some(B256::repeat_byte(chain_id as u8)));
+        }
+    }
+
+    #[test]
+    fn test_dead_instances_are_per_chain() {
+        let dir = TempDir::new().unwrap();
+        let mut app = test_app(&dir);
+        let token = ContractId::new(PathBuf::from("/test/Token.sol"), "Token".to_string());
+        app.abi_cache.borrow_mut().insert(token.path.clone(), CachedAbis::Loaded(vec![(token.name.clone(), Arc::new(JsonAbi::new()))]));
+        let address = Address::repeat_byte(0x11);
+        for chain_id in [31337, 11155111] {
+            app.store.record_deployment(&token, DeploymentRecord { chain_id: Some(chain_id), ..DeploymentRecord::new(address) });
+        }
+
+        // Gone from the restarted dev node, still live on the testnet
+        app.state.chain_id = Some(31337);
+        app.apply_instance_check(InstanceCheck { chain_id: 31337, dead: vec![("Token".to_string(), address, "no code at this address")] });
+        assert!(app.state.sidebar.is_dead(&address));
+
+        let export = dir.path().join("book");
+        let summary = app.export_address_book(&export, ExportFormats::default()).unwrap();
+        assert!(summary.contains("dead on the connected node"), "{summary}");
+        assert!(export.join("11155111.json").is_file());
+        assert!(!export.join("31337.json").exists());
+
+        app.prune_dead_instances();
+        let records = app.store.get_deployment_records(&token);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].chain_id, Some(11155111));
+        assert!(!app.state.sidebar.is_dead(&address));
+    }
+
+    #[tokio::test]
+    async fn test_chainless_instances_are_checked() {
+        let dir = TempDir::new().unwrap();
+        let (provider, asserter) = mock_provider();
+        let store = DeploymentStore::load_from(Some(dir.path().join("config.json"))).unwrap();
+        let endpoints = Endpoints::from_config(&store.config).unwrap();
+        let mut app = App::new(provider, store, PrivateKeySigner::random(), endpoints, RpcLog::default(), NodeSender::default());
+        let token = ContractId::new(PathBuf::from("/test/Token.sol"), "Token".to_string());
+        let loaded = Address::repeat_byte(0x11);
+        let testnet = Address::repeat_byte(0x22);
+        app.store.add_deployment(&token, loaded);
+        app.store.record_deployment(&token, DeploymentRecord { chain_id: Some(11155111), ..DeploymentRecord::new(testnet) });
+
+        // Only the instance without a chain is looked up on the dev node, and has no code there
+        app.state.connection = ConnectionStatus::Connected;
+        app.state.chain_id = Some(31337);
+        asserter.push_success(&Bytes::new());
+        app.check_instances(31337);
+        app.wait_for_instance_check().await;
+        assert!(app.state.sidebar.is_dead(&loaded));
+        assert!(!app.state.sidebar.is_dead(&testnet));
+
+        app.prune_dead_instances();
+        assert_eq!(app.store.get_deployments(&token), vec![testnet]);
+    }
+
+    #[tokio::test]
+    async fn test_manifest_resumes_at_failed_call() {
+        let (provider, asserter) = mock_provider();
+        let (updates, received) = std::sync::mpsc::channel();
+        // Stands in for the main loop: no transaction gets confirmed
+        let main_loop = std::thread::spawn(move || {
+            received
+                .iter()
+                .filter_map(|update| match update {
+                    TxUpdate::ManifestStep { step, address, calls, .. } => Some((step, address, calls)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        });
+        let flow = FlowSender {
+            provider,
+            updates,
+            next_id: Arc::new(AtomicU64::new(0)),
+            chain_id: Some(31337),
+            policy: ReceiptPolicy::from_config(&crate::store::Config::default()),
+        };
+        let step: manifest::Step = serde_json::from_value(serde_json::json!({
+            "name": "token",
+            "contract": "Token",
+            "path": "/test/Token.sol",
+            "calls": [{ "method": "initialize" }, { "method": "unpause" }],
+        }))
+        .unwrap();
+        let abi = attach::abi_from_signatures(&["function initialize()".to_string(), "function unpause()".to_string()]).unwrap();
+        let compiled = CompiledContract { name: "Token".to_string(), abi, bytecode: vec![0x60, 0x00], diagnostics: Vec::new() };
+
+        // Deployed and initialized by an earlier run, which failed to unpause
+        let token = Address::repeat_byte(0x11);
+        asserter.push_success(&Bytes::from(vec![0x60, 0x00]));
+        let run = ManifestStepRun { step, target: BytecodeTarget::Evm, settings: CompilerSettings::default(), compiled, deployed: Some(token), calls_sent: 1 };
+        let error = run_manifest(flow, 31337, PathBuf::from("/test/deploy.json"), vec![run], Address::ZERO).await.unwrap_err();
+        assert_eq!(error.to_string(), "token: unpause() on token failed");
+        assert_eq!(main_loop.join().unwrap(), vec![("token".to_string(), token, 1)]);
+    }
+
+    #[test]
+    fn test_upgrade_picks_implementation_and_call() {
+        let dir = TempDir::new().unwrap();
+        let mut app = test_app(&dir);
+        let v1 = ContractId::new(PathBuf::from("/test/Token.sol"), "Token".to_string());
+        let v2 = ContractId::new(PathBuf::from("/test/TokenV2.sol"), "TokenV2".to_string());
+        for (id, signatures) in [
+            (&v1, vec!["function mint(uint256)"]),
+            (&v2, vec!["function reinitialize(uint256 fee)", "function fee() view returns (uint256)"]),
+        ] {
+            let abi = attach::abi_from_signatures(&signatures.into_iter().map(String::from).collect::<Vec<_>>()).unwrap();
+            app.abi_cache.borrow_mut().insert(id.path.clone(), CachedAbis::Loaded(vec![(id.name.clone(), Arc::new(abi))]));
+        }
+        let proxy = Address::repeat_byte(0xaa);
+        let info = ProxyInfo { kind: ProxyKind::Uups, implementation: Address::repeat_byte(0x11), admin: None, beacon: None };
+        app.pending_action = PendingAction::Deploy {
+            contract_name: v1.name.clone(),
+            contract_path: v1.path.clone(),
+            abi: app.get_abi_for_contract(&v1),
+            mode: DeployMode::Upgrade { proxy, info, target: BytecodeTarget::Pvm, call: None },
+        };
+        app.state.popup = PopupState::ImplementationSelector {
+            proxy,
+            contracts: vec![(v1.name.clone(), v1.path.clone()), (v2.name.clone(), v2.path.clone())],
+            selected: 0,
+        };
+
+        // TokenV2, then its reinitializer; the view function is not offered
+        app.handle_implementation_selector_key(KeyEvent::from(KeyCode::Down));
+        app.handle_implementation_selector_key(KeyEvent::from(KeyCode::Enter));
+        let PopupState::UpgradeCallSelector { functions, .. } = &app.state.popup else { panic!("no call selector") };
+        assert_eq!(functions.iter().map(|f| f.name.as_str()).collect::<Vec<_>>(), ["reinitialize"]);
+        app.handle_upgrade_call_selector_key(KeyEvent::from(KeyCode::Down));
+        app.handle_upgrade_call_selector_key(KeyEvent::from(KeyCode::Enter));
+
+        let PopupState::ParameterPopup { method_name, params, bytecode_target, .. } = &app.state.popup else { panic!("no parameter popup") };
+        assert_eq!(method_name, "constructor, then reinitialize");
+        assert_eq!(params.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(), ["reinitialize: fee"]);
+        assert_eq!(*bytecode_target, Some(BytecodeTarget::Pvm));
+        let PendingAction::Deploy { contract_name, abi, mode: DeployMode::Upgrade { call: Some(call), .. }, .. } = &app.pending_action else {
+            panic!("no upgrade call");
+        };
+        assert_eq!(contract_name, "TokenV2");
+        assert_eq!(call.name, "reinitialize");
+
+        // The popup's values split back into the (empty) constructor's and the call's
+        let fee = DynSolValue::Uint(U256::from(5), 256);
+        assert_eq!(split_constructor_args(abi, vec![fee.clone()]), (Vec::new(), vec![fee]));
+    }
+
+    #[test]
+    fn test_upgrade_switches_proxy_abi() {
+        let dir = TempDir::new().unwrap();
+        let mut app = test_app(&dir);
+        let v1 = ContractId::new(PathBuf::from("/test/Token.sol"), "Token".to_string());
+        let v2 = ContractId::new(PathBuf::from("/test/TokenV2.sol"), "TokenV2".to_string());
+        for (id, signatures) in [
+            (&v1, vec!["function mint(uint256)"]),
+            (&v2, vec!["function mint(uint256)", "function burn(uint256)"]),
+        ] {
+            let abi = attach::abi_from_signatures(&signatures.into_iter().map(String::from).collect::<Vec<_>>()).unwrap();
+            app.abi_cache.borrow_mut().insert(id.path.clone(), CachedAbis::Loaded(vec![(id.name.clone(), Arc::new(abi))]));
+        }
+
+        // A proxy deployed in front of Token
+        let proxy = Address::repeat_byte(0xaa);
+        let v1_implementation = Address::repeat_byte(0x11);
+        app.finish_deployment(&deployment_receipt(v1_implementation), None, v1.name.clone(), v1.path.clone(), BytecodeTarget::Evm, CompilerSettings::default(), Vec::new(), DeploymentRole::Implementation);
+        app.store.add_deployment(&v1, proxy);
+        let info = ProxyInfo { kind: ProxyKind::Uups, implementation: v1_implementation, admin: None, beacon: None };
+        app.store.set_proxy(None, proxy, Some(info.clone()));
+        let abi = app.find_abi_for_address(proxy).unwrap();
+        assert!(abi.function("burn").is_none());
+
+        // Upgraded to TokenV2, whose new function is reachable through the proxy
+        let v2_implementation = Address::repeat_byte(0x22);
+        let result = app.finish_deployment(&deployment_receipt(v2_implementation), None, v2.name.clone(), v2.path.clone(), BytecodeTarget::Evm, CompilerSettings::default(), Vec::new(), DeploymentRole::Implementation);
+        assert!(matches!(result, TxResult::Deployed(address) if address == v2_implementation));
+        app.store.set_proxy(None, proxy, Some(ProxyInfo { implementation: v2_implementation, ..info }));
+        let abi = app.find_abi_for_address(proxy).unwrap();
+        assert!(abi.function("burn").is_some());
+        assert!(abi.event("Upgraded").is_some());
+
+        // Implementations stay out of the sidebar
+        assert_eq!(app.store.get_deployments(&v2), Vec::<Address>::new());
+    }
+}
@@ -0,0 +1,191 @@
+use alloy::json_abi::{Fallback, JsonAbi, Receive, StateMutability};
+use alloy::primitives::Address;
+use anyhow::{bail, Context, Result};
+
+/// Name shown in the sidebar when none is given
+pub const DEFAULT_NAME: &str = "Attached";
+
+/// Starting point of the "Attach by signature" editor
+pub const TEMPLATE: &str = "\
+# Attach a contract by address and human-readable ABI.
+# Save and quit to attach; leave address and ABI empty to cancel.
+#
+# One ABI item per line, for example:
+#   function balanceOf(address owner) view returns (uint256)
+#   function transfer(address to, uint256 amount) returns (bool)
+#   event Transfer(address indexed from, address indexed to, uint256 value)
+#   error InsufficientBalance(uint256 available, uint256 required)
+#   receive() external payable
+
+address:
+name:
+";
+
+/// An address with the ABI items typed for it
+#[derive(Debug, Clone)]
+pub struct AttachSpec {
+    pub address: Address,
+    pub name: String,
+    /// The ABI lines as written, which is what gets stored
+    pub signatures: Vec<String>,
+    pub abi: JsonAbi,
+}
+
+/// Parse the editor contents. `Ok(None)` means nothing was filled in.
+pub fn parse(text: &str) -> Result<Option<AttachSpec>> {
+    let mut address = None;
+    let mut name = None;
+    let mut signatures = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+            continue;
+        }
+        if let Some(value) = line.strip_prefix("address:") {
+            let value = value.trim();
+            if !value.is_empty() {
+                let parsed: Address = value
+                    .parse()
+                    .with_context(|| format!("Line {}: invalid address \"{value}\"", i + 1))?;
+                address = Some(parsed);
+            }
+        } else if let Some(value) = line.strip_prefix("name:") {
+            let value = value.trim();
+            if !value.is_empty() {
+                name = Some(value.to_string());
+            }
+        } else {
+            // Accept items pasted from Solidity interfaces or JS arrays
+            let item = line
+                .trim_end_matches([';', ','])
+                .trim_matches(['"', '\''])
+                .trim();
+            let item = item.to_string();
+            abi_from_signatures(std::slice::from_ref(&item))
+                .with_context(|| format!("Line {}: \"{item}\"", i + 1))?;
+            signatures.push(item);
+        }
+    }
+
+    let Some(address) = address else {
+        if signatures.is_empty() {
+            return Ok(None);
+        }
+        bail!("Missing \"address: 0x...\" line");
+    };
+    if signatures.is_empty() {
+        bail!("No ABI items given for {address:?}");
+    }
+
+    Ok(Some(AttachSpec {
+        address,
+        name: name.unwrap_or_else(|| DEFAULT_NAME.to_string()),
+        abi: abi_from_signatures(&signatures)?,
+        signatures,
+    }))
+}
+
+/// Build the ABI of an attached contract from its stored signatures
+pub fn abi_from_signatures(signatures: &[String]) -> Result<JsonAbi> {
+    // alloy's human-readable parser has no syntax for fallback() and receive()
+    let (special, items): (Vec<&str>, Vec<&str>) = signatures
+        .iter()
+        .map(String::as_str)
+        .partition(|item| item.starts_with("fallback(") || item.starts_with("receive("));
+    let mut abi = JsonAbi::parse(items).context("Invalid ABI signatures")?;
+    for item in special {
+        let payable = item.split_whitespace().any(|word| word == "payable");
+        if item.starts_with("receive(") {
+            if !payable {
+                bail!("receive() must be payable: \"{item}\"");
+            }
+            abi.receive = Some(Receive {
+                state_mutability: StateMutability::Payable,
+            });
+        } else {
+            abi.fallback = Some(Fallback {
+                state_mutability: if payable {
+                    StateMutability::Payable
+                } else {
+                    StateMutability::NonPayable
+                },
+            });
+        }
+    }
+    Ok(abi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_is_empty() {
+        assert!(parse(TEMPLATE).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parse() {
+        let text = format!(
+            "{TEMPLATE}\
+             address: 0x5fbdb2315678afecb367f032d93f642f64180aa3\n\
+             name: Token\n\
+             function balanceOf(address owner) view returns (uint256);\n\
+             \"function transfer(address to, uint256 amount) returns (bool)\",\n\
+             event Transfer(address indexed from, address indexed to, uint256 value)\n"
+        );
+        let spec = parse(&text).unwrap().unwrap();
+        assert_eq!(
+            spec.address,
+            "0x5fbdb2315678afecb367f032d93f642f64180aa3"
+                .parse::<Address>()
+                .unwrap()
+        );
+        assert_eq!(spec.name, "Token");
+        assert_eq!(spec.signatures.len(), 3);
+        assert_eq!(
+            spec.signatures[1],
+            "function transfer(address to, uint256 amount) returns (bool)"
+        );
+        assert!(spec.abi.function("balanceOf").is_some());
+        assert!(spec.abi.event("Transfer").is_some());
+
+        let rebuilt = abi_from_signatures(&spec.signatures).unwrap();
+        assert_eq!(rebuilt, spec.abi);
+    }
+
+    #[test]
+    fn test_fallback_and_receive() {
+        let spec = parse(
+            "address: 0x5fbdb2315678afecb367f032d93f642f64180aa3\n\
+             receive() external payable;\n\
+             fallback() external\n",
+        )
+        .unwrap()
+        .unwrap();
+        assert!(spec.abi.receive.is_some());
+        assert_eq!(
+            spec.abi.fallback.unwrap().state_mutability,
+            StateMutability::NonPayable
+        );
+
+        let bad = parse("address: 0x5fbdb2315678afecb367f032d93f642f64180aa3\nreceive() external");
+        assert!(bad.unwrap_err().to_string().starts_with("Line 2"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let missing_address = parse("function totalSupply() view returns (uint256)");
+        assert!(missing_address.unwrap_err().to_string().contains("address"));
+
+        let no_items = parse("address: 0x5fbdb2315678afecb367f032d93f642f64180aa3");
+        assert!(no_items.is_err());
+
+        let bad_item =
+            parse("address: 0x5fbdb2315678afecb367f032d93f642f64180aa3\nfunction broken(uint256");
+        assert!(bad_item.unwrap_err().to_string().starts_with("Line 2"));
+
+        assert!(parse("address: 0x1234").is_err());
+    }
+}
@@ -0,0 +1,95 @@
+use alloy::providers::{Provider, RootProvider};
+use alloy::rpc::client::RpcClient;
+use alloy::transports::{RpcError, TransportConnect, TransportErrorKind};
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::provider::Endpoints;
+
+/// How often the head is polled over HTTP, where there are no subscriptions
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Pause before reconnecting after the connection is lost
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// What the watcher reports to the app
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockEvent {
+    /// A new head; `live` when it came from an `eth_subscribe("newHeads")` subscription
+    Head { number: u64, live: bool },
+    /// The connection was lost; the watcher reconnects and resubscribes on its own
+    Lost(String),
+}
+
+/// Follow the chain head on its own connection to the active endpoint until the app stops
+/// listening. Runs as a background task: `tokio::spawn(block_watcher::watch(endpoints, tx))`.
+pub async fn watch(endpoints: Endpoints, events: Sender<BlockEvent>) {
+    loop {
+        let reason = match follow(&endpoints, &events).await {
+            Ok(()) => return,
+            Err(reason) => reason,
+        };
+        log::warn!("[BLOCKS] {reason}");
+        if events.send(BlockEvent::Lost(reason)).is_err() {
+            return;
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// Connect, then subscribe to new heads or poll for them. Returns `Ok` once the app is
+/// gone and `Err` with the reason when the connection is lost.
+async fn follow(endpoints: &Endpoints, events: &Sender<BlockEvent>) -> Result<(), String> {
+    let endpoint = endpoints.active().clone();
+    let transport = endpoint
+        .connect()
+        .await
+        .map_err(|e| format!("connection to {} failed: {e}", endpoint.display))?;
+    let client = RpcClient::new(transport, endpoint.connection.is_local());
+    let provider: RootProvider = RootProvider::new(client);
+
+    match provider.subscribe_blocks().await {
+        Ok(mut subscription) => {
+            log::info!("[BLOCKS] Subscribed to new heads");
+            loop {
+                match subscription.recv().await {
+                    Ok(header) => {
+                        let head = BlockEvent::Head {
+                            number: header.number,
+                            live: true,
+                        };
+                        if events.send(head).is_err() {
+                            return Ok(());
+                        }
+                    }
+                    // Missed a few heads while busy; the next one catches up
+                    Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => return Err("new heads subscription closed".into()),
+                }
+            }
+        }
+        Err(RpcError::Transport(TransportErrorKind::PubsubUnavailable)) => {
+            log::info!("[BLOCKS] No subscriptions over this transport, polling the head");
+            let mut last = None;
+            loop {
+                let number = provider
+                    .get_block_number()
+                    .await
+                    .map_err(|e| format!("block number poll failed: {e}"))?;
+                if last != Some(number) {
+                    last = Some(number);
+                    let head = BlockEvent::Head {
+                        number,
+                        live: false,
+                    };
+                    if events.send(head).is_err() {
+                        return Ok(());
+                    }
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+        Err(e) => Err(format!("eth_subscribe failed: {e}")),
+    }
+}
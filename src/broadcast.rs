@@ -0,0 +1,329 @@
+use alloy::primitives::{Address, B256};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directories never searched for sources: dependencies and build/broadcast output
+const SKIPPED_DIRS: &[&str] = &[
+    "lib",
+    "node_modules",
+    "out",
+    "out-evm",
+    "out-pvm",
+    "cache",
+    "broadcast",
+];
+
+/// A `forge script --broadcast` run, as written to
+/// `broadcast/<Script>.s.sol/<chain>/run-latest.json`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Broadcast {
+    pub chain: u64,
+    #[serde(default)]
+    transactions: Vec<BroadcastTx>,
+    #[serde(default)]
+    receipts: Vec<BroadcastReceipt>,
+    /// Seconds, or milliseconds in newer forge versions
+    #[serde(default)]
+    timestamp: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BroadcastTx {
+    hash: Option<B256>,
+    transaction_type: String,
+    contract_name: Option<String>,
+    contract_address: Option<Address>,
+    #[serde(default)]
+    arguments: Option<Vec<String>>,
+    #[serde(default)]
+    transaction: BroadcastTxRequest,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct BroadcastTxRequest {
+    from: Option<Address>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BroadcastReceipt {
+    transaction_hash: B256,
+    block_number: Option<String>,
+    status: Option<String>,
+}
+
+/// A contract created by the script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Created {
+    pub contract_name: String,
+    pub address: Address,
+    pub tx_hash: Option<B256>,
+    pub block_number: Option<u64>,
+    pub deployer: Option<Address>,
+    pub arguments: Vec<String>,
+}
+
+impl Broadcast {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| {
+            format!(
+                "Failed to parse {} as a forge broadcast file",
+                path.display()
+            )
+        })
+    }
+
+    /// Contracts created by CREATE/CREATE2 transactions that did not revert
+    pub fn created(&self) -> Vec<Created> {
+        self.transactions
+            .iter()
+            .filter(|tx| matches!(tx.transaction_type.as_str(), "CREATE" | "CREATE2"))
+            .filter_map(|tx| {
+                let receipt = tx
+                    .hash
+                    .and_then(|hash| self.receipts.iter().find(|r| r.transaction_hash == hash));
+                if receipt.and_then(|r| r.status.as_deref()) == Some("0x0") {
+                    return None;
+                }
+                Some(Created {
+                    contract_name: tx.contract_name.clone()?,
+                    address: tx.contract_address?,
+                    tx_hash: tx.hash,
+                    block_number: receipt
+                        .and_then(|r| r.block_number.as_deref())
+                        .and_then(parse_quantity),
+                    deployer: tx.transaction.from,
+                    arguments: tx.arguments.clone().unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+
+    /// When the script ran, as RFC 3339
+    pub fn time(&self) -> Option<String> {
+        let timestamp = self.timestamp? as i64;
+        let time = if timestamp > 10_000_000_000 {
+            chrono::DateTime::from_timestamp_millis(timestamp)
+        } else {
+            chrono::DateTime::from_timestamp(timestamp, 0)
+        };
+        time.map(|t| t.to_rfc3339())
+    }
+}
+
+/// Receipt numbers are hex quantities (`"0x1a"`); accept decimal too
+fn parse_quantity(value: &str) -> Option<u64> {
+    match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+/// The forge project a broadcast file belongs to: the nearest ancestor with a foundry.toml
+pub fn project_root(broadcast_file: &Path) -> Option<PathBuf> {
+    let path = broadcast_file.canonicalize().ok()?;
+    path.ancestors()
+        .find(|dir| dir.join("foundry.toml").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Source files in `root` defining `contract_name`: from forge's `out/` artifacts when
+/// present, otherwise by scanning the project's .sol files
+pub fn find_sources(root: &Path, contract_name: &str) -> Vec<PathBuf> {
+    let from_artifacts = artifact_sources(root, contract_name);
+    if !from_artifacts.is_empty() {
+        return from_artifacts;
+    }
+    let mut found = Vec::new();
+    scan_sources(root, contract_name, &mut found);
+    found.sort();
+    found
+}
+
+/// `out/<File>.sol/<Contract>.json` records the source it was compiled from
+fn artifact_sources(root: &Path, contract_name: &str) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(root.join("out")) else {
+        return Vec::new();
+    };
+    let mut found: Vec<PathBuf> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let artifact = entry.path().join(format!("{contract_name}.json"));
+            let json: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(artifact).ok()?).ok()?;
+            let from_metadata = json["metadata"]["settings"]["compilationTarget"]
+                .as_object()
+                .and_then(|targets| {
+                    targets
+                        .iter()
+                        .find(|(_, name)| name.as_str() == Some(contract_name))
+                })
+                .map(|(path, _)| path.clone());
+            let source =
+                from_metadata.or_else(|| json["ast"]["absolutePath"].as_str().map(String::from))?;
+            let source = root.join(source);
+            source.is_file().then_some(source)
+        })
+        .collect();
+    found.sort();
+    found.dedup();
+    found
+}
+
+fn scan_sources(dir: &Path, contract_name: &str, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str()) {
+                scan_sources(&path, contract_name, found);
+            }
+        } else if name.ends_with(".sol")
+            && fs::read_to_string(&path).is_ok_and(|source| defines(&source, contract_name))
+        {
+            found.push(path);
+        }
+    }
+}
+
+/// Whether `source` declares a deployable `contract` (or `library`) named `name`
+fn defines(source: &str, name: &str) -> bool {
+    let tokens: Vec<&str> = source.split_whitespace().collect();
+    tokens.iter().enumerate().skip(1).any(|(i, token)| {
+        matches!(tokens[i - 1], "contract" | "library")
+            && (i < 2 || tokens[i - 2] != "abstract")
+            && token.split('{').next() == Some(name)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const RUN: &str = r#"{
+        "transactions": [
+            {
+                "hash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+                "transactionType": "CREATE",
+                "contractName": "Token",
+                "contractAddress": "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+                "function": null,
+                "arguments": ["\"Token\"", "18"],
+                "transaction": { "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266", "nonce": "0x0" },
+                "additionalContracts": [],
+                "isFixedGasLimit": false
+            },
+            {
+                "hash": "0x2222222222222222222222222222222222222222222222222222222222222222",
+                "transactionType": "CALL",
+                "contractName": "Token",
+                "contractAddress": "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+                "function": "mint(address,uint256)",
+                "arguments": ["0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266", "1"],
+                "transaction": { "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266" }
+            },
+            {
+                "hash": "0x3333333333333333333333333333333333333333333333333333333333333333",
+                "transactionType": "CREATE2",
+                "contractName": "Vault",
+                "contractAddress": "0xe7f1725e7734ce288f8367e1bb143e90bb3f0512",
+                "arguments": null,
+                "transaction": { "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266" }
+            },
+            {
+                "hash": "0x4444444444444444444444444444444444444444444444444444444444444444",
+                "transactionType": "CREATE",
+                "contractName": "Broken",
+                "contractAddress": "0x9fe46736679d2d9a65f0992f2272de9f3c7fa6e0",
+                "transaction": { "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266" }
+            }
+        ],
+        "receipts": [
+            { "transactionHash": "0x1111111111111111111111111111111111111111111111111111111111111111", "blockNumber": "0x1", "status": "0x1" },
+            { "transactionHash": "0x3333333333333333333333333333333333333333333333333333333333333333", "blockNumber": "0x3", "status": "0x1" },
+            { "transactionHash": "0x4444444444444444444444444444444444444444444444444444444444444444", "blockNumber": "0x4", "status": "0x0" }
+        ],
+        "libraries": [],
+        "pending": [],
+        "returns": {},
+        "timestamp": 1700000000,
+        "chain": 31337,
+        "commit": "abc1234"
+    }"#;
+
+    #[test]
+    fn test_created_contracts() {
+        let broadcast: Broadcast = serde_json::from_str(RUN).unwrap();
+        assert_eq!(broadcast.chain, 31337);
+
+        let created = broadcast.created();
+        let names: Vec<_> = created.iter().map(|c| c.contract_name.as_str()).collect();
+        // Calls and reverted deployments are left out
+        assert_eq!(names, ["Token", "Vault"]);
+        assert_eq!(created[0].block_number, Some(1));
+        assert_eq!(created[0].arguments, ["\"Token\"", "18"]);
+        assert_eq!(
+            created[0].deployer,
+            Some(
+                "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+                    .parse()
+                    .unwrap()
+            )
+        );
+        assert!(created[1].arguments.is_empty());
+        assert_eq!(
+            broadcast.time().as_deref(),
+            Some("2023-11-14T22:13:20+00:00")
+        );
+    }
+
+    #[test]
+    fn test_find_sources() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::write(root.join("foundry.toml"), "[profile.default]\n").unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("lib/dep/src")).unwrap();
+        fs::create_dir_all(root.join("broadcast/Deploy.s.sol/31337")).unwrap();
+        fs::write(
+            root.join("src/Token.sol"),
+            "contract Token is ERC20 {}\ncontract TokenFactory{}",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/Vault.sol"),
+            "abstract contract Base {}\ncontract Vault{}",
+        )
+        .unwrap();
+        fs::write(root.join("lib/dep/src/Token.sol"), "contract Token {}").unwrap();
+        let run = root.join("broadcast/Deploy.s.sol/31337/run-latest.json");
+        fs::write(&run, RUN).unwrap();
+
+        let root = project_root(&run).unwrap();
+        assert_eq!(root, dir.path().canonicalize().unwrap());
+        // Dependencies under lib/ are not matched
+        assert_eq!(find_sources(&root, "Token"), [root.join("src/Token.sol")]);
+        assert_eq!(find_sources(&root, "Vault"), [root.join("src/Vault.sol")]);
+        assert!(find_sources(&root, "Base").is_empty());
+
+        // Artifacts take precedence over scanning
+        fs::create_dir_all(root.join("out/Token.sol")).unwrap();
+        fs::create_dir_all(root.join("other")).unwrap();
+        fs::write(root.join("other/Token.sol"), "contract Token {}").unwrap();
+        fs::write(
+            root.join("out/Token.sol/Token.json"),
+            r#"{ "metadata": { "settings": { "compilationTarget": { "other/Token.sol": "Token" } } } }"#,
+        )
+        .unwrap();
+        assert_eq!(find_sources(&root, "Token"), [root.join("other/Token.sol")]);
+    }
+}
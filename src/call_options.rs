@@ -0,0 +1,264 @@
+use alloy::eips::{BlockId, BlockNumberOrTag};
+use alloy::primitives::utils::parse_units;
+use alloy::primitives::{Address, Bytes, B256, U256};
+use alloy::rpc::types::state::{AccountOverride, StateOverride};
+
+/// Labels and placeholders of the optional fields that follow the parameters of a view call
+pub const CALL_OPTION_FIELDS: [(&str, &str); 3] = [
+    (
+        "block (optional)",
+        "latest, pending, safe, finalized, earliest, number or 0x hash",
+    ),
+    ("from (optional)", "0x... (default: your account)"),
+    (
+        "state overrides (optional)",
+        "0xADDR.balance=1ether; 0xADDR.nonce=5; 0xADDR.code=0x...; 0xADDR.slot[0x0]=0x1",
+    ),
+];
+
+/// Where and as whom an `eth_call` runs, and with what hypothetical state
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CallOptions {
+    pub block: Option<BlockId>,
+    pub from: Option<Address>,
+    pub overrides: Option<StateOverride>,
+}
+
+impl CallOptions {
+    /// Parse the call option fields (block, from, overrides); errors carry the field index
+    pub fn parse(values: &[&str]) -> Result<Self, Vec<(usize, String)>> {
+        let mut options = Self::default();
+        let mut errors = Vec::new();
+        let value = |i: usize| values.get(i).map(|v| v.trim()).unwrap_or_default();
+
+        match parse_block(value(0)) {
+            Ok(block) => options.block = block,
+            Err(e) => errors.push((0, e)),
+        }
+        if !value(1).is_empty() {
+            match value(1).parse() {
+                Ok(from) => options.from = Some(from),
+                Err(_) => errors.push((1, "Invalid address format".to_string())),
+            }
+        }
+        match parse_overrides(value(2)) {
+            Ok(overrides) if overrides.is_empty() => {}
+            Ok(overrides) => options.overrides = Some(overrides),
+            Err(e) => errors.push((2, e)),
+        }
+
+        if errors.is_empty() {
+            Ok(options)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// `block 19000000, from 0x..., 2 overrides`, or None for a plain call at latest
+    pub fn describe(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(block) = self.block {
+            parts.push(format!("block {}", describe_block(block)));
+        }
+        if let Some(from) = self.from {
+            parts.push(format!("from {from:?}"));
+        }
+        if let Some(overrides) = &self.overrides {
+            let count: usize = overrides.values().map(override_count).sum();
+            let plural = if count == 1 { "" } else { "s" };
+            parts.push(format!("{count} state override{plural}"));
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+}
+
+/// Block numbers in decimal (`BlockId`'s Display prints them as hex)
+fn describe_block(block: BlockId) -> String {
+    match block {
+        BlockId::Number(BlockNumberOrTag::Number(number)) => number.to_string(),
+        BlockId::Number(tag) => tag.to_string(),
+        BlockId::Hash(hash) => format!("{:?}", hash.block_hash),
+    }
+}
+
+fn override_count(account: &AccountOverride) -> usize {
+    usize::from(account.balance.is_some())
+        + usize::from(account.nonce.is_some())
+        + usize::from(account.code.is_some())
+        + account.state_diff.as_ref().map_or(0, |slots| slots.len())
+}
+
+/// `latest` when empty
+fn parse_block(input: &str) -> Result<Option<BlockId>, String> {
+    if input.is_empty() {
+        return Ok(None);
+    }
+    input.to_lowercase().parse().map(Some).map_err(|_| {
+        "Expected a tag (latest, pending, safe, finalized, earliest), a number or a block hash"
+            .to_string()
+    })
+}
+
+/// `0xADDR.balance=<wei|Nether|Ngwei>`, `0xADDR.nonce=<n>`, `0xADDR.code=<hex>` and
+/// `0xADDR.slot[<key>]=<value>`, separated by `;` or newlines. Slots are overridden
+/// one by one (`stateDiff`); the rest of the account's storage is kept.
+fn parse_overrides(input: &str) -> Result<StateOverride, String> {
+    let mut overrides = StateOverride::default();
+    for entry in input.split([';', '\n']).map(str::trim) {
+        if entry.is_empty() {
+            continue;
+        }
+        let (target, value) = entry
+            .split_once('=')
+            .ok_or_else(|| format!("\"{entry}\": expected <address>.<field>=<value>"))?;
+        let (address, field) = target
+            .trim()
+            .split_once('.')
+            .ok_or_else(|| format!("\"{entry}\": expected <address>.<field>=<value>"))?;
+        let address: Address = address
+            .parse()
+            .map_err(|_| format!("\"{entry}\": invalid address"))?;
+        let value = value.trim();
+        let account = overrides.entry(address).or_default();
+
+        match field.trim() {
+            "balance" => {
+                account.balance =
+                    Some(parse_amount(value).map_err(|e| format!("\"{entry}\": {e}"))?)
+            }
+            "nonce" => {
+                account.nonce = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("\"{entry}\": invalid nonce"))?,
+                )
+            }
+            "code" => {
+                account.code = Some(
+                    value
+                        .parse::<Bytes>()
+                        .map_err(|_| format!("\"{entry}\": invalid hex code"))?,
+                )
+            }
+            field => {
+                let key = field
+                    .strip_prefix("slot[")
+                    .and_then(|rest| rest.strip_suffix(']'))
+                    .ok_or_else(|| {
+                        format!(
+                            "\"{entry}\": unknown field (use balance, nonce, code or slot[<key>])"
+                        )
+                    })?;
+                let key = parse_word(key).map_err(|e| format!("\"{entry}\": slot {e}"))?;
+                let value = parse_word(value).map_err(|e| format!("\"{entry}\": value {e}"))?;
+                account
+                    .state_diff
+                    .get_or_insert_with(Default::default)
+                    .insert(key, value);
+            }
+        }
+    }
+    Ok(overrides)
+}
+
+/// Wei as a decimal or 0x number, or with an `ether`/`gwei` unit (`1.5ether`)
+fn parse_amount(input: &str) -> Result<U256, String> {
+    for unit in ["ether", "gwei", "wei"] {
+        if let Some(amount) = input.strip_suffix(unit) {
+            return parse_units(amount.trim(), unit)
+                .map(Into::into)
+                .map_err(|_| format!("invalid amount \"{input}\""));
+        }
+    }
+    parse_uint(input).map_err(|_| format!("invalid amount \"{input}\""))
+}
+
+/// A 32-byte storage word: 0x hex (left-padded) or a decimal number
+fn parse_word(input: &str) -> Result<B256, String> {
+    parse_uint(input)
+        .map(B256::from)
+        .map_err(|_| format!("\"{input}\" is not a 32-byte hex word or number"))
+}
+
+fn parse_uint(input: &str) -> Result<U256, ()> {
+    match input.strip_prefix("0x") {
+        Some(hex) => U256::from_str_radix(hex, 16).map_err(|_| ()),
+        None => input.parse().map_err(|_| ()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_block() {
+        assert_eq!(parse_block("").unwrap(), None);
+        assert_eq!(
+            parse_block("Finalized").unwrap(),
+            Some(BlockId::Number(BlockNumberOrTag::Finalized))
+        );
+        assert_eq!(parse_block("1234").unwrap(), Some(BlockId::number(1234)));
+        assert_eq!(parse_block("0x10").unwrap(), Some(BlockId::number(16)));
+        let hash = B256::repeat_byte(0xab);
+        assert_eq!(
+            parse_block(&format!("{hash:?}")).unwrap(),
+            Some(BlockId::hash(hash))
+        );
+        assert!(parse_block("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_parse_overrides() {
+        let token = Address::repeat_byte(0x11);
+        let me = Address::repeat_byte(0x22);
+        let overrides = parse_overrides(&format!(
+            "{me:?}.balance=1.5ether; {me:?}.nonce=7;\n{token:?}.slot[0x2]=100; {token:?}.code=0x6001"
+        ))
+        .unwrap();
+
+        let account = &overrides[&me];
+        assert_eq!(
+            account.balance,
+            Some(U256::from(1_500_000_000_000_000_000u128))
+        );
+        assert_eq!(account.nonce, Some(7));
+        let account = &overrides[&token];
+        assert_eq!(
+            account.code.as_ref().map(|c| c.to_vec()),
+            Some(vec![0x60, 0x01])
+        );
+        let slots = account.state_diff.as_ref().unwrap();
+        assert_eq!(
+            slots[&B256::from(U256::from(2))],
+            B256::from(U256::from(100))
+        );
+        assert!(account.state.is_none());
+
+        assert!(parse_overrides("").unwrap().is_empty());
+        assert!(parse_overrides("0x1234.balance=1").is_err());
+        assert!(parse_overrides(&format!("{me:?}.storage=1")).is_err());
+        assert!(parse_overrides(&format!("{me:?}.balance")).is_err());
+    }
+
+    #[test]
+    fn test_options() {
+        let me = Address::repeat_byte(0x22);
+        let options = CallOptions::parse(&[
+            "19000000",
+            &format!("{me:?}"),
+            &format!("{me:?}.balance=1; {me:?}.slot[0]=1"),
+        ])
+        .unwrap();
+        assert_eq!(
+            options.describe().unwrap(),
+            format!("block 19000000, from {me:?}, 2 state overrides")
+        );
+
+        assert_eq!(CallOptions::parse(&["", "", ""]).unwrap().describe(), None);
+
+        let errors = CallOptions::parse(&["soon", "0x12", ""]).unwrap_err();
+        let fields: Vec<_> = errors.iter().map(|(i, _)| *i).collect();
+        assert_eq!(fields, [0, 1]);
+    }
+}
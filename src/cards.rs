@@ -7,11 +7,15 @@ pub enum Card {
     Transaction {
         hash: TxHash,
         status: TransactionStatus,
+        /// Known once the node returns the sent transaction
+        nonce: Option<u64>,
         function_name: String,
         gas_used: Option<String>,
         contract_name: String,
         contract_address: Option<Address>,
         error_message: Option<String>,
+        /// Compiler target and settings used for a deployment, e.g. "EVM, optimizer 200 runs"
+        compiler: Option<String>,
     },
     Call {
         from: Address,
@@ -27,21 +31,29 @@ pub enum Card {
         account: Address,
         balance: Option<String>,
         chain_id: Option<u64>,
+        /// RPC endpoint requests currently go to
+        endpoint: String,
         error: Option<String>,
     },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TransactionStatus {
+    /// Sent, waiting for the receipt
+    Pending,
     Success,
     Failed,
+    /// Replaced by a zero-value transfer with the same nonce
+    Cancelled,
 }
 
 impl fmt::Display for TransactionStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            TransactionStatus::Pending => write!(f, "Pending"),
             TransactionStatus::Success => write!(f, "Success"),
             TransactionStatus::Failed => write!(f, "Failed"),
+            TransactionStatus::Cancelled => write!(f, "Cancelled"),
         }
     }
 }
@@ -60,6 +72,10 @@ pub enum CardAction {
     DebugTrace,
     DebugCall,
     Copy,
+    /// Re-send a pending transaction with higher fees
+    SpeedUp,
+    /// Replace a pending transaction with a zero-value transfer to yourself
+    Cancel,
 }
 
 impl fmt::Display for CardAction {
@@ -69,6 +85,8 @@ impl fmt::Display for CardAction {
             CardAction::DebugTrace => write!(f, "Debug Trace"),
             CardAction::DebugCall => write!(f, "Debug Call"),
             CardAction::Copy => write!(f, "Copy"),
+            CardAction::SpeedUp => write!(f, "Speed Up"),
+            CardAction::Cancel => write!(f, "Cancel"),
         }
     }
 }
@@ -76,6 +94,10 @@ impl fmt::Display for CardAction {
 /// Get available actions for a card type
 pub fn get_card_actions(card: &Card) -> Vec<CardAction> {
     match card {
+        Card::Transaction {
+            status: TransactionStatus::Pending,
+            ..
+        } => vec![CardAction::Copy, CardAction::SpeedUp, CardAction::Cancel],
         Card::Transaction { .. } => vec![
             CardAction::Copy,
             CardAction::ViewReceipt,
@@ -1,11 +1,17 @@
 use alloy::json_abi::JsonAbi;
 use anyhow::{bail, Context, Result};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::compile_cache::{CacheEntry, CachedContract, CompileCache};
+use crate::diagnostics::{self, Diagnostic, Severity};
 
 /// Target bytecode format for compilation
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum BytecodeTarget {
     #[default]
     Evm,
@@ -39,11 +45,261 @@ impl std::fmt::Display for BytecodeTarget {
     }
 }
 
+/// Compiler options for a contract, persisted per contract in the store.
+/// `None`/`false` means "use forge's default".
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompilerSettings {
+    /// Optimizer runs; `Some` enables the solc optimizer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optimizer_runs: Option<u32>,
+    /// Target EVM version (e.g. "cancun", "paris")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evm_version: Option<String>,
+    /// Compile through the Yul IR pipeline
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub via_ir: bool,
+    /// resolc optimization level: one of 0, 1, 2, 3, s, z
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolc_optimization: Option<char>,
+    /// PVM heap size in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolc_heap_size: Option<u32>,
+    /// PVM stack size in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolc_stack_size: Option<u32>,
+}
+
+/// Valid resolc optimization levels
+pub const RESOLC_OPTIMIZATION_LEVELS: [char; 6] = ['0', '1', '2', '3', 's', 'z'];
+
+/// Known `--evm-version` values accepted by solc
+pub const EVM_VERSIONS: [&str; 13] = [
+    "homestead",
+    "tangerineWhistle",
+    "spuriousDragon",
+    "byzantium",
+    "constantinople",
+    "petersburg",
+    "istanbul",
+    "berlin",
+    "london",
+    "paris",
+    "shanghai",
+    "cancun",
+    "prague",
+];
+
+impl CompilerSettings {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Extra `forge build` arguments for these settings.
+    /// resolc options are only passed when building for PVM.
+    pub fn forge_args(&self, target: BytecodeTarget) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(runs) = self.optimizer_runs {
+            args.push("--optimize".to_string());
+            args.push("--optimizer-runs".to_string());
+            args.push(runs.to_string());
+        }
+        if let Some(evm_version) = &self.evm_version {
+            args.push("--evm-version".to_string());
+            args.push(evm_version.clone());
+        }
+        if self.via_ir {
+            args.push("--via-ir".to_string());
+        }
+        if target == BytecodeTarget::Pvm {
+            if let Some(level) = self.resolc_optimization {
+                args.push("--resolc-optimization".to_string());
+                args.push(level.to_string());
+            }
+            if let Some(heap) = self.resolc_heap_size {
+                args.push("--heap-size".to_string());
+                args.push(heap.to_string());
+            }
+            if let Some(stack) = self.resolc_stack_size {
+                args.push("--stack-size".to_string());
+                args.push(stack.to_string());
+            }
+        }
+        args
+    }
+
+    /// Field values for the settings form: optimizer runs, evm version, via-ir,
+    /// resolc optimization, heap size, stack size (empty means default)
+    pub fn to_fields(&self) -> [String; 6] {
+        let opt = |v: Option<u32>| v.map(|n| n.to_string()).unwrap_or_default();
+        [
+            opt(self.optimizer_runs),
+            self.evm_version.clone().unwrap_or_default(),
+            if self.via_ir {
+                "true".to_string()
+            } else {
+                String::new()
+            },
+            self.resolc_optimization
+                .map(String::from)
+                .unwrap_or_default(),
+            opt(self.resolc_heap_size),
+            opt(self.resolc_stack_size),
+        ]
+    }
+
+    /// Parse the settings form, returning `(field index, message)` for every invalid field
+    pub fn from_fields(values: &[&str]) -> Result<Self, Vec<(usize, String)>> {
+        let mut errors = Vec::new();
+        let field = |i: usize| values.get(i).map(|v| v.trim()).unwrap_or("");
+        let mut number = |i: usize| -> Option<u32> {
+            let value = field(i);
+            if value.is_empty() {
+                return None;
+            }
+            match value.replace('_', "").parse() {
+                Ok(n) => Some(n),
+                Err(_) => {
+                    errors.push((i, format!("Expected a positive integer, got '{value}'")));
+                    None
+                }
+            }
+        };
+
+        let optimizer_runs = number(0);
+        let resolc_heap_size = number(4);
+        let resolc_stack_size = number(5);
+
+        let evm_version = match field(1) {
+            "" => None,
+            v => match EVM_VERSIONS
+                .iter()
+                .find(|known| known.eq_ignore_ascii_case(v))
+            {
+                Some(known) => Some(known.to_string()),
+                None => {
+                    errors.push((
+                        1,
+                        format!(
+                            "Unknown EVM version (expected one of {})",
+                            EVM_VERSIONS.join(", ")
+                        ),
+                    ));
+                    None
+                }
+            },
+        };
+
+        let via_ir = match field(2).to_lowercase().as_str() {
+            "" | "false" | "no" => false,
+            "true" | "yes" => true,
+            _ => {
+                errors.push((2, "Expected true or false".to_string()));
+                false
+            }
+        };
+
+        let resolc_optimization = match field(3) {
+            "" => None,
+            v => {
+                let level = v.trim_start_matches("-O");
+                let mut chars = level.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if RESOLC_OPTIMIZATION_LEVELS.contains(&c) => Some(c),
+                    _ => {
+                        errors.push((3, "Expected one of 0, 1, 2, 3, s, z".to_string()));
+                        None
+                    }
+                }
+            }
+        };
+
+        if !errors.is_empty() {
+            errors.sort_by_key(|(i, _)| *i);
+            return Err(errors);
+        }
+
+        Ok(Self {
+            optimizer_runs,
+            evm_version,
+            via_ir,
+            resolc_optimization,
+            resolc_heap_size,
+            resolc_stack_size,
+        })
+    }
+
+    /// Stable representation of the settings relevant to `target`, for cache keys
+    pub fn cache_key(&self, target: BytecodeTarget) -> String {
+        self.forge_args(target).join(" ")
+    }
+
+    /// Short summary of the settings that apply to `target`, e.g. "optimizer 200 runs, via-ir"
+    pub fn summary(&self, target: BytecodeTarget) -> String {
+        let mut parts = Vec::new();
+        match self.optimizer_runs {
+            Some(runs) => parts.push(format!("optimizer {runs} runs")),
+            None => parts.push("optimizer off".to_string()),
+        }
+        if let Some(evm_version) = &self.evm_version {
+            parts.push(format!("evm {evm_version}"));
+        }
+        if self.via_ir {
+            parts.push("via-ir".to_string());
+        }
+        if target == BytecodeTarget::Pvm {
+            if let Some(level) = self.resolc_optimization {
+                parts.push(format!("resolc -O{level}"));
+            }
+            if let Some(heap) = self.resolc_heap_size {
+                parts.push(format!("heap {heap}"));
+            }
+            if let Some(stack) = self.resolc_stack_size {
+                parts.push(format!("stack {stack}"));
+            }
+        }
+        parts.join(", ")
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CompiledContract {
     pub name: String,
     pub abi: JsonAbi,
     pub bytecode: Vec<u8>,
+    /// Warnings reported by the compiler for this build
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// ABIs of all contracts in a source file, plus any compiler warnings
+#[derive(Debug, Clone)]
+pub struct LoadedAbis {
+    pub contracts: Vec<(String, JsonAbi)>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Output of a successful `forge build`
+struct ForgeBuild {
+    /// Command line, formatted for display
+    command: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Error returned when `forge build` exits with a failure status.
+/// Carries the parsed diagnostics so the UI can show them in full.
+#[derive(Debug, thiserror::Error)]
+#[error("forge build failed: {summary}")]
+pub struct BuildError {
+    summary: String,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Runtime bytecode of a contract, as it should appear on chain
+#[derive(Debug, Clone)]
+pub struct RuntimeCode {
+    pub bytecode: Vec<u8>,
+    /// Byte ranges `(start, length)` written by the constructor (EVM immutables)
+    pub immutable_references: Vec<(usize, usize)>,
 }
 
 /// Forge artifact JSON structure
@@ -51,6 +307,8 @@ pub struct CompiledContract {
 struct ForgeArtifact {
     abi: serde_json::Value,
     bytecode: ForgeBytecode,
+    #[serde(default, rename = "deployedBytecode")]
+    deployed_bytecode: Option<ForgeDeployedBytecode>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -58,12 +316,28 @@ struct ForgeBytecode {
     object: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct ForgeDeployedBytecode {
+    object: String,
+    /// AST id -> every location the immutable is written to
+    #[serde(default, rename = "immutableReferences")]
+    immutable_references: HashMap<String, Vec<ForgeImmutableRef>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ForgeImmutableRef {
+    start: usize,
+    length: usize,
+}
+
 /// PVM bytecode magic bytes (ASCII "PVM\0")
-const PVM_MAGIC: [u8; 4] = [0x50, 0x56, 0x4d, 0x00];
+pub const PVM_MAGIC: [u8; 4] = [0x50, 0x56, 0x4d, 0x00];
 
 /// Load contract ABI from forge build artifacts (EVM target).
 /// This does a quick build to get the ABI without full bytecode validation.
-pub fn load_contract_abi(sol_path: &Path) -> Result<Vec<(String, JsonAbi)>> {
+/// Served from the compile cache when the sources are unchanged.
+pub fn load_contract_abi(sol_path: &Path) -> Result<LoadedAbis> {
+    // ABIs don't depend on optimizer settings, so always use forge's defaults here
     let sol_path = sol_path
         .canonicalize()
         .with_context(|| format!("Failed to resolve path: {}", sol_path.display()))?;
@@ -72,25 +346,145 @@ pub fn load_contract_abi(sol_path: &Path) -> Result<Vec<(String, JsonAbi)>> {
         bail!("Solidity file not found: {}", sol_path.display());
     }
 
-    // Run forge build for EVM to get ABIs
-    let parent_dir = sol_path.parent().unwrap_or(Path::new("."));
-    let output_dir = parent_dir.join(BytecodeTarget::Evm.output_dir());
+    let entry = build_cached(&sol_path, BytecodeTarget::Evm, &CompilerSettings::default())?;
+
+    let contracts: Vec<(String, JsonAbi)> = entry
+        .contracts
+        .into_iter()
+        .map(|c| (c.name, c.abi))
+        .collect();
+
+    if contracts.is_empty() {
+        bail!("No contracts found in {}", sol_path.display());
+    }
+
+    Ok(LoadedAbis {
+        contracts,
+        diagnostics: entry.diagnostics,
+    })
+}
+
+/// Compile contract for a specific bytecode target using forge.
+/// Served from the compile cache when the sources are unchanged.
+pub fn compile_contract(
+    sol_path: &Path,
+    contract_name: &str,
+    target: BytecodeTarget,
+    settings: &CompilerSettings,
+) -> Result<CompiledContract> {
+    let sol_path = sol_path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve path: {}", sol_path.display()))?;
+
+    let entry = build_cached(&sol_path, target, settings)?;
+
+    let Some(artifact) = entry
+        .contracts
+        .into_iter()
+        .find(|c| c.name == contract_name)
+    else {
+        log::error!(
+            "Contract '{contract_name}' not found in artifacts of {} ({target}). The contract name may not match any contract in the file.",
+            sol_path.display()
+        );
+
+        // Simplified error for UI
+        bail!("Contract '{contract_name}' not found in artifacts. The name may not match. Check ~/.evm-cli/output.log for details.");
+    };
+
+    // Decode bytecode
+    let bytecode = hex::decode(&artifact.bytecode)
+        .with_context(|| format!("Failed to decode bytecode for {contract_name}"))?;
+
+    if bytecode.is_empty() {
+        bail!("Empty bytecode for {contract_name}. This may be an interface or abstract contract.");
+    }
+
+    // Validate PVM magic if targeting PVM
+    if target == BytecodeTarget::Pvm && (bytecode.len() < 4 || bytecode[..4] != PVM_MAGIC) {
+        bail!("Invalid PVM bytecode: missing magic bytes. Ensure resolc is installed and working.");
+    }
+
+    Ok(CompiledContract {
+        name: contract_name.to_string(),
+        abi: artifact.abi,
+        bytecode,
+        diagnostics: entry.diagnostics,
+    })
+}
+
+/// Compile a contract and return its runtime bytecode for `target`.
+/// Served from the compile cache when the sources are unchanged.
+pub fn compile_runtime_code(
+    sol_path: &Path,
+    contract_name: &str,
+    target: BytecodeTarget,
+    settings: &CompilerSettings,
+) -> Result<RuntimeCode> {
+    let sol_path = sol_path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve path: {}", sol_path.display()))?;
+
+    let entry = build_cached(&sol_path, target, settings)?;
+    let Some(artifact) = entry
+        .contracts
+        .into_iter()
+        .find(|c| c.name == contract_name)
+    else {
+        bail!(
+            "Contract '{contract_name}' not found in artifacts of {}",
+            sol_path.display()
+        );
+    };
+
+    let bytecode = hex::decode(&artifact.deployed_bytecode)
+        .with_context(|| format!("Failed to decode runtime bytecode for {contract_name}"))?;
+    if bytecode.is_empty() {
+        bail!("No runtime bytecode for {contract_name}. This may be an interface or abstract contract.");
+    }
 
-    let build_cmd = run_forge_build(&sol_path, BytecodeTarget::Evm)?;
+    Ok(RuntimeCode {
+        bytecode,
+        immutable_references: artifact.immutable_references,
+    })
+}
+
+/// Return the build output for a source file, running forge only on a cache miss.
+fn build_cached(
+    sol_path: &Path,
+    target: BytecodeTarget,
+    settings: &CompilerSettings,
+) -> Result<CacheEntry> {
+    let cache = CompileCache::default_location();
+    let toolchain = toolchain_version(target);
+    let key = match CompileCache::key(sol_path, target, &settings.cache_key(target), &toolchain) {
+        Ok(key) => Some(key),
+        Err(e) => {
+            log::warn!("Compile cache disabled for {}: {e}", sol_path.display());
+            None
+        }
+    };
+
+    if let Some(entry) = key.as_deref().and_then(|key| cache.load(key)) {
+        log::info!("Compile cache hit for {} ({target})", sol_path.display());
+        return Ok(entry);
+    }
 
-    // Find and parse artifacts
+    let build = run_forge_build(sol_path, target, settings)?;
+    let parent_dir = sol_path.parent().unwrap_or(Path::new("."));
     let filename = sol_path
         .file_stem()
         .ok_or_else(|| anyhow::anyhow!("Invalid file path"))?
         .to_string_lossy();
-
-    let artifact_dir = output_dir.join(format!("{filename}.sol"));
+    let artifact_dir = parent_dir
+        .join(target.output_dir())
+        .join(format!("{filename}.sol"));
 
     if !artifact_dir.exists() {
         let detailed_error = format!(
             "No artifacts found at {}.\n\nCommand executed:\n  {}\n\nThe build may have succeeded but produced no artifacts. Check if the contract is valid.",
             artifact_dir.display(),
-            build_cmd
+            build.command
         );
         log::error!("{detailed_error}");
 
@@ -98,9 +492,49 @@ pub fn load_contract_abi(sol_path: &Path) -> Result<Vec<(String, JsonAbi)>> {
         bail!("No artifacts found. The build may have succeeded but produced no artifacts. Check ~/.evm-cli/output.log for details.");
     }
 
+    let entry = CacheEntry {
+        contracts: read_artifacts(&artifact_dir)?,
+        diagnostics: build.diagnostics,
+    };
+
+    if let Some(key) = key {
+        if let Err(e) = cache.store(&key, &entry) {
+            log::warn!("Failed to write compile cache entry: {e}");
+        }
+    }
+
+    Ok(entry)
+}
+
+/// Versions of the compilers building for `target`, looked up once per run
+fn toolchain_version(target: BytecodeTarget) -> String {
+    static FORGE: OnceLock<String> = OnceLock::new();
+    static RESOLC: OnceLock<String> = OnceLock::new();
+
+    let forge = FORGE.get_or_init(|| tool_version("forge"));
+    match target {
+        BytecodeTarget::Evm => forge.clone(),
+        BytecodeTarget::Pvm => {
+            format!("{forge}\n{}", RESOLC.get_or_init(|| tool_version("resolc")))
+        }
+    }
+}
+
+/// Output of `<tool> --version`, or a placeholder when it can't be run
+fn tool_version(tool: &str) -> String {
+    match Command::new(tool).arg("--version").output() {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+        _ => format!("{tool} unknown"),
+    }
+}
+
+/// Read every contract artifact forge wrote for one source file
+fn read_artifacts(artifact_dir: &Path) -> Result<Vec<CachedContract>> {
     let mut contracts = Vec::new();
 
-    for entry in std::fs::read_dir(&artifact_dir).with_context(|| {
+    for entry in std::fs::read_dir(artifact_dir).with_context(|| {
         format!(
             "Failed to read artifact directory: {}",
             artifact_dir.display()
@@ -125,82 +559,46 @@ pub fn load_contract_abi(sol_path: &Path) -> Result<Vec<(String, JsonAbi)>> {
             let abi: JsonAbi = serde_json::from_value(artifact.abi)
                 .with_context(|| format!("Failed to parse ABI for {contract_name}"))?;
 
-            contracts.push((contract_name, abi));
+            let bytecode = strip_hex_prefix(&artifact.bytecode.object);
+            let (deployed_bytecode, immutable_references) = match artifact.deployed_bytecode {
+                Some(deployed) => {
+                    let mut refs: Vec<(usize, usize)> = deployed
+                        .immutable_references
+                        .into_values()
+                        .flatten()
+                        .map(|r| (r.start, r.length))
+                        .collect();
+                    refs.sort_unstable();
+                    (strip_hex_prefix(&deployed.object), refs)
+                }
+                None => (String::new(), Vec::new()),
+            };
+
+            contracts.push(CachedContract {
+                name: contract_name,
+                abi,
+                bytecode,
+                deployed_bytecode,
+                immutable_references,
+            });
         }
     }
 
-    if contracts.is_empty() {
-        bail!("No contracts found in {}", sol_path.display());
-    }
-
+    // Stable ordering regardless of directory iteration order
+    contracts.sort_by(|a, b| a.name.cmp(&b.name));
     Ok(contracts)
 }
 
-/// Compile contract for a specific bytecode target using forge.
-pub fn compile_contract(
-    sol_path: &Path,
-    contract_name: &str,
-    target: BytecodeTarget,
-) -> Result<CompiledContract> {
-    let sol_path = sol_path
-        .canonicalize()
-        .with_context(|| format!("Failed to resolve path: {}", sol_path.display()))?;
-
-    // Run forge build
-    let build_cmd = run_forge_build(&sol_path, target)?;
-
-    // Find artifact
-    let artifact_path = get_artifact_path(&sol_path, contract_name, target);
-
-    if !artifact_path.exists() {
-        let detailed_error = format!(
-            "Contract artifact not found: {}.\n\nCommand executed:\n  {}\n\nThe contract name '{}' may not match any contract in the file.",
-            artifact_path.display(),
-            build_cmd,
-            contract_name
-        );
-        log::error!("{detailed_error}");
-
-        // Simplified error for UI
-        bail!("Contract '{contract_name}' not found in artifacts. The name may not match. Check ~/.evm-cli/output.log for details.");
-    }
-
-    let content = std::fs::read_to_string(&artifact_path)
-        .with_context(|| format!("Failed to read artifact: {}", artifact_path.display()))?;
-
-    let artifact: ForgeArtifact = serde_json::from_str(&content)
-        .with_context(|| format!("Failed to parse artifact: {}", artifact_path.display()))?;
-
-    let abi: JsonAbi = serde_json::from_value(artifact.abi)
-        .with_context(|| format!("Failed to parse ABI for {contract_name}"))?;
-
-    // Decode bytecode
-    let bytecode_hex = artifact
-        .bytecode
-        .object
-        .strip_prefix("0x")
-        .unwrap_or(&artifact.bytecode.object);
-    let bytecode = hex::decode(bytecode_hex)
-        .with_context(|| format!("Failed to decode bytecode for {contract_name}"))?;
-
-    if bytecode.is_empty() {
-        bail!("Empty bytecode for {contract_name}. This may be an interface or abstract contract.");
-    }
-
-    // Validate PVM magic if targeting PVM
-    if target == BytecodeTarget::Pvm && (bytecode.len() < 4 || bytecode[..4] != PVM_MAGIC) {
-        bail!("Invalid PVM bytecode: missing magic bytes. Ensure resolc is installed and working.");
-    }
-
-    Ok(CompiledContract {
-        name: contract_name.to_string(),
-        abi,
-        bytecode,
-    })
+fn strip_hex_prefix(object: &str) -> String {
+    object.strip_prefix("0x").unwrap_or(object).to_string()
 }
 
 /// Run forge build for a specific target
-fn run_forge_build(sol_path: &Path, target: BytecodeTarget) -> Result<String> {
+fn run_forge_build(
+    sol_path: &Path,
+    target: BytecodeTarget,
+    settings: &CompilerSettings,
+) -> Result<ForgeBuild> {
     let parent_dir = sol_path.parent().unwrap_or(Path::new("."));
     let output_dir = parent_dir.join(target.output_dir());
 
@@ -215,9 +613,12 @@ fn run_forge_build(sol_path: &Path, target: BytecodeTarget) -> Result<String> {
         cmd.arg("--resolc-compile");
     }
 
+    let extra_args = settings.forge_args(target);
+    cmd.args(&extra_args);
+
     // Format command for display
     let cmd_display = format!(
-        "cd {} && forge build -o {} {}{}",
+        "cd {} && forge build -o {} {}{}{}",
         parent_dir.display(),
         output_dir.display(),
         sol_path.display(),
@@ -225,16 +626,23 @@ fn run_forge_build(sol_path: &Path, target: BytecodeTarget) -> Result<String> {
             " --resolc-compile"
         } else {
             ""
-        }
+        },
+        extra_args
+            .iter()
+            .map(|arg| format!(" {arg}"))
+            .collect::<String>()
     );
 
     let output = cmd
         .output()
         .context("Failed to execute forge. Is it installed?")?;
 
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // Diagnostics may be printed on either stream depending on forge version
+    let diagnostics = diagnostics::parse_forge_output(&format!("{stdout}\n{stderr}"), parent_dir);
+
     if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
         let combined = if stderr.is_empty() { stdout } else { stderr };
 
         // Log detailed error
@@ -243,33 +651,124 @@ fn run_forge_build(sol_path: &Path, target: BytecodeTarget) -> Result<String> {
         log::error!("{detailed_error}");
         log::info!("Command: {cmd_display}");
 
-        // Simplified error for UI - show first line of error
-        let first_error_line = combined
-            .lines()
-            .find(|line| !line.trim().is_empty())
-            .unwrap_or("Unknown error");
-        bail!(
-            "forge build failed: {first_error_line}\n\nCheck ~/.evm-cli/output.log for full details."
-        );
+        // Simplified error for UI - the full list is carried in the error
+        let summary = match diagnostics.iter().find(|d| d.severity == Severity::Error) {
+            Some(first) => format!("{} ({})", first, diagnostics::summary(&diagnostics)),
+            None => {
+                let first_error_line = combined
+                    .lines()
+                    .find(|line| !line.trim().is_empty())
+                    .unwrap_or("Unknown error");
+                format!("{first_error_line}\n\nCheck ~/.evm-cli/output.log for full details.")
+            }
+        };
+        return Err(BuildError {
+            summary,
+            diagnostics,
+        }
+        .into());
     }
 
     // Log successful builds too
     log::info!("Command: {cmd_display}");
+    for diagnostic in &diagnostics {
+        log::warn!("{diagnostic}");
+    }
 
-    Ok(cmd_display)
+    Ok(ForgeBuild {
+        command: cmd_display,
+        diagnostics,
+    })
 }
 
-/// Get the artifact path for a compiled contract
-fn get_artifact_path(
-    sol_path: &Path,
-    contract_name: &str,
-    target: BytecodeTarget,
-) -> std::path::PathBuf {
-    let parent_dir = sol_path.parent().unwrap_or(Path::new("."));
-    let filename = sol_path.file_stem().unwrap_or_default().to_string_lossy();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_settings_add_no_args() {
+        let settings = CompilerSettings::default();
+        assert!(settings.is_default());
+        assert!(settings.forge_args(BytecodeTarget::Evm).is_empty());
+        assert!(settings.forge_args(BytecodeTarget::Pvm).is_empty());
+        assert_eq!(settings.summary(BytecodeTarget::Evm), "optimizer off");
+    }
 
-    parent_dir
-        .join(target.output_dir())
-        .join(format!("{filename}.sol"))
-        .join(format!("{contract_name}.json"))
+    #[test]
+    fn test_resolc_args_only_for_pvm() {
+        let settings = CompilerSettings {
+            optimizer_runs: Some(200),
+            evm_version: Some("cancun".to_string()),
+            via_ir: true,
+            resolc_optimization: Some('z'),
+            resolc_heap_size: Some(65536),
+            resolc_stack_size: None,
+        };
+
+        assert_eq!(
+            settings.forge_args(BytecodeTarget::Evm),
+            vec![
+                "--optimize",
+                "--optimizer-runs",
+                "200",
+                "--evm-version",
+                "cancun",
+                "--via-ir"
+            ]
+        );
+        let pvm_args = settings.forge_args(BytecodeTarget::Pvm);
+        assert!(pvm_args.ends_with(&[
+            "--resolc-optimization".to_string(),
+            "z".to_string(),
+            "--heap-size".to_string(),
+            "65536".to_string(),
+        ]));
+        assert_ne!(
+            settings.cache_key(BytecodeTarget::Evm),
+            settings.cache_key(BytecodeTarget::Pvm)
+        );
+        assert_eq!(
+            settings.summary(BytecodeTarget::Evm),
+            "optimizer 200 runs, evm cancun, via-ir"
+        );
+    }
+
+    #[test]
+    fn test_fields_roundtrip_and_validation() {
+        let parsed =
+            CompilerSettings::from_fields(&["1_000", "Cancun", "true", "-Oz", "", "32768"])
+                .unwrap();
+        assert_eq!(parsed.optimizer_runs, Some(1000));
+        assert_eq!(parsed.evm_version.as_deref(), Some("cancun"));
+        assert!(parsed.via_ir);
+        assert_eq!(parsed.resolc_optimization, Some('z'));
+        assert_eq!(parsed.resolc_heap_size, None);
+        assert_eq!(parsed.resolc_stack_size, Some(32768));
+
+        let fields = parsed.to_fields();
+        let refs: Vec<&str> = fields.iter().map(String::as_str).collect();
+        assert_eq!(CompilerSettings::from_fields(&refs).unwrap(), parsed);
+
+        assert!(CompilerSettings::from_fields(&["", "", "", "", "", ""])
+            .unwrap()
+            .is_default());
+
+        let errors =
+            CompilerSettings::from_fields(&["-1", "frontier", "maybe", "4", "x", ""]).unwrap_err();
+        let indices: Vec<usize> = errors.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indices, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_settings_serialization_omits_defaults() {
+        let settings = CompilerSettings {
+            optimizer_runs: Some(1000),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&settings).unwrap();
+        assert_eq!(json, r#"{"optimizer_runs":1000}"#);
+
+        let parsed: CompilerSettings = serde_json::from_str("{}").unwrap();
+        assert!(parsed.is_default());
+    }
 }
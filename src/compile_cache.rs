@@ -0,0 +1,481 @@
+use alloy::json_abi::JsonAbi;
+use alloy::primitives::keccak256;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::compile::BytecodeTarget;
+use crate::diagnostics::Diagnostic;
+
+/// Bump when the entry format or the key derivation changes
+const CACHE_VERSION: &str = "evm-cli-compile-cache-v3";
+
+/// Project files that change how forge builds, hashed into every key
+const CONFIG_FILES: [&str; 2] = ["foundry.toml", "remappings.txt"];
+
+/// A cached `forge build` result for one source file and target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub contracts: Vec<CachedContract>,
+    /// Warnings reported when the entry was built, replayed on cache hits
+    #[serde(default)]
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedContract {
+    pub name: String,
+    pub abi: JsonAbi,
+    /// Hex-encoded creation bytecode (empty for interfaces and abstract contracts)
+    pub bytecode: String,
+    /// Hex-encoded runtime bytecode, as stored on chain after deployment
+    #[serde(default)]
+    pub deployed_bytecode: String,
+    /// Byte ranges `(start, length)` of the runtime code filled in by the constructor
+    #[serde(default)]
+    pub immutable_references: Vec<(usize, usize)>,
+}
+
+/// Content-addressed store of compiled artifacts under ~/.evm-cli/cache.
+///
+/// Entries are keyed by the hash of the source file, every import it
+/// (transitively) pulls in, the project's foundry.toml and remappings, the
+/// compiler versions, the bytecode target and the compiler settings, so
+/// changing any of those produces a new key and forces a rebuild.
+pub struct CompileCache {
+    dir: PathBuf,
+}
+
+impl CompileCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Cache in ~/.evm-cli/cache
+    pub fn default_location() -> Self {
+        let dir = dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".evm-cli")
+            .join("cache");
+        Self::new(dir)
+    }
+
+    /// Compute the cache key for a source file built for `target` with the
+    /// given compiler settings and toolchain (any stable textual representation,
+    /// e.g. the output of `forge --version`).
+    pub fn key(
+        sol_path: &Path,
+        target: BytecodeTarget,
+        settings: &str,
+        toolchain: &str,
+    ) -> Result<String> {
+        let mut input = Vec::new();
+        for part in [CACHE_VERSION, &target.to_string(), settings, toolchain] {
+            input.extend_from_slice(part.as_bytes());
+            input.push(0);
+        }
+
+        let file_dir = sol_path.parent().unwrap_or(Path::new("."));
+        let root_dir = project_root(file_dir);
+        for config in CONFIG_FILES {
+            // A missing file hashes differently from an empty one
+            match fs::read(root_dir.join(config)) {
+                Ok(content) => input.extend_from_slice(&content),
+                Err(_) => input.push(1),
+            }
+            input.push(0);
+        }
+
+        let resolver = ImportResolver::new(&root_dir);
+        let mut visited = HashSet::new();
+        hash_sources(sol_path, &resolver, &mut visited, &mut input)?;
+
+        Ok(hex::encode(keccak256(&input)))
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+
+    /// Look up an entry. Unreadable or corrupt entries are treated as misses.
+    pub fn load(&self, key: &str) -> Option<CacheEntry> {
+        let content = fs::read_to_string(self.entry_path(key)).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                log::warn!("Ignoring corrupt compile cache entry {key}: {e}");
+                None
+            }
+        }
+    }
+
+    pub fn store(&self, key: &str, entry: &CacheEntry) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create directory {}", self.dir.display()))?;
+
+        let path = self.entry_path(key);
+        let content = serde_json::to_string(entry).context("Failed to serialize cache entry")?;
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Remove every cached entry, returning how many were deleted
+    pub fn clear(&self) -> Result<usize> {
+        if !self.dir.exists() {
+            return Ok(0);
+        }
+
+        let mut removed = 0;
+        for entry in fs::read_dir(&self.dir)
+            .with_context(|| format!("Failed to read {}", self.dir.display()))?
+        {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// Append `path` and all files it imports to `out`, depth first, each file once.
+fn hash_sources(
+    path: &Path,
+    resolver: &ImportResolver,
+    visited: &mut HashSet<PathBuf>,
+    out: &mut Vec<u8>,
+) -> Result<()> {
+    let canonical = path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve path: {}", path.display()))?;
+    if !visited.insert(canonical.clone()) {
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(&canonical)
+        .with_context(|| format!("Failed to read {}", canonical.display()))?;
+    out.extend_from_slice(content.as_bytes());
+    out.push(0);
+
+    let file_dir = canonical.parent().unwrap_or(Path::new("."));
+    for import in parse_imports(&content) {
+        match resolver.resolve(&import, file_dir) {
+            Some(resolved) => hash_sources(&resolved, resolver, visited, out)?,
+            // Imports we can't locate only contribute their name; forge will
+            // fail on them anyway unless they come from somewhere we don't model
+            None => {
+                log::debug!("Compile cache could not resolve import {import}");
+                out.extend_from_slice(import.as_bytes());
+                out.push(0);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Extract the paths of all `import` directives in a Solidity source
+fn parse_imports(source: &str) -> Vec<String> {
+    strip_comments(source)
+        .split(';')
+        .map(str::trim)
+        .filter(|stmt| is_import_directive(stmt))
+        .filter_map(first_string_literal)
+        .collect()
+}
+
+fn is_import_directive(stmt: &str) -> bool {
+    stmt.strip_prefix("import")
+        .is_some_and(|rest| !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+}
+
+fn first_string_literal(stmt: &str) -> Option<String> {
+    let start = stmt.find(['"', '\''])?;
+    let quote = stmt[start..].chars().next()?;
+    let rest = &stmt[start + 1..];
+    let end = rest.find(quote)?;
+    Some(rest[..end].to_string())
+}
+
+fn strip_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Directory forge treats as the project root for a source in `dir`: the
+/// nearest ancestor with a foundry.toml, or `dir` itself when there is none.
+fn project_root(dir: &Path) -> PathBuf {
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    dir.ancestors()
+        .find(|ancestor| ancestor.join("foundry.toml").is_file())
+        .unwrap_or(dir.as_path())
+        .to_path_buf()
+}
+
+/// Resolves imports the way forge does for the common cases: relative to the
+/// importing file, through the project's remappings, or relative to the
+/// project root / its `lib` and `node_modules`.
+struct ImportResolver {
+    root_dir: PathBuf,
+    /// `(prefix, target)` pairs, longest prefix first
+    remappings: Vec<(String, String)>,
+}
+
+impl ImportResolver {
+    fn new(root_dir: &Path) -> Self {
+        let mut remappings = Vec::new();
+        if let Ok(content) = fs::read_to_string(root_dir.join("remappings.txt")) {
+            remappings.extend(content.lines().filter_map(parse_remapping));
+        }
+        if let Ok(content) = fs::read_to_string(root_dir.join("foundry.toml")) {
+            remappings.extend(foundry_toml_remappings(&content));
+        }
+        // forge's auto-detected remapping for `lib/<name>/src`
+        if let Ok(entries) = fs::read_dir(root_dir.join("lib")) {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if entry.path().join("src").is_dir() {
+                    remappings.push((format!("{name}/"), format!("lib/{name}/src/")));
+                }
+            }
+        }
+        remappings.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+
+        Self {
+            root_dir: root_dir.to_path_buf(),
+            remappings,
+        }
+    }
+
+    fn resolve(&self, import: &str, file_dir: &Path) -> Option<PathBuf> {
+        let mut candidates = Vec::new();
+        if import.starts_with("./") || import.starts_with("../") {
+            candidates.push(file_dir.join(import));
+        } else {
+            if let Some((prefix, target)) = self
+                .remappings
+                .iter()
+                .find(|(prefix, _)| import.starts_with(prefix.as_str()))
+            {
+                let remapped = format!("{target}{}", &import[prefix.len()..]);
+                candidates.push(self.root_dir.join(remapped));
+            }
+            candidates.push(self.root_dir.join(import));
+            candidates.push(self.root_dir.join("lib").join(import));
+            candidates.push(self.root_dir.join("node_modules").join(import));
+        }
+        candidates.into_iter().find(|p| p.is_file())
+    }
+}
+
+/// Parse one `prefix=target` remapping, ignoring an optional `context:` part
+fn parse_remapping(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let (prefix, target) = line.split_once('=')?;
+    let prefix = prefix.rsplit_once(':').map_or(prefix, |(_, p)| p);
+    Some((prefix.trim().to_string(), target.trim().to_string()))
+}
+
+/// The `remappings = [...]` entries of a foundry.toml
+fn foundry_toml_remappings(content: &str) -> Vec<(String, String)> {
+    let Some(start) = content.find("remappings") else {
+        return Vec::new();
+    };
+    let rest = &content[start..];
+    let Some(open) = rest.find('[') else {
+        return Vec::new();
+    };
+    let Some(close) = rest[open..].find(']') else {
+        return Vec::new();
+    };
+    rest[open + 1..open + close]
+        .split(',')
+        .map(|entry| entry.trim().trim_matches(['"', '\'']))
+        .filter_map(parse_remapping)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_parse_imports() {
+        let source = r#"
+            // import "./Commented.sol";
+            import "./A.sol";
+            import {B, C} from '../lib/B.sol';
+            /* import "./Block.sol"; */
+            import * as D from "@oz/D.sol";
+            contract importer {}
+        "#;
+        assert_eq!(
+            parse_imports(source),
+            vec!["./A.sol", "../lib/B.sol", "@oz/D.sol"]
+        );
+    }
+
+    #[test]
+    fn test_key_changes_with_imported_source() {
+        let dir = TempDir::new().unwrap();
+        let main = write(
+            dir.path(),
+            "Main.sol",
+            "import \"./Dep.sol\";\ncontract Main {}",
+        );
+        write(dir.path(), "Dep.sol", "contract Dep {}");
+
+        let key1 = CompileCache::key(&main, BytecodeTarget::Evm, "", "").unwrap();
+        assert_eq!(
+            key1,
+            CompileCache::key(&main, BytecodeTarget::Evm, "", "").unwrap()
+        );
+
+        write(dir.path(), "Dep.sol", "contract Dep { uint x; }");
+        let key2 = CompileCache::key(&main, BytecodeTarget::Evm, "", "").unwrap();
+        assert_ne!(key1, key2);
+    }
+
+    #[test]
+    fn test_key_depends_on_target_and_settings() {
+        let dir = TempDir::new().unwrap();
+        let main = write(dir.path(), "Main.sol", "contract Main {}");
+
+        let evm = CompileCache::key(&main, BytecodeTarget::Evm, "", "").unwrap();
+        let pvm = CompileCache::key(&main, BytecodeTarget::Pvm, "", "").unwrap();
+        let optimized = CompileCache::key(&main, BytecodeTarget::Evm, "optimizer=200", "").unwrap();
+        assert_ne!(evm, pvm);
+        assert_ne!(evm, optimized);
+    }
+
+    #[test]
+    fn test_key_depends_on_toolchain_and_config() {
+        let dir = TempDir::new().unwrap();
+        let main = write(dir.path(), "src/Main.sol", "contract Main {}");
+
+        let key = |toolchain| CompileCache::key(&main, BytecodeTarget::Evm, "", toolchain).unwrap();
+        let before = key("forge 1.0.0");
+        assert_ne!(before, key("forge 1.1.0"));
+
+        // foundry.toml is looked up from the source upwards
+        write(dir.path(), "foundry.toml", "[profile.default]\n");
+        let with_config = key("forge 1.0.0");
+        assert_ne!(before, with_config);
+        write(
+            dir.path(),
+            "foundry.toml",
+            "[profile.default]\nsolc = \"0.8.24\"\n",
+        );
+        assert_ne!(with_config, key("forge 1.0.0"));
+    }
+
+    #[test]
+    fn test_key_follows_remapped_imports() {
+        let dir = TempDir::new().unwrap();
+        write(
+            dir.path(),
+            "foundry.toml",
+            "remappings = [\"@oz/=lib/oz/contracts/\"]\n",
+        );
+        write(dir.path(), "remappings.txt", "solmate/=lib/solmate/src/\n");
+        let main = write(
+            dir.path(),
+            "src/Main.sol",
+            "import \"@oz/Token.sol\";\nimport \"solmate/Auth.sol\";\nimport \"forge-std/Test.sol\";",
+        );
+        write(
+            dir.path(),
+            "lib/oz/contracts/Token.sol",
+            "contract Token {}",
+        );
+        write(dir.path(), "lib/solmate/src/Auth.sol", "contract Auth {}");
+        write(dir.path(), "lib/forge-std/src/Test.sol", "contract Test {}");
+
+        let key = || CompileCache::key(&main, BytecodeTarget::Evm, "", "").unwrap();
+        let mut previous = key();
+        for (dependency, content) in [
+            ("lib/oz/contracts/Token.sol", "contract Token { uint x; }"),
+            ("lib/solmate/src/Auth.sol", "contract Auth { uint x; }"),
+            ("lib/forge-std/src/Test.sol", "contract Test { uint x; }"),
+        ] {
+            write(dir.path(), dependency, content);
+            let changed = key();
+            assert_ne!(previous, changed, "{dependency} is not part of the key");
+            previous = changed;
+        }
+    }
+
+    #[test]
+    fn test_import_cycles_terminate() {
+        let dir = TempDir::new().unwrap();
+        let a = write(dir.path(), "A.sol", "import \"./B.sol\";");
+        write(dir.path(), "B.sol", "import \"./A.sol\";");
+        assert!(CompileCache::key(&a, BytecodeTarget::Evm, "", "").is_ok());
+    }
+
+    #[test]
+    fn test_store_load_and_clear() {
+        let dir = TempDir::new().unwrap();
+        let cache = CompileCache::new(dir.path().join("cache"));
+        let entry = CacheEntry {
+            contracts: vec![CachedContract {
+                name: "Main".to_string(),
+                abi: JsonAbi::new(),
+                bytecode: "6080".to_string(),
+                deployed_bytecode: "6080".to_string(),
+                immutable_references: Vec::new(),
+            }],
+            diagnostics: Vec::new(),
+        };
+
+        assert!(cache.load("abc").is_none());
+        cache.store("abc", &entry).unwrap();
+        let loaded = cache.load("abc").unwrap();
+        assert_eq!(loaded.contracts[0].name, "Main");
+        assert_eq!(loaded.contracts[0].bytecode, "6080");
+
+        assert_eq!(cache.clear().unwrap(), 1);
+        assert!(cache.load("abc").is_none());
+    }
+}
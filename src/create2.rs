@@ -0,0 +1,187 @@
+use alloy::primitives::{address, hex, Address, Bytes, B256, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use anyhow::{bail, Context, Result};
+
+/// Arachnid's deterministic deployment proxy, present at the same address on most chains
+/// (<https://github.com/Arachnid/deterministic-deployment-proxy>)
+pub const FACTORY: Address = address!("4e59b44847b379578588920cA78FbF26c0B4956C");
+
+/// Keyless account that sends `FACTORY_DEPLOY_TX`
+const FACTORY_DEPLOYER: Address = address!("3fab184622dc19b6109349b94811493bf2a45362");
+
+/// Gas price (100 gwei) times gas limit (100,000) of `FACTORY_DEPLOY_TX`
+const FACTORY_DEPLOY_COST: u128 = 10_000_000_000_000_000;
+
+/// Pre-EIP-155 transaction deploying the factory, signed with a made-up signature
+const FACTORY_DEPLOY_TX: [u8; 167] = hex!(
+    "f8a58085174876e800830186a08080b853604580600e600039806000f350fe7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe03601600081602082378035828234f58015156039578182fd5b8082525050506014600cf31ba02222222222222222222222222222222222222222222222222222222222222222a02222222222222222222222222222222222222222222222222222222222222222"
+);
+
+/// Runtime code of the factory, for dev nodes that can set code directly
+const FACTORY_RUNTIME: [u8; 69] = hex!(
+    "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe03601600081602082378035828234f58015156039578182fd5b8082525050506014600cf3"
+);
+
+/// How the factory became available
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FactorySetup {
+    AlreadyDeployed,
+    /// Installed with `anvil_setCode` / `hardhat_setCode`
+    CodeSet,
+    /// Installed by funding the keyless deployer and replaying its transaction
+    Deployed,
+}
+
+/// Parse a salt: 0x-prefixed hex of up to 32 bytes (left-padded) or a decimal number
+pub fn parse_salt(input: &str) -> Result<B256, String> {
+    let input = input.trim();
+    if let Some(hex_str) = input.strip_prefix("0x") {
+        let bytes = hex::decode(hex_str).map_err(|e| format!("Invalid hex salt: {e}"))?;
+        if bytes.len() > 32 {
+            return Err("Salt is longer than 32 bytes".to_string());
+        }
+        let mut salt = [0u8; 32];
+        salt[32 - bytes.len()..].copy_from_slice(&bytes);
+        return Ok(B256::from(salt));
+    }
+    input
+        .parse::<U256>()
+        .map(B256::from)
+        .map_err(|_| "Expected 0x-prefixed hex or a decimal number".to_string())
+}
+
+/// Calldata for the factory: the salt followed by the init code
+pub fn factory_calldata(salt: B256, init_code: &[u8]) -> Bytes {
+    let mut data = salt.to_vec();
+    data.extend_from_slice(init_code);
+    data.into()
+}
+
+/// Address a CREATE2 deployment through the factory will end up at
+pub fn predict_address(salt: B256, init_code: &[u8]) -> Address {
+    FACTORY.create2_from_code(salt, init_code)
+}
+
+/// Whether the RPC endpoint looks like a local dev node we may install the factory on
+pub fn is_local_node(rpc_url: &str, chain_id: Option<u64>) -> bool {
+    let local_host = url::Url::parse(rpc_url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .is_some_and(|host| {
+            matches!(
+                host.as_str(),
+                "localhost" | "127.0.0.1" | "[::1]" | "0.0.0.0"
+            )
+        });
+    local_host || matches!(chain_id, Some(31337 | 1337))
+}
+
+/// Make sure the factory exists. On local nodes (`allow_install`) a missing factory is
+/// installed, first through the dev node's set-code RPC, then by funding the keyless
+/// deployer from `funder` and broadcasting its presigned transaction.
+pub async fn ensure_factory<P: Provider>(
+    provider: &P,
+    funder: Address,
+    allow_install: bool,
+) -> Result<FactorySetup> {
+    if !provider.get_code_at(FACTORY).await?.is_empty() {
+        return Ok(FactorySetup::AlreadyDeployed);
+    }
+    if !allow_install {
+        bail!("CREATE2 factory {FACTORY} is not deployed on this chain");
+    }
+
+    let code = Bytes::from(FACTORY_RUNTIME.to_vec());
+    for method in ["anvil_setCode", "hardhat_setCode"] {
+        let result = provider
+            .raw_request::<_, serde_json::Value>(method.into(), (FACTORY, code.clone()))
+            .await;
+        match result {
+            Ok(_) if !provider.get_code_at(FACTORY).await?.is_empty() => {
+                return Ok(FactorySetup::CodeSet)
+            }
+            Ok(_) => {}
+            Err(e) => log::info!("[CREATE2] {method} unavailable: {e}"),
+        }
+    }
+
+    let balance = provider.get_balance(FACTORY_DEPLOYER).await?;
+    let cost = U256::from(FACTORY_DEPLOY_COST);
+    if balance < cost {
+        let fund = TransactionRequest::default()
+            .from(funder)
+            .to(FACTORY_DEPLOYER)
+            .value(cost - balance);
+        provider
+            .send_transaction(fund)
+            .await
+            .context("Failed to fund the factory deployer")?
+            .get_receipt()
+            .await
+            .context("Failed to fund the factory deployer")?;
+    }
+
+    provider
+        .send_raw_transaction(&FACTORY_DEPLOY_TX)
+        .await
+        .context(
+            "Failed to deploy the CREATE2 factory (the node may reject pre-EIP-155 transactions)",
+        )?
+        .get_receipt()
+        .await
+        .context("Failed to deploy the CREATE2 factory")?;
+
+    if provider.get_code_at(FACTORY).await?.is_empty() {
+        bail!("CREATE2 factory deployment did not produce code at {FACTORY}");
+    }
+    Ok(FactorySetup::Deployed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::consensus::{SignableTransaction, Transaction, TxEnvelope};
+    use alloy::eips::Decodable2718;
+    use alloy::primitives::keccak256;
+
+    #[test]
+    fn test_parse_salt() {
+        assert_eq!(parse_salt("0").unwrap(), B256::ZERO);
+        assert_eq!(parse_salt("0x01").unwrap(), B256::with_last_byte(1));
+        assert_eq!(parse_salt("256").unwrap(), B256::from(U256::from(256)));
+        assert!(parse_salt(&format!("0x{}", "ab".repeat(33))).is_err());
+        assert!(parse_salt("hello").is_err());
+    }
+
+    #[test]
+    fn test_predict_address() {
+        // Same derivation as `forge create --salt` through the default factory
+        let salt = B256::ZERO;
+        let init_code = [0x00u8];
+        let mut preimage = vec![0xff];
+        preimage.extend_from_slice(FACTORY.as_slice());
+        preimage.extend_from_slice(salt.as_slice());
+        preimage.extend_from_slice(keccak256(init_code).as_slice());
+        let expected = Address::from_slice(&keccak256(&preimage)[12..]);
+        assert_eq!(predict_address(salt, &init_code), expected);
+
+        let calldata = factory_calldata(B256::with_last_byte(7), &[0xaa, 0xbb]);
+        assert_eq!(calldata.len(), 34);
+        assert_eq!(&calldata[31..], &[7, 0xaa, 0xbb]);
+    }
+
+    #[test]
+    fn test_factory_deploy_tx_is_consistent() {
+        let envelope = TxEnvelope::decode_2718(&mut FACTORY_DEPLOY_TX.as_slice()).unwrap();
+        let tx = envelope.as_legacy().expect("legacy transaction");
+        let signer = tx
+            .signature()
+            .recover_address_from_prehash(&tx.tx().signature_hash())
+            .unwrap();
+        assert_eq!(signer, FACTORY_DEPLOYER);
+        assert_eq!(FACTORY_DEPLOYER.create(0), FACTORY);
+        // The init code returns the runtime code that follows it
+        assert!(tx.tx().input().ends_with(&FACTORY_RUNTIME));
+    }
+}
@@ -0,0 +1,309 @@
+use alloy::eips::BlockId;
+use alloy::json_abi::{Function, JsonAbi};
+use alloy::primitives::utils::format_ether;
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use anyhow::Result;
+use chrono::{DateTime, Local};
+
+use crate::method_list;
+use crate::multicall;
+use crate::prompts;
+
+/// Label of the native balance row, above the getters
+pub const BALANCE: &str = "balance";
+
+/// One value of the dashboard: formatted return value, or why it could not be read
+pub type Value = std::result::Result<String, String>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub label: String,
+    pub value: Value,
+    /// What it was before the last refresh, when that changed it
+    pub previous: Option<Value>,
+}
+
+impl Row {
+    pub fn changed(&self) -> bool {
+        self.previous.is_some()
+    }
+}
+
+/// Values read in one refresh, all at the same block
+#[derive(Debug, Clone)]
+pub struct Reading {
+    pub block: u64,
+    pub values: Vec<(String, Value)>,
+}
+
+/// Result of a refresh running in the background
+#[derive(Debug)]
+pub struct Update {
+    pub address: Address,
+    pub reading: Result<Reading>,
+}
+
+/// Live view of an instance: its native balance and every getter
+#[derive(Debug, Clone)]
+pub struct Dashboard {
+    pub address: Address,
+    pub name: String,
+    pub getters: Vec<Function>,
+    pub rows: Vec<Row>,
+    /// Block of the values shown
+    pub block: Option<u64>,
+    pub refreshed_at: Option<DateTime<Local>>,
+    /// A refresh is running
+    pub refreshing: bool,
+    /// Another refresh was asked for while one was running
+    pub stale: bool,
+    /// Why the last refresh failed as a whole
+    pub error: Option<String>,
+    pub scroll: usize,
+}
+
+impl Dashboard {
+    pub fn new(address: Address, name: String, abi: &JsonAbi) -> Self {
+        Self {
+            address,
+            name,
+            getters: method_list::getters(abi).into_iter().cloned().collect(),
+            rows: Vec::new(),
+            block: None,
+            refreshed_at: None,
+            refreshing: false,
+            stale: false,
+            error: None,
+            scroll: 0,
+        }
+    }
+
+    /// Show a refresh's values, marking those that differ from the ones shown before
+    pub fn apply(&mut self, reading: Reading) {
+        let rows = reading
+            .values
+            .into_iter()
+            .map(|(label, value)| {
+                let previous = self
+                    .rows
+                    .iter()
+                    .find(|row| row.label == label)
+                    .map(|row| row.value.clone())
+                    .filter(|previous| *previous != value);
+                Row {
+                    label,
+                    value,
+                    previous,
+                }
+            })
+            .collect();
+        self.rows = rows;
+        self.block = Some(reading.block);
+        self.refreshed_at = Some(Local::now());
+        self.error = None;
+    }
+}
+
+/// Read the balance of `address` and its getters at the latest block, in one Multicall3
+/// call when `batched`, one call each otherwise
+pub async fn read<P: Provider>(
+    provider: &P,
+    address: Address,
+    getters: &[Function],
+    batched: bool,
+) -> Result<Reading> {
+    let block = provider.get_block_number().await?;
+    let at = BlockId::number(block);
+
+    let mut values = Vec::with_capacity(getters.len() + 1);
+    if batched {
+        let mut calls = vec![multicall::balance_call(address)];
+        calls.extend(
+            getters
+                .iter()
+                .map(|getter| (address, Bytes::from(getter.selector().to_vec()))),
+        );
+        let data = provider
+            .call(
+                TransactionRequest::default()
+                    .to(multicall::ADDRESS)
+                    .input(multicall::encode(&calls).into()),
+            )
+            .block(at)
+            .await?;
+        let mut outcomes = multicall::decode(&data, calls.len())?.into_iter();
+        let balance = match outcomes.next() {
+            Some(Ok(data)) => multicall::decode_balance(&data).map_err(|e| e.to_string()),
+            _ => Err("Balance unavailable".to_string()),
+        };
+        values.push((BALANCE.to_string(), balance.map(format_balance)));
+        for (getter, outcome) in getters.iter().zip(outcomes) {
+            let value = match outcome {
+                Ok(data) => decode(getter, &data),
+                Err(revert) => Err(multicall::revert_message(&revert)),
+            };
+            values.push((getter.name.clone(), value));
+        }
+    } else {
+        let balance = provider.get_balance(address).block_id(at).await;
+        values.push((
+            BALANCE.to_string(),
+            balance.map(format_balance).map_err(|e| e.to_string()),
+        ));
+        for getter in getters {
+            let tx = TransactionRequest::default()
+                .to(address)
+                .input(Bytes::from(getter.selector().to_vec()).into());
+            let value = match provider.call(tx).block(at).await {
+                Ok(data) => decode(getter, &data),
+                Err(e) => Err(e.to_string()),
+            };
+            values.push((getter.name.clone(), value));
+        }
+    }
+    Ok(Reading { block, values })
+}
+
+fn decode(getter: &Function, data: &[u8]) -> Value {
+    use alloy::dyn_abi::FunctionExt;
+    getter
+        .abi_decode_output(data)
+        .map(|values| prompts::format_return_values(&values))
+        .map_err(|e| format!("Failed to decode: {e}"))
+}
+
+fn format_balance(balance: U256) -> String {
+    format!("{} ETH", format_ether(balance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::mock_provider;
+    use alloy::sol_types::SolValue;
+
+    fn reading(block: u64, values: &[(&str, Value)]) -> Reading {
+        Reading {
+            block,
+            values: values
+                .iter()
+                .map(|(label, value)| (label.to_string(), value.clone()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_apply_marks_changes() {
+        let abi = JsonAbi::parse([
+            "function owner() view returns (address)",
+            "function totalSupply() view returns (uint256)",
+            "function mint(uint256)",
+        ])
+        .unwrap();
+        let mut dashboard = Dashboard::new(Address::ZERO, "Token".to_string(), &abi);
+        assert_eq!(dashboard.getters.len(), 2);
+
+        dashboard.apply(reading(
+            1,
+            &[
+                ("totalSupply", Ok("100".into())),
+                ("paused", Err("reverted".into())),
+            ],
+        ));
+        assert!(dashboard.rows.iter().all(|row| !row.changed()));
+
+        dashboard.apply(reading(
+            2,
+            &[
+                ("totalSupply", Ok("150".into())),
+                ("paused", Err("reverted".into())),
+            ],
+        ));
+        assert_eq!(dashboard.block, Some(2));
+        assert_eq!(dashboard.rows[0].previous, Some(Ok("100".into())));
+        assert!(!dashboard.rows[1].changed());
+
+        // Highlights only last until the next refresh
+        dashboard.apply(reading(3, &[("totalSupply", Ok("150".into()))]));
+        assert!(!dashboard.rows[0].changed());
+    }
+
+    #[tokio::test]
+    async fn test_read_one_by_one() {
+        let abi = JsonAbi::parse([
+            "function paused() view returns (bool)",
+            "function totalSupply() view returns (uint256)",
+        ])
+        .unwrap();
+        let getters: Vec<Function> = method_list::getters(&abi).into_iter().cloned().collect();
+
+        let (provider, asserter) = mock_provider();
+        asserter.push_success(&"0x7");
+        asserter.push_success(&"0xde0b6b3a7640000");
+        asserter.push_failure_msg("execution reverted");
+        let supply = format!("0x{}", hex::encode(U256::from(42).abi_encode()));
+        asserter.push_success(&supply);
+
+        let reading = read(&provider, Address::ZERO, &getters, false)
+            .await
+            .unwrap();
+        assert_eq!(reading.block, 7);
+        assert_eq!(
+            reading.values[0],
+            (BALANCE.to_string(), Ok("1.000000000000000000 ETH".into()))
+        );
+        assert_eq!(reading.values[1].0, "paused");
+        assert!(reading.values[1].1.is_err());
+        assert_eq!(
+            reading.values[2],
+            ("totalSupply".to_string(), Ok("42".into()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_batched() {
+        use alloy::providers::bindings::IMulticall3;
+        use alloy::sol_types::{Revert, SolCall, SolError};
+
+        let abi = JsonAbi::parse([
+            "function name() view returns (string)",
+            "function paused() view returns (bool)",
+        ])
+        .unwrap();
+        let getters: Vec<Function> = method_list::getters(&abi).into_iter().cloned().collect();
+
+        let results = vec![
+            IMulticall3::Result {
+                success: true,
+                returnData: U256::from(5).abi_encode().into(),
+            },
+            IMulticall3::Result {
+                success: true,
+                returnData: "Token".to_string().abi_encode().into(),
+            },
+            IMulticall3::Result {
+                success: false,
+                returnData: Revert::from("no").abi_encode().into(),
+            },
+        ];
+        let returned = IMulticall3::aggregate3Call::abi_encode_returns(&results);
+        let (provider, asserter) = mock_provider();
+        asserter.push_success(&"0x10");
+        asserter.push_success(&format!("0x{}", hex::encode(returned)));
+
+        let reading = read(&provider, Address::ZERO, &getters, true)
+            .await
+            .unwrap();
+        assert_eq!(reading.block, 16);
+        assert_eq!(
+            reading.values,
+            [
+                (BALANCE.to_string(), Ok("0.000000000000000005 ETH".into())),
+                ("name".to_string(), Ok("\"Token\"".into())),
+                ("paused".to_string(), Err("revert: no".into())),
+            ]
+        );
+    }
+}
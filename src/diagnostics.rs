@@ -0,0 +1,338 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Severity of a compiler diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Info => write!(f, "info"),
+        }
+    }
+}
+
+/// A single error, warning or info message reported by forge/solc/resolc
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Error kind as printed by solc (e.g. "TypeError", "ParserError", "Warning")
+    pub kind: String,
+    /// Numeric solc error code, if any (e.g. "2314")
+    pub code: Option<String>,
+    pub message: String,
+    pub file: Option<PathBuf>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    /// Source excerpt printed below the location, kept verbatim
+    pub snippet: Vec<String>,
+}
+
+impl Diagnostic {
+    /// Location formatted as "file:line:column" (parts omitted when unknown)
+    pub fn location(&self) -> Option<String> {
+        let file = self.file.as_ref()?;
+        let name = file
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| file.display().to_string());
+        Some(match (self.line, self.column) {
+            (Some(line), Some(col)) => format!("{name}:{line}:{col}"),
+            (Some(line), None) => format!("{name}:{line}"),
+            _ => name,
+        })
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.severity)?;
+        if let Some(code) = &self.code {
+            write!(f, "[{code}]")?;
+        }
+        if let Some(location) = self.location() {
+            write!(f, " {location}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Count diagnostics by severity: (errors, warnings)
+pub fn count(diagnostics: &[Diagnostic]) -> (usize, usize) {
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    let warnings = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Warning)
+        .count();
+    (errors, warnings)
+}
+
+/// Human readable summary such as "2 errors, 1 warning"
+pub fn summary(diagnostics: &[Diagnostic]) -> String {
+    let (errors, warnings) = count(diagnostics);
+    let plural = |n: usize, word: &str| {
+        if n == 1 {
+            format!("{n} {word}")
+        } else {
+            format!("{n} {word}s")
+        }
+    };
+    match (errors, warnings) {
+        (0, 0) => "no diagnostics".to_string(),
+        (e, 0) => plural(e, "error"),
+        (0, w) => plural(w, "warning"),
+        (e, w) => format!("{}, {}", plural(e, "error"), plural(w, "warning")),
+    }
+}
+
+/// Parse the human-readable output of `forge build` into structured diagnostics.
+///
+/// Forge relays solc's formatting:
+///
+/// ```text
+/// Error (2314): Expected ';' but got '}'
+///  --> Demo.sol:10:5:
+///    |
+/// 10 |     }
+///    |     ^
+/// ```
+///
+/// Relative file paths are resolved against `base_dir` (the directory forge ran in).
+pub fn parse_forge_output(output: &str, base_dir: &Path) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut current: Option<Diagnostic> = None;
+    let mut in_snippet = false;
+
+    for raw_line in output.lines() {
+        let line = strip_ansi(raw_line);
+        let line = line.trim_end();
+
+        if let Some(diagnostic) = parse_header(line) {
+            diagnostics.extend(current.take());
+            current = Some(diagnostic);
+            in_snippet = false;
+            continue;
+        }
+
+        let Some(diagnostic) = current.as_mut() else {
+            continue;
+        };
+
+        if let Some(location) = line.trim_start().strip_prefix("-->") {
+            let (file, line_no, column) = parse_location(location.trim());
+            diagnostic.file = Some(resolve_path(base_dir, &file));
+            diagnostic.line = line_no;
+            diagnostic.column = column;
+            in_snippet = true;
+        } else if line.trim().is_empty() {
+            in_snippet = false;
+        } else if in_snippet {
+            diagnostic.snippet.push(line.to_string());
+        } else if diagnostic.file.is_none() && diagnostic.snippet.is_empty() {
+            // Continuation of a multi-line message (e.g. forge's own errors)
+            diagnostic.message.push('\n');
+            diagnostic.message.push_str(line.trim());
+        }
+    }
+
+    diagnostics.extend(current);
+    diagnostics
+}
+
+/// Parse a header line like "Warning (2072): Unused local variable."
+fn parse_header(line: &str) -> Option<Diagnostic> {
+    if line.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let (head, message) = line.split_once(": ")?;
+    let (kind, code) = match head.split_once(" (") {
+        Some((kind, rest)) => {
+            let code = rest.strip_suffix(')')?;
+            if code.is_empty() || !code.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            (kind, Some(code.to_string()))
+        }
+        None => (head, None),
+    };
+
+    if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    let severity = if kind.ends_with("Error") {
+        Severity::Error
+    } else if kind.ends_with("Warning") {
+        Severity::Warning
+    } else if kind == "Info" {
+        Severity::Info
+    } else {
+        return None;
+    };
+
+    let message = message.trim();
+    // Forge wraps solc output in "Error: Compiler run failed:", which is not a diagnostic itself
+    if message.is_empty() || message.starts_with("Compiler run") {
+        return None;
+    }
+
+    Some(Diagnostic {
+        severity,
+        kind: kind.to_string(),
+        code,
+        message: message.to_string(),
+        file: None,
+        line: None,
+        column: None,
+        snippet: Vec::new(),
+    })
+}
+
+/// Parse "path:line:column:" (line and column are optional)
+fn parse_location(location: &str) -> (String, Option<usize>, Option<usize>) {
+    let location = location.trim_end_matches(':');
+    let parts: Vec<&str> = location.rsplitn(3, ':').collect();
+
+    match parts.as_slice() {
+        [col, line, file] => match (line.parse(), col.parse()) {
+            (Ok(line), Ok(col)) => (file.to_string(), Some(line), Some(col)),
+            _ => (location.to_string(), None, None),
+        },
+        [line, file] => match line.parse() {
+            Ok(line) => (file.to_string(), Some(line), None),
+            Err(_) => (location.to_string(), None, None),
+        },
+        _ => (location.to_string(), None, None),
+    }
+}
+
+fn resolve_path(base_dir: &Path, file: &str) -> PathBuf {
+    let path = PathBuf::from(file);
+    if path.is_absolute() {
+        path
+    } else {
+        base_dir.join(path)
+    }
+}
+
+/// Remove ANSI escape sequences (forge colors its output when it thinks it's on a tty)
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' && chars.peek() == Some(&'[') {
+            chars.next();
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAILED_BUILD: &str = "\
+Compiling 1 files with Solc 0.8.28
+Solc 0.8.28 finished in 12.34ms
+Error: Compiler run failed:
+Error (2314): Expected ';' but got '}'
+  --> Demo.sol:10:5:
+   |
+10 |     }
+   |     ^
+
+TypeError (7407): Type uint256 is not implicitly convertible to expected type bool.
+  --> /abs/path/Other.sol:3:16:
+  |
+3 |         return 1;
+  |                ^
+";
+
+    #[test]
+    fn test_parse_errors_with_locations() {
+        let diagnostics = parse_forge_output(FAILED_BUILD, Path::new("/project"));
+        assert_eq!(diagnostics.len(), 2);
+
+        let first = &diagnostics[0];
+        assert_eq!(first.severity, Severity::Error);
+        assert_eq!(first.kind, "Error");
+        assert_eq!(first.code.as_deref(), Some("2314"));
+        assert_eq!(first.message, "Expected ';' but got '}'");
+        assert_eq!(first.file, Some(PathBuf::from("/project/Demo.sol")));
+        assert_eq!(first.line, Some(10));
+        assert_eq!(first.column, Some(5));
+        assert_eq!(first.snippet.len(), 3);
+        assert_eq!(first.snippet[1], "10 |     }");
+
+        let second = &diagnostics[1];
+        assert_eq!(second.kind, "TypeError");
+        assert_eq!(second.file, Some(PathBuf::from("/abs/path/Other.sol")));
+        assert_eq!(second.line, Some(3));
+    }
+
+    #[test]
+    fn test_parse_warnings_on_success() {
+        let output = "\
+Compiler run successful with warnings:
+Warning (2072): Unused local variable.
+ --> Demo.sol:12:9:
+   |
+12 |         uint x = 1;
+   |         ^^^^^^
+
+Warning: SPDX license identifier not provided in source file.
+--> Demo.sol
+";
+        let diagnostics = parse_forge_output(output, Path::new("/project"));
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+        assert_eq!(diagnostics[1].code, None);
+        assert_eq!(diagnostics[1].line, None);
+        assert_eq!(diagnostics[1].location().as_deref(), Some("Demo.sol"));
+        assert_eq!(summary(&diagnostics), "2 warnings");
+    }
+
+    #[test]
+    fn test_parse_strips_ansi_colors() {
+        let output = "\u{1b}[31mError (1234)\u{1b}[0m: Broken\n --> A.sol:1:1:\n";
+        let diagnostics = parse_forge_output(output, Path::new("."));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "Broken");
+        assert_eq!(diagnostics[0].line, Some(1));
+    }
+
+    #[test]
+    fn test_non_diagnostic_lines_ignored() {
+        let output = "Compiling 1 files with Solc 0.8.28\nNo files changed, compilation skipped\n";
+        assert!(parse_forge_output(output, Path::new(".")).is_empty());
+    }
+
+    #[test]
+    fn test_display_and_summary() {
+        let diagnostics = parse_forge_output(FAILED_BUILD, Path::new("/project"));
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "error[2314] Demo.sol:10:5: Expected ';' but got '}'"
+        );
+        assert_eq!(summary(&diagnostics), "2 errors");
+    }
+}
@@ -1,12 +1,33 @@
+mod accounts;
+mod address_book;
 mod app;
+mod attach;
+mod block_watcher;
+mod broadcast;
+mod call_options;
 mod cards;
 mod compile;
+mod compile_cache;
+mod create2;
+mod dashboard;
+mod diagnostics;
 mod logger;
+mod manifest;
 mod method_list;
+mod multicall;
+mod pending_tx;
 mod prompts;
 mod provider;
+mod proxy;
+mod raw_tx;
+mod rpc_log;
+mod signing;
+mod size_report;
 mod store;
+#[cfg(test)]
+mod test_support;
 mod tui;
+mod verify;
 
 use anyhow::Result;
 use clap::Parser;
@@ -19,6 +40,22 @@ struct Args {
     /// Path to config file (default: ~/.evm-cli/config.json)
     #[arg(short, long)]
     config: Option<PathBuf>,
+
+    /// Run a deployment manifest, print the results and exit
+    #[arg(short, long)]
+    manifest: Option<PathBuf>,
+
+    /// Write the address book (<chainId>.json per chain, with ABIs) to DIR and exit
+    #[arg(long, value_name = "DIR")]
+    export_addresses: Option<PathBuf>,
+
+    /// Also write addresses.ts (with --export-addresses)
+    #[arg(long, requires = "export_addresses")]
+    ts: bool,
+
+    /// Also write <chainId>.env per chain (with --export-addresses)
+    #[arg(long, requires = "export_addresses")]
+    env: bool,
 }
 
 #[tokio::main]
@@ -32,15 +69,37 @@ async fn main() -> Result<()> {
     let store = store::DeploymentStore::load_from(args.config)?;
 
     // Create provider (no connection test - will be done asynchronously)
-    let (provider, signer) = provider::create_provider(&store.config)?;
-    let signer_address = signer.address();
+    let endpoints = provider::Endpoints::from_config(&store.config)?;
+    let rpc_log = rpc_log::RpcLog::default();
+    let node_sender = accounts::NodeSender::default();
+    let (provider, signer) = provider::create_provider(
+        &store.config,
+        endpoints.clone(),
+        rpc_log.clone(),
+        node_sender.clone(),
+    )?;
 
-    // Create app with the already-loaded store and signer address
-    let mut app = app::App::new(provider, store, signer_address);
+    // Create app with the already-loaded store and signer
+    let mut app = app::App::new(provider, store, signer, endpoints, rpc_log, node_sender);
 
     // Try initial connection (non-blocking failure)
     app.try_connect().await;
 
+    if let Some(manifest) = args.manifest {
+        return app.run_manifest_headless(&manifest).await;
+    }
+
+    if let Some(dir) = args.export_addresses {
+        // Dead instances are left out of the address book
+        app.wait_for_instance_check().await;
+        let formats = address_book::ExportFormats {
+            typescript: args.ts,
+            env: args.env,
+        };
+        println!("{}", app.export_address_book(&dir, formats)?);
+        return Ok(());
+    }
+
     // Create connection card (will be updated by polling if disconnected)
     app.add_connection_card();
 
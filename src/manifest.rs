@@ -0,0 +1,368 @@
+use alloy::dyn_abi::{DynSolType, DynSolValue};
+use alloy::json_abi::{Function, JsonAbi, Param};
+use alloy::primitives::Address;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::compile::{self, BytecodeTarget, CompiledContract, CompilerSettings};
+
+/// Reference to the configured account, usable in any argument
+pub const DEPLOYER_REF: &str = "deployer";
+
+/// A deployment manifest: contracts deployed in order, whose arguments may reference
+/// earlier deployments as `${name}`
+///
+/// ```json
+/// {
+///   "target": "evm",
+///   "deployments": [
+///     { "name": "token", "contract": "Token", "path": "src/Token.sol", "args": ["Token", "TKN"] },
+///     {
+///       "name": "vault", "contract": "Vault", "path": "src/Vault.sol", "args": ["${token}"],
+///       "calls": [{ "to": "token", "method": "grantMinter", "args": ["${vault}"] }]
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    /// Target for steps that don't set their own
+    #[serde(default)]
+    pub target: BytecodeTarget,
+    pub deployments: Vec<Step>,
+}
+
+/// One contract to deploy, followed by its initialization calls
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Step {
+    /// Name later steps use to reference this deployment
+    pub name: String,
+    pub contract: String,
+    /// Solidity source, relative to the manifest
+    pub path: PathBuf,
+    #[serde(default)]
+    pub target: Option<BytecodeTarget>,
+    /// Constructor arguments
+    #[serde(default)]
+    pub args: Vec<Value>,
+    #[serde(default)]
+    pub calls: Vec<Call>,
+}
+
+/// A transaction sent once the step's contract is deployed
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Call {
+    /// Step whose contract is called (defaults to the step declaring the call)
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Function name, or full signature for overloads (`mint(address,uint256)`)
+    pub method: String,
+    #[serde(default)]
+    pub args: Vec<Value>,
+}
+
+impl Manifest {
+    /// Read and validate a manifest, resolving source paths against its directory
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut manifest: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+        let base = path.parent().unwrap_or(Path::new("."));
+        for step in &mut manifest.deployments {
+            let source = base.join(&step.path);
+            step.path = source.canonicalize().unwrap_or(source);
+        }
+
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    /// Names must be unique and references must point at earlier steps
+    /// (calls may also reference their own step)
+    fn validate(&self) -> Result<()> {
+        if self.deployments.is_empty() {
+            bail!("Manifest has no deployments");
+        }
+
+        let mut known: HashSet<&str> = HashSet::from([DEPLOYER_REF]);
+        for step in &self.deployments {
+            if step.name.is_empty() {
+                bail!("Deployment of {} has no name", step.contract);
+            }
+            if known.contains(step.name.as_str()) {
+                bail!("Duplicate or reserved deployment name \"{}\"", step.name);
+            }
+
+            for name in step.args.iter().flat_map(references) {
+                if !known.contains(name.as_str()) {
+                    bail!(
+                        "{}: constructor argument references \"{name}\", which is not deployed before it",
+                        step.name
+                    );
+                }
+            }
+
+            known.insert(&step.name);
+            for call in &step.calls {
+                if let Some(to) = &call.to {
+                    if to == DEPLOYER_REF || !known.contains(to.as_str()) {
+                        bail!(
+                            "{}: {}() is sent to unknown deployment \"{to}\"",
+                            step.name,
+                            call.method
+                        );
+                    }
+                }
+                for name in call.args.iter().flat_map(references) {
+                    if !known.contains(name.as_str()) {
+                        bail!(
+                            "{}: {}() references unknown deployment \"{name}\"",
+                            step.name,
+                            call.method
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn step_target(&self, step: &Step) -> BytecodeTarget {
+        step.target.unwrap_or(self.target)
+    }
+
+    /// Compile every step with its settings (blocking, goes through the compile cache)
+    pub fn compile(&self, settings: &[CompilerSettings]) -> Vec<Result<CompiledContract>> {
+        self.deployments
+            .iter()
+            .zip(settings)
+            .map(|(step, settings)| {
+                compile::compile_contract(
+                    &step.path,
+                    &step.contract,
+                    self.step_target(step),
+                    settings,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Names referenced as `${name}` in an argument
+fn references(value: &Value) -> Vec<String> {
+    references_in(&arg_text(value))
+}
+
+fn references_in(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        names.push(rest[start + 2..start + 2 + len].to_string());
+        rest = &rest[start + 3 + len..];
+    }
+    names
+}
+
+/// Arguments are written as JSON; strings are used as-is, anything else in its JSON form
+/// (`[1, 2]`, `true`, `42`)
+fn arg_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Substitute `${name}` references with deployed addresses
+fn substitute(text: &str, addresses: &HashMap<String, Address>) -> Result<String> {
+    let mut out = text.to_string();
+    for name in references_in(text) {
+        let Some(address) = addresses.get(&name) else {
+            bail!("Unknown deployment \"{name}\"");
+        };
+        out = out.replace(&format!("${{{name}}}"), &address.to_string());
+    }
+    Ok(out)
+}
+
+/// Turn manifest arguments into ABI values for `params`
+pub fn resolve_args(
+    values: &[Value],
+    params: &[Param],
+    addresses: &HashMap<String, Address>,
+) -> Result<Vec<DynSolValue>> {
+    if values.len() != params.len() {
+        bail!("Expected {} arguments, got {}", params.len(), values.len());
+    }
+    values
+        .iter()
+        .zip(params)
+        .enumerate()
+        .map(|(i, (value, param))| {
+            let name = if param.name.is_empty() {
+                format!("arg{i}")
+            } else {
+                param.name.clone()
+            };
+            let text = substitute(&arg_text(value), addresses)?;
+            let ty: DynSolType = param
+                .selector_type()
+                .parse()
+                .with_context(|| format!("{name}: unsupported type {}", param.ty))?;
+            ty.coerce_str(&text)
+                .with_context(|| format!("{name}: invalid {} \"{text}\"", param.ty))
+        })
+        .collect()
+}
+
+/// Find the function a call refers to: by full signature, or by name and argument count
+pub fn find_function<'a>(abi: &'a JsonAbi, method: &str, arg_count: usize) -> Result<&'a Function> {
+    if method.contains('(') {
+        let signature: String = method.chars().filter(|c| !c.is_whitespace()).collect();
+        return abi
+            .functions()
+            .find(|f| f.signature() == signature)
+            .with_context(|| format!("No function {method}"));
+    }
+
+    let overloads = abi
+        .function(method)
+        .with_context(|| format!("No function {method}"))?;
+    let mut matching = overloads.iter().filter(|f| f.inputs.len() == arg_count);
+    match (matching.next(), matching.next()) {
+        (Some(function), None) => Ok(function),
+        (Some(_), Some(_)) => bail!("{method} is overloaded, use its full signature"),
+        (None, _) => bail!("No {method} taking {arg_count} arguments"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, content: &str) -> PathBuf {
+        let path = dir.join("deploy.json");
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_load_resolves_paths_and_targets() {
+        let dir = TempDir::new().unwrap();
+        let path = write(
+            dir.path(),
+            r#"{
+                "target": "pvm",
+                "deployments": [
+                    { "name": "token", "contract": "Token", "path": "Token.sol", "args": ["T", 18] },
+                    { "name": "vault", "contract": "Vault", "path": "src/Vault.sol", "target": "evm",
+                      "args": ["${token}"], "calls": [{ "to": "token", "method": "mint", "args": ["${vault}", "${deployer}"] }] }
+                ]
+            }"#,
+        );
+        let manifest = Manifest::load(&path).unwrap();
+        assert_eq!(manifest.deployments[0].path, dir.path().join("Token.sol"));
+        assert_eq!(
+            manifest.step_target(&manifest.deployments[0]),
+            BytecodeTarget::Pvm
+        );
+        assert_eq!(
+            manifest.step_target(&manifest.deployments[1]),
+            BytecodeTarget::Evm
+        );
+        assert_eq!(
+            manifest.deployments[1].calls[0].to.as_deref(),
+            Some("token")
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_references() {
+        let dir = TempDir::new().unwrap();
+        let forward = write(
+            dir.path(),
+            r#"{ "deployments": [
+                { "name": "a", "contract": "A", "path": "A.sol", "args": ["${b}"] },
+                { "name": "b", "contract": "B", "path": "B.sol" }
+            ] }"#,
+        );
+        let err = Manifest::load(&forward).unwrap_err().to_string();
+        assert!(
+            err.contains("\"b\", which is not deployed before it"),
+            "{err}"
+        );
+
+        let duplicate = write(
+            dir.path(),
+            r#"{ "deployments": [
+                { "name": "a", "contract": "A", "path": "A.sol" },
+                { "name": "a", "contract": "B", "path": "B.sol" }
+            ] }"#,
+        );
+        assert!(Manifest::load(&duplicate).is_err());
+
+        let unknown_field = write(
+            dir.path(),
+            r#"{ "deployments": [{ "name": "a", "contract": "A", "path": "A.sol", "arg": [] }] }"#,
+        );
+        assert!(Manifest::load(&unknown_field).is_err());
+    }
+
+    #[test]
+    fn test_resolve_args() {
+        let token = Address::repeat_byte(0x11);
+        let addresses = HashMap::from([("token".to_string(), token)]);
+        let params: Vec<Param> = serde_json::from_str(
+            r#"[
+                { "name": "token", "type": "address", "internalType": "address" },
+                { "name": "amounts", "type": "uint256[]", "internalType": "uint256[]" },
+                { "name": "label", "type": "string", "internalType": "string" }
+            ]"#,
+        )
+        .unwrap();
+
+        let values: Vec<Value> =
+            serde_json::from_str(r#"["${token}", [1, 2], "vault for ${token}"]"#).unwrap();
+        let args = resolve_args(&values, &params, &addresses).unwrap();
+        assert_eq!(args[0], DynSolValue::Address(token));
+        assert_eq!(args[1].as_array().unwrap().len(), 2);
+        assert_eq!(args[2].as_str().unwrap(), format!("vault for {token}"));
+
+        let missing: Vec<Value> = serde_json::from_str(r#"["${nope}", [], ""]"#).unwrap();
+        assert!(resolve_args(&missing, &params, &addresses).is_err());
+        assert!(resolve_args(&values[..1], &params, &addresses).is_err());
+    }
+
+    #[test]
+    fn test_find_function() {
+        let abi = JsonAbi::parse([
+            "function mint(address to)",
+            "function mint(address to, uint256 amount)",
+            "function pause()",
+        ])
+        .unwrap();
+        assert_eq!(find_function(&abi, "pause", 0).unwrap().name, "pause");
+        assert_eq!(find_function(&abi, "mint", 2).unwrap().inputs.len(), 2);
+        assert_eq!(
+            find_function(&abi, "mint(address, uint256)", 2)
+                .unwrap()
+                .inputs
+                .len(),
+            2
+        );
+        assert!(find_function(&abi, "mint", 3).is_err());
+        assert!(find_function(&abi, "burn", 0).is_err());
+    }
+}
@@ -4,6 +4,12 @@ use alloy::json_abi::{Function, JsonAbi, StateMutability};
 pub enum MethodSelection {
     Constructor,
     Function(Function),
+    /// `fallback()`, reached with calldata no function selector matches
+    Fallback {
+        payable: bool,
+    },
+    /// `receive()`, reached with empty calldata
+    Receive,
 }
 
 pub struct MethodDisplay {
@@ -81,9 +87,42 @@ pub fn list_methods(abi: &JsonAbi, include_constructor: bool) -> Vec<MethodDispl
         });
     }
 
+    // fallback() and receive() are not in `abi.functions()`
+    if let Some(fallback) = &abi.fallback {
+        let payable = fallback.state_mutability == StateMutability::Payable;
+        methods.push(MethodDisplay {
+            label: "fallback()".to_string(),
+            tag: if payable { "payable" } else { "send" },
+            selection: MethodSelection::Fallback { payable },
+        });
+    }
+    if abi.receive.is_some() {
+        methods.push(MethodDisplay {
+            label: "receive()".to_string(),
+            tag: "payable",
+            selection: MethodSelection::Receive,
+        });
+    }
+
     methods
 }
 
+/// View and pure functions without parameters, by name
+pub fn getters(abi: &JsonAbi) -> Vec<&Function> {
+    let mut getters: Vec<_> = abi
+        .functions()
+        .filter(|f| {
+            f.inputs.is_empty()
+                && matches!(
+                    f.state_mutability,
+                    StateMutability::View | StateMutability::Pure
+                )
+        })
+        .collect();
+    getters.sort_by(|a, b| a.name.cmp(&b.name));
+    getters
+}
+
 fn format_params(params: &[alloy::json_abi::Param]) -> String {
     params
         .iter()
@@ -110,3 +149,51 @@ fn format_outputs(outputs: &[alloy::json_abi::Param]) -> String {
     let types: Vec<_> = outputs.iter().map(|p| p.ty.to_string()).collect();
     format!("({})", types.join(", "))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_and_receive_are_listed_last() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[
+                {"type": "receive", "stateMutability": "payable"},
+                {"type": "fallback", "stateMutability": "nonpayable"},
+                {"type": "function", "name": "total", "inputs": [], "outputs": [{"name": "", "type": "uint256"}], "stateMutability": "view"},
+                {"type": "function", "name": "deposit", "inputs": [], "outputs": [], "stateMutability": "payable"}
+            ]"#,
+        )
+        .unwrap();
+
+        let methods = list_methods(&abi, false);
+        let labels: Vec<_> = methods.iter().map(|m| m.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            ["total() -> uint256", "deposit()", "fallback()", "receive()"]
+        );
+        assert!(matches!(
+            methods[2].selection,
+            MethodSelection::Fallback { payable: false }
+        ));
+        assert_eq!(methods[2].tag, "send");
+        assert_eq!(methods[3].tag, "payable");
+
+        let abi = JsonAbi::parse(["function deposit() payable"]).unwrap();
+        assert_eq!(list_methods(&abi, false).len(), 1);
+    }
+
+    #[test]
+    fn test_getters() {
+        let abi = JsonAbi::parse([
+            "function totalSupply() view returns (uint256)",
+            "function balanceOf(address) view returns (uint256)",
+            "function decimals() pure returns (uint8)",
+            "function name() view returns (string)",
+            "function mint() returns (uint256)",
+        ])
+        .unwrap();
+        let names: Vec<_> = getters(&abi).iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["decimals", "name", "totalSupply"]);
+    }
+}
@@ -0,0 +1,190 @@
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::providers::bindings::IMulticall3;
+use alloy::providers::{Provider, MULTICALL3_ADDRESS};
+use alloy::sol_types::{GenericRevertReason, RevertReason, SolCall};
+use anyhow::{bail, ensure, Context, Result};
+
+/// Multicall3, present at the same address on most chains (<https://github.com/mds1/multicall>)
+pub const ADDRESS: Address = MULTICALL3_ADDRESS;
+
+/// Length of the constructor in front of the runtime code in `IMulticall3::BYTECODE`
+const CONSTRUCTOR_LEN: usize = 0x20;
+
+/// How Multicall3 became available
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Multicall3Setup {
+    AlreadyDeployed,
+    /// Installed with `anvil_setCode` / `hardhat_setCode`
+    CodeSet,
+}
+
+/// What one call of a batch returned: its return data, or its revert data
+pub type Outcome = std::result::Result<Bytes, Bytes>;
+
+/// Runtime code of Multicall3, for dev nodes that can set code directly
+pub fn runtime_code() -> Bytes {
+    IMulticall3::BYTECODE.slice(CONSTRUCTOR_LEN..)
+}
+
+/// Make sure Multicall3 exists. On local nodes (`allow_install`) a missing one is installed
+/// at its usual address through the dev node's set-code RPC.
+pub async fn ensure_deployed<P: Provider>(
+    provider: &P,
+    allow_install: bool,
+) -> Result<Multicall3Setup> {
+    if !provider.get_code_at(ADDRESS).await?.is_empty() {
+        return Ok(Multicall3Setup::AlreadyDeployed);
+    }
+    if !allow_install {
+        bail!("Multicall3 {ADDRESS} is not deployed on this chain");
+    }
+
+    let code = runtime_code();
+    for method in ["anvil_setCode", "hardhat_setCode"] {
+        let result = provider
+            .raw_request::<_, serde_json::Value>(method.into(), (ADDRESS, code.clone()))
+            .await;
+        match result {
+            Ok(_) if !provider.get_code_at(ADDRESS).await?.is_empty() => {
+                return Ok(Multicall3Setup::CodeSet)
+            }
+            Ok(_) => {}
+            Err(e) => log::info!("[MULTICALL] {method} unavailable: {e}"),
+        }
+    }
+    bail!("The node cannot set code, so Multicall3 could not be installed")
+}
+
+/// Calldata of `aggregate3` for `calls` (target and calldata), each allowed to fail on its own
+pub fn encode(calls: &[(Address, Bytes)]) -> Bytes {
+    let calls = calls
+        .iter()
+        .map(|(target, data)| IMulticall3::Call3 {
+            target: *target,
+            allowFailure: true,
+            callData: data.clone(),
+        })
+        .collect();
+    IMulticall3::aggregate3Call { calls }.abi_encode().into()
+}
+
+/// Multicall3's own `getEthBalance`, to read the balance of `address` in the same batch
+pub fn balance_call(address: Address) -> (Address, Bytes) {
+    let data = IMulticall3::getEthBalanceCall { addr: address }.abi_encode();
+    (ADDRESS, data.into())
+}
+
+pub fn decode_balance(data: &[u8]) -> Result<U256> {
+    IMulticall3::getEthBalanceCall::abi_decode_returns(data)
+        .context("Unexpected balance from Multicall3")
+}
+
+/// Split the return data of `aggregate3` back into one outcome per call
+pub fn decode(data: &[u8], count: usize) -> Result<Vec<Outcome>> {
+    let results = IMulticall3::aggregate3Call::abi_decode_returns(data)
+        .context("Unexpected return data from Multicall3")?;
+    ensure!(
+        results.len() == count,
+        "Multicall3 returned {} results for {count} calls",
+        results.len()
+    );
+    Ok(results
+        .into_iter()
+        .map(|result| match result.success {
+            true => Ok(result.returnData),
+            false => Err(result.returnData),
+        })
+        .collect())
+}
+
+/// Revert reason of a failed call: the `Error(string)` or panic message when there is one,
+/// the raw data otherwise
+pub fn revert_message(data: &[u8]) -> String {
+    if data.is_empty() {
+        return "Execution reverted".to_string();
+    }
+    match GenericRevertReason::decode(data) {
+        Some(reason @ RevertReason::ContractError(_)) => reason.to_string(),
+        _ => format!("Execution reverted: 0x{}", hex::encode(data)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::mock_provider;
+    use alloy::sol_types::{Revert, SolError, SolValue};
+
+    #[test]
+    fn test_runtime_code() {
+        // The constructor copies the 0x0ee0 bytes that follow it and returns them
+        let constructor = &IMulticall3::BYTECODE[..CONSTRUCTOR_LEN];
+        assert!(constructor.windows(3).any(|w| w == [0x61, 0x0e, 0xe0]));
+        assert_eq!(runtime_code().len(), 0x0ee0);
+        assert_eq!(&runtime_code()[..4], &[0x60, 0x80, 0x60, 0x40]);
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let calls = [
+            (
+                Address::repeat_byte(0x11),
+                Bytes::from(vec![0x06, 0xfd, 0xde, 0x03]),
+            ),
+            (
+                Address::repeat_byte(0x22),
+                Bytes::from(vec![0x18, 0x16, 0x0d, 0xdd]),
+            ),
+        ];
+        let calldata = encode(&calls);
+        let decoded = IMulticall3::aggregate3Call::abi_decode(&calldata).unwrap();
+        assert_eq!(decoded.calls.len(), 2);
+        assert!(decoded.calls.iter().all(|call| call.allowFailure));
+        assert_eq!(decoded.calls[1].target, Address::repeat_byte(0x22));
+
+        let revert = Revert::from("not allowed").abi_encode();
+        let returned = vec![
+            IMulticall3::Result {
+                success: true,
+                returnData: 42u64.abi_encode().into(),
+            },
+            IMulticall3::Result {
+                success: false,
+                returnData: revert.into(),
+            },
+        ];
+        let data = IMulticall3::aggregate3Call::abi_encode_returns(&returned);
+        let outcomes = decode(&data, 2).unwrap();
+        assert_eq!(outcomes[0].as_ref().unwrap().len(), 32);
+        let reason = outcomes[1].as_ref().unwrap_err();
+        assert_eq!(revert_message(reason), "revert: not allowed");
+        assert!(decode(&data, 3).is_err());
+
+        assert_eq!(revert_message(&[]), "Execution reverted");
+        assert_eq!(revert_message(&[0xde, 0xad]), "Execution reverted: 0xdead");
+    }
+
+    #[tokio::test]
+    async fn test_ensure_deployed() {
+        let (provider, asserter) = mock_provider();
+
+        asserter.push_success(&"0x6080");
+        assert_eq!(
+            ensure_deployed(&provider, false).await.unwrap(),
+            Multicall3Setup::AlreadyDeployed
+        );
+
+        // Missing on a chain it may not be installed on
+        asserter.push_success(&"0x");
+        assert!(ensure_deployed(&provider, false).await.is_err());
+
+        // Installed through anvil_setCode
+        asserter.push_success(&"0x");
+        asserter.push_success(&serde_json::Value::Null);
+        asserter.push_success(&format!("0x{}", hex::encode(runtime_code())));
+        assert_eq!(
+            ensure_deployed(&provider, true).await.unwrap(),
+            Multicall3Setup::CodeSet
+        );
+    }
+}
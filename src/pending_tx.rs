@@ -0,0 +1,311 @@
+use alloy::consensus::Transaction as _;
+use alloy::network::TransactionBuilder;
+use alloy::primitives::utils::format_units;
+use alloy::primitives::{Address, TxHash, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::{Transaction, TransactionReceipt, TransactionRequest};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::store::Config;
+
+/// How often pending transactions are checked for a receipt
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Gas of a plain value transfer, which is what a cancellation is
+const TRANSFER_GAS: u64 = 21_000;
+
+/// How long to wait for a receipt and how deep it must be
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReceiptPolicy {
+    pub timeout: Duration,
+    /// 1 means mined; each additional confirmation is one more block on top
+    pub confirmations: u64,
+}
+
+impl ReceiptPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            timeout: Duration::from_secs(config.receipt_timeout_secs),
+            confirmations: config.confirmations.max(1),
+        }
+    }
+}
+
+/// Fee fields of a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fees {
+    Legacy {
+        gas_price: u128,
+    },
+    Eip1559 {
+        max_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+    },
+}
+
+impl Fees {
+    pub fn of(tx: &Transaction) -> Self {
+        match tx.max_priority_fee_per_gas() {
+            Some(max_priority_fee_per_gas) => Fees::Eip1559 {
+                max_fee_per_gas: tx.max_fee_per_gas(),
+                max_priority_fee_per_gas,
+            },
+            None => Fees::Legacy {
+                gas_price: tx.gas_price().unwrap_or_default(),
+            },
+        }
+    }
+
+    /// Fees for a replacement: a fifth above these (nodes want at least a tenth more to
+    /// accept one) and never below what the network asks for now
+    pub fn bump(self, network: Option<Fees>) -> Self {
+        let raise = |fee: u128| fee.saturating_add(fee / 5).saturating_add(1);
+        match (self, network) {
+            (
+                Fees::Eip1559 {
+                    max_fee_per_gas,
+                    max_priority_fee_per_gas,
+                },
+                network,
+            ) => {
+                let (network_max, network_priority) = match network {
+                    Some(Fees::Eip1559 {
+                        max_fee_per_gas,
+                        max_priority_fee_per_gas,
+                    }) => (max_fee_per_gas, max_priority_fee_per_gas),
+                    Some(Fees::Legacy { gas_price }) => (gas_price, 0),
+                    None => (0, 0),
+                };
+                let max_priority_fee_per_gas =
+                    raise(max_priority_fee_per_gas).max(network_priority);
+                Fees::Eip1559 {
+                    max_fee_per_gas: raise(max_fee_per_gas)
+                        .max(network_max)
+                        .max(max_priority_fee_per_gas),
+                    max_priority_fee_per_gas,
+                }
+            }
+            (Fees::Legacy { gas_price }, network) => {
+                let network_price = match network {
+                    Some(Fees::Legacy { gas_price }) => gas_price,
+                    Some(Fees::Eip1559 {
+                        max_fee_per_gas, ..
+                    }) => max_fee_per_gas,
+                    None => 0,
+                };
+                Fees::Legacy {
+                    gas_price: raise(gas_price).max(network_price),
+                }
+            }
+        }
+    }
+
+    pub fn apply(self, tx: TransactionRequest) -> TransactionRequest {
+        match self {
+            Fees::Legacy { gas_price } => tx.with_gas_price(gas_price),
+            Fees::Eip1559 {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => tx
+                .with_max_fee_per_gas(max_fee_per_gas)
+                .with_max_priority_fee_per_gas(max_priority_fee_per_gas),
+        }
+    }
+}
+
+impl fmt::Display for Fees {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fees::Legacy { gas_price } => write!(f, "gas price {} gwei", gwei(*gas_price)),
+            Fees::Eip1559 {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => write!(
+                f,
+                "max fee {} gwei, priority {} gwei",
+                gwei(*max_fee_per_gas),
+                gwei(*max_priority_fee_per_gas)
+            ),
+        }
+    }
+}
+
+/// Wei as gwei without trailing zeros
+fn gwei(wei: u128) -> String {
+    let formatted = format_units(U256::from(wei), "gwei").unwrap_or_else(|_| wei.to_string());
+    match formatted.split_once('.') {
+        Some((whole, fraction)) if fraction.trim_end_matches('0').is_empty() => whole.to_string(),
+        Some(_) => formatted.trim_end_matches('0').to_string(),
+        None => formatted,
+    }
+}
+
+/// What it takes to replace a transaction the node accepted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SentTx {
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub fees: Fees,
+}
+
+impl SentTx {
+    pub fn of(tx: &Transaction) -> Self {
+        Self {
+            nonce: tx.nonce(),
+            gas_limit: tx.gas_limit(),
+            fees: Fees::of(tx),
+        }
+    }
+}
+
+/// Hashes of one nonce: the original transaction followed by its replacements
+pub type Watched = Arc<Mutex<Vec<TxHash>>>;
+
+/// A speed-up re-sends `request` as is; a cancellation sends nothing to `from` itself.
+/// Both reuse the nonce of `sent` so only one of them can be mined.
+pub fn replacement(
+    request: &TransactionRequest,
+    from: Address,
+    sent: SentTx,
+    fees: Fees,
+    cancel: bool,
+) -> TransactionRequest {
+    let (tx, gas_limit) = if cancel {
+        let mut tx = TransactionRequest::default()
+            .with_from(from)
+            .with_to(from)
+            .with_value(U256::ZERO);
+        if let Some(chain_id) = request.chain_id {
+            tx = tx.with_chain_id(chain_id);
+        }
+        (tx, TRANSFER_GAS)
+    } else {
+        (request.clone(), sent.gas_limit)
+    };
+    fees.apply(tx.with_nonce(sent.nonce).with_gas_limit(gas_limit))
+}
+
+/// Poll until any of the `watched` hashes has a receipt with enough confirmations, or
+/// the timeout runs out
+pub async fn wait_for_receipt<P: Provider>(
+    provider: &P,
+    watched: &Watched,
+    policy: ReceiptPolicy,
+) -> Result<TransactionReceipt, String> {
+    let started = Instant::now();
+    loop {
+        let hashes = watched.lock().expect("watched hashes lock").clone();
+        for hash in hashes {
+            match provider.get_transaction_receipt(hash).await {
+                Ok(Some(receipt)) if is_confirmed(provider, &receipt, policy).await => {
+                    return Ok(receipt)
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("[TX] receipt of {hash:?}: {e}"),
+            }
+        }
+
+        if started.elapsed() >= policy.timeout {
+            return Err(format!(
+                "no receipt after {}s (it may still be mined later)",
+                policy.timeout.as_secs()
+            ));
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+async fn is_confirmed<P: Provider>(
+    provider: &P,
+    receipt: &TransactionReceipt,
+    policy: ReceiptPolicy,
+) -> bool {
+    let Some(mined_in) = receipt.block_number else {
+        return false;
+    };
+    if policy.confirmations <= 1 {
+        return true;
+    }
+    match provider.get_block_number().await {
+        Ok(head) => head + 1 >= mined_in + policy.confirmations,
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GWEI: u128 = 1_000_000_000;
+
+    #[test]
+    fn test_bump() {
+        let fees = Fees::Eip1559 {
+            max_fee_per_gas: 10 * GWEI,
+            max_priority_fee_per_gas: GWEI,
+        };
+        assert_eq!(
+            fees.bump(None),
+            Fees::Eip1559 {
+                max_fee_per_gas: 12 * GWEI + 1,
+                max_priority_fee_per_gas: GWEI + GWEI / 5 + 1,
+            }
+        );
+
+        // The network asking for more wins
+        let busy = Fees::Eip1559 {
+            max_fee_per_gas: 30 * GWEI,
+            max_priority_fee_per_gas: 2 * GWEI,
+        };
+        assert_eq!(fees.bump(Some(busy)), busy);
+
+        let legacy = Fees::Legacy { gas_price: 0 };
+        assert_eq!(legacy.bump(None), Fees::Legacy { gas_price: 1 });
+    }
+
+    #[test]
+    fn test_replacement() {
+        let me = Address::repeat_byte(0x11);
+        let token = Address::repeat_byte(0x22);
+        let request = TransactionRequest::default()
+            .with_to(token)
+            .with_input(vec![0xa9, 0x05, 0x9c, 0xbb])
+            .with_chain_id(31337);
+        let sent = SentTx {
+            nonce: 7,
+            gas_limit: 50_000,
+            fees: Fees::Legacy { gas_price: GWEI },
+        };
+        let fees = sent.fees.bump(None);
+
+        let speed_up = replacement(&request, me, sent, fees, false);
+        assert_eq!(speed_up.nonce, Some(7));
+        assert_eq!(speed_up.gas, Some(50_000));
+        assert_eq!(speed_up.to, request.to);
+        assert_eq!(speed_up.input, request.input);
+        assert_eq!(speed_up.gas_price, Some(GWEI + GWEI / 5 + 1));
+
+        let cancel = replacement(&request, me, sent, fees, true);
+        assert_eq!(cancel.nonce, Some(7));
+        assert_eq!(cancel.gas, Some(TRANSFER_GAS));
+        assert_eq!(cancel.to, Some(me.into()));
+        assert_eq!(cancel.value, Some(U256::ZERO));
+        assert_eq!(cancel.chain_id, Some(31337));
+        assert!(cancel.input.input().is_none());
+    }
+
+    #[test]
+    fn test_display() {
+        let fees = Fees::Eip1559 {
+            max_fee_per_gas: 12 * GWEI + GWEI / 2,
+            max_priority_fee_per_gas: GWEI,
+        };
+        assert_eq!(fees.to_string(), "max fee 12.5 gwei, priority 1 gwei");
+        assert_eq!(
+            Fees::Legacy { gas_price: 7 }.to_string(),
+            "gas price 0.000000007 gwei"
+        );
+    }
+}
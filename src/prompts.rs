@@ -2,7 +2,12 @@ use alloy::dyn_abi::DynSolValue;
 use alloy::json_abi::Param;
 
 pub fn format_method_call(name: &str, params: &[Param], args: &[DynSolValue]) -> String {
-    let params_str: Vec<String> = params
+    format!("{}({})", name, format_named_args(params, args).join(", "))
+}
+
+/// Each argument as `name: value` (the type stands in for unnamed parameters)
+pub fn format_named_args(params: &[Param], args: &[DynSolValue]) -> Vec<String> {
+    params
         .iter()
         .zip(args.iter())
         .map(|(param, arg)| {
@@ -13,9 +18,19 @@ pub fn format_method_call(name: &str, params: &[Param], args: &[DynSolValue]) ->
             };
             format!("{param_name}: {}", format_return_value(arg))
         })
-        .collect();
+        .collect()
+}
 
-    format!("{}({})", name, params_str.join(", "))
+/// All return values of a call: a single one as is, several as a tuple
+pub fn format_return_values(values: &[DynSolValue]) -> String {
+    match values {
+        [] => "(no return value)".to_string(),
+        [single] => format_return_value(single),
+        multiple => {
+            let formatted: Vec<_> = multiple.iter().map(format_return_value).collect();
+            format!("({})", formatted.join(", "))
+        }
+    }
 }
 
 pub fn format_return_value(value: &DynSolValue) -> String {
@@ -31,7 +46,7 @@ pub fn format_return_value(value: &DynSolValue) -> String {
             let items: Vec<_> = arr.iter().map(format_return_value).collect();
             format!("[{}]", items.join(", "))
         }
-        DynSolValue::Tuple(fields) => {
+        DynSolValue::Tuple(fields) | DynSolValue::CustomStruct { tuple: fields, .. } => {
             let items: Vec<_> = fields.iter().map(format_return_value).collect();
             format!("({})", items.join(", "))
         }
@@ -1,15 +1,48 @@
 use alloy::network::EthereumWallet;
+use alloy::providers::fillers::BlobGasFiller;
 use alloy::providers::{Provider, ProviderBuilder};
+use alloy::pubsub::PubSubConnect;
+use alloy::rpc::client::{BuiltInConnectionString, ClientBuilder};
+use alloy::rpc::json_rpc::{RequestPacket, ResponsePacket};
 use alloy::signers::local::PrivateKeySigner;
-use anyhow::{Context, Result};
+use alloy::transports::http::reqwest::{
+    self,
+    header::{HeaderMap, HeaderName, HeaderValue},
+};
+use alloy::transports::http::Http;
+use alloy::transports::ipc::IpcConnect;
+use alloy::transports::ws::WsConnect;
+use alloy::transports::{
+    Authorization, BoxTransport, RpcError, Transport, TransportConnect, TransportError,
+    TransportErrorKind, TransportFut,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::Poll;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tower::Service;
 
+use crate::accounts::{NodeSender, SignerFiller};
+use crate::rpc_log::{RecordingLayer, RpcLog};
 use crate::store::Config;
 
 /// Create provider and signer using configuration from the config file.
+///
+/// Requests go to the active endpoint of `endpoints` and move on to the next one when it
+/// keeps failing (see `Failover`). Nothing is dialed here: connections are opened by the
+/// first request, so the app starts while the node is down. Every request and response is
+/// recorded into `rpc_log`. Transactions from the `node_sender` address are left for the
+/// node to sign (see `SignerFiller`).
 pub fn create_provider(
     config: &Config,
+    endpoints: Endpoints,
+    rpc_log: RpcLog,
+    node_sender: NodeSender,
 ) -> Result<(impl Provider + Clone + 'static, PrivateKeySigner)> {
-    log::info!("Using RPC URL: {}", config.rpc_url);
+    log::info!("Using RPC URL: {}", endpoints.active().display);
 
     let private_key = config
         .private_key
@@ -20,9 +53,545 @@ pub fn create_provider(
 
     let wallet = EthereumWallet::from(signer.clone());
 
-    let url: url::Url = config.rpc_url.parse().context("Failed to parse RPC URL")?;
+    let is_local = endpoints.active().connection.is_local();
+    let failover = Failover::new(endpoints, RetryPolicy::from_config(config));
+    let client = ClientBuilder::default()
+        .layer(RecordingLayer::new(rpc_log))
+        .transport(failover, is_local);
 
-    let provider = ProviderBuilder::new().wallet(wallet).connect_http(url);
+    // The recommended fillers, except that the nonce is asked from the node for every
+    // transaction: a cached one goes stale once a raw transaction or another tool sends
+    // from the same account
+    let provider = ProviderBuilder::new()
+        .disable_recommended_fillers()
+        .with_gas_estimation()
+        .filler(BlobGasFiller::default())
+        .with_simple_nonce_management()
+        .fetch_chain_id()
+        .filler(SignerFiller::new(wallet, node_sender))
+        .connect_client(client);
 
     Ok((provider, signer))
 }
+
+/// Classify `rpc_url` by scheme; anything without an `http`/`ws` scheme is an IPC path.
+/// Unlike `BuiltInConnectionString::from_str` the socket does not have to exist yet.
+pub fn connection_string(rpc_url: &str) -> Result<BuiltInConnectionString> {
+    let rpc_url = rpc_url.trim();
+    let scheme = rpc_url
+        .split_once("://")
+        .map(|(scheme, _)| scheme.to_ascii_lowercase());
+    let parsed = match scheme.as_deref() {
+        Some("http" | "https") => BuiltInConnectionString::try_as_http(rpc_url),
+        Some("ws" | "wss") => BuiltInConnectionString::try_as_ws(rpc_url),
+        // `localhost:8545` and `127.0.0.1:8545` are HTTP, as with alloy's own parsing
+        None if rpc_url.starts_with("localhost:")
+            || rpc_url.parse::<std::net::SocketAddr>().is_ok() =>
+        {
+            BuiltInConnectionString::try_as_http(rpc_url)
+        }
+        Some("ipc" | "file") | None => {
+            let path = rpc_url
+                .strip_prefix("ipc://")
+                .or_else(|| rpc_url.strip_prefix("file://"))
+                .unwrap_or(rpc_url);
+            if path.is_empty() {
+                return Err(anyhow!("RPC URL is empty"));
+            }
+            return Ok(BuiltInConnectionString::Ipc(PathBuf::from(path)));
+        }
+        Some(other) => {
+            return Err(anyhow!(
+            "Unsupported RPC URL scheme '{other}' (expected http, https, ws, wss or an IPC path)"
+        ))
+        }
+    };
+    parsed.map_err(|e| anyhow!("Failed to parse RPC URL: {e}"))
+}
+
+/// An extra HTTP header sent to an endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub name: String,
+    /// Value as written in the config, `${VAR}` references included
+    pub template: String,
+    /// Value with the references filled in from the environment
+    pub value: String,
+}
+
+/// One RPC endpoint and what it takes to reach it
+#[derive(Debug, Clone)]
+pub struct Endpoint {
+    /// Credentials in an HTTP URL are moved to an `Authorization` header
+    pub connection: BuiltInConnectionString,
+    pub headers: Vec<Header>,
+    /// URL without credentials, for the connection card and logs
+    pub display: String,
+}
+
+impl Endpoint {
+    pub fn new(rpc_url: &str, config: &Config) -> Result<Self> {
+        let mut connection = connection_string(rpc_url)?;
+
+        // Headers for every endpoint first; the endpoint's own override them by name
+        let mut headers: Vec<Header> = Vec::new();
+        for key in ["*", rpc_url] {
+            for (name, template) in config.rpc_headers.get(key).into_iter().flatten() {
+                let value =
+                    expand_env(template).with_context(|| format!("Header {name} of {rpc_url}"))?;
+                headers.retain(|header| !header.name.eq_ignore_ascii_case(name));
+                headers.push(Header {
+                    name: name.clone(),
+                    template: template.clone(),
+                    value,
+                });
+            }
+        }
+        let has_authorization = headers
+            .iter()
+            .any(|header| header.name.eq_ignore_ascii_case("authorization"));
+
+        let display = match &mut connection {
+            BuiltInConnectionString::Http(url) => {
+                // reqwest does not send `user:pass@` from the URL, so send it as basic auth
+                if let Some(auth) = Authorization::extract_from_url(url) {
+                    if !has_authorization {
+                        headers.push(Header {
+                            name: "Authorization".to_string(),
+                            template: auth.to_string(),
+                            value: auth.to_string(),
+                        });
+                    }
+                }
+                strip_credentials(url)
+            }
+            // alloy already took the credentials for the WebSocket handshake
+            BuiltInConnectionString::Ws(url, _) => strip_credentials(url),
+            BuiltInConnectionString::Ipc(path) => path.display().to_string(),
+            _ => rpc_url.to_string(),
+        };
+
+        Ok(Self {
+            connection,
+            headers,
+            display,
+        })
+    }
+
+    /// Open a transport to this endpoint
+    pub async fn connect(&self) -> Result<BoxTransport, TransportError> {
+        match &self.connection {
+            BuiltInConnectionString::Http(url) => {
+                let mut header_map = HeaderMap::new();
+                for header in &self.headers {
+                    let name = HeaderName::from_bytes(header.name.as_bytes())
+                        .map_err(TransportErrorKind::custom)?;
+                    let mut value =
+                        HeaderValue::from_str(&header.value).map_err(TransportErrorKind::custom)?;
+                    value.set_sensitive(true);
+                    header_map.insert(name, value);
+                }
+                let client = reqwest::Client::builder()
+                    .default_headers(header_map)
+                    .build()
+                    .map_err(TransportErrorKind::custom)?;
+                Ok(Http::with_client(client, url.clone()).boxed())
+            }
+            BuiltInConnectionString::Ws(url, url_auth) => {
+                // The WebSocket handshake carries only the `Authorization` header
+                let mut auth = url_auth.clone();
+                for header in &self.headers {
+                    if header.name.eq_ignore_ascii_case("authorization") {
+                        auth = Some(Authorization::raw(header.value.clone()));
+                    } else {
+                        log::warn!("[RPC] Header {} is not sent over WebSocket", header.name);
+                    }
+                }
+                let connect = WsConnect::new(url.as_str()).with_auth_opt(auth);
+                Ok(connect.into_service().await?.boxed())
+            }
+            BuiltInConnectionString::Ipc(path) => {
+                Ok(IpcConnect::new(path.clone()).into_service().await?.boxed())
+            }
+            connection => connection.get_transport().await,
+        }
+    }
+}
+
+/// The URL without `user:pass@`
+fn strip_credentials(url: &mut url::Url) -> String {
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    url.to_string()
+}
+
+/// Replace `${VAR}` references with environment variables; a missing variable is an error
+fn expand_env(template: &str) -> Result<String> {
+    let mut expanded = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            bail!("unterminated ${{ in \"{template}\"");
+        };
+        let name = &rest[start + 2..start + end];
+        let value = std::env::var(name)
+            .with_context(|| format!("environment variable {name} is not set"))?;
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&value);
+        rest = &rest[start + end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// The configured endpoints, `rpc_url` first and then `fallback_rpc_urls`, and which one
+/// requests currently go to. Shared by the provider, the block watcher and the app.
+#[derive(Debug, Clone)]
+pub struct Endpoints {
+    endpoints: Arc<Vec<Endpoint>>,
+    active: Arc<AtomicUsize>,
+}
+
+impl Endpoints {
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let endpoints = std::iter::once(&config.rpc_url)
+            .chain(&config.fallback_rpc_urls)
+            .map(|url| Endpoint::new(url, config))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            endpoints: Arc::new(endpoints),
+            active: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    pub fn active(&self) -> &Endpoint {
+        &self.endpoints[self.active_index()]
+    }
+
+    /// 0 is the primary (`rpc_url`)
+    pub fn active_index(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    /// Send requests to the primary endpoint again
+    pub fn reset(&self) {
+        self.active.store(0, Ordering::Relaxed);
+    }
+
+    fn get(&self, index: usize) -> &Endpoint {
+        &self.endpoints[index]
+    }
+
+    fn set_active(&self, index: usize) {
+        self.active.store(index, Ordering::Relaxed);
+    }
+}
+
+/// Timeout and retries of a single request, from the config
+#[derive(Debug, Clone, Copy)]
+struct RetryPolicy {
+    timeout: Duration,
+    retries: u32,
+    backoff: Duration,
+}
+
+impl RetryPolicy {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            timeout: Duration::from_secs(config.rpc_timeout_secs.max(1)),
+            retries: config.rpc_retries,
+            backoff: Duration::from_millis(config.rpc_backoff_ms),
+        }
+    }
+
+    /// Pause before retry number `attempt` (from 1): the backoff, doubled for each retry
+    fn delay(&self, attempt: u32) -> Duration {
+        self.backoff
+            .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+    }
+}
+
+/// A transport to one endpoint that connects on the first request and again after the
+/// connection is gone for good (alloy retries a dropped socket a few times on its own first)
+#[derive(Clone)]
+struct LazyTransport {
+    endpoint: Endpoint,
+    transport: Arc<Mutex<Option<BoxTransport>>>,
+}
+
+impl LazyTransport {
+    fn new(endpoint: Endpoint) -> Self {
+        Self {
+            endpoint,
+            transport: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    async fn connected(&self) -> Result<BoxTransport, TransportError> {
+        let mut transport = self.transport.lock().await;
+        if let Some(transport) = transport.as_ref() {
+            return Ok(transport.clone());
+        }
+        let connected = self.endpoint.connect().await?;
+        log::info!(
+            "[RPC] Connected to {} over {}",
+            self.endpoint.display,
+            transport_name(&self.endpoint.connection)
+        );
+        *transport = Some(connected.clone());
+        Ok(connected)
+    }
+
+    async fn request(&self, request: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        let mut transport = self.connected().await?;
+        let response = transport.call(request).await;
+        if let Err(RpcError::Transport(TransportErrorKind::BackendGone)) = &response {
+            log::warn!(
+                "[RPC] Connection to {} lost, reconnecting on the next request",
+                self.endpoint.display
+            );
+            self.transport.lock().await.take();
+        }
+        response
+    }
+}
+
+/// Sends each request to the active endpoint, retrying transport failures with backoff,
+/// then moves on to the next endpoint and stays there once it answers. JSON-RPC errors
+/// (reverts, bad params) are answers and are not retried.
+#[derive(Clone)]
+struct Failover {
+    endpoints: Endpoints,
+    transports: Arc<Vec<LazyTransport>>,
+    policy: RetryPolicy,
+}
+
+impl Failover {
+    fn new(endpoints: Endpoints, policy: RetryPolicy) -> Self {
+        let transports = (0..endpoints.len())
+            .map(|index| LazyTransport::new(endpoints.get(index).clone()))
+            .collect();
+        Self {
+            endpoints,
+            transports: Arc::new(transports),
+            policy,
+        }
+    }
+
+    async fn request(self, request: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        let start = self.endpoints.active_index();
+        let count = self.transports.len();
+        let mut last_error = None;
+        for step in 0..count {
+            let index = (start + step) % count;
+            let transport = &self.transports[index];
+            for attempt in 0..=self.policy.retries {
+                if attempt > 0 {
+                    tokio::time::sleep(self.policy.delay(attempt)).await;
+                }
+                let response =
+                    tokio::time::timeout(self.policy.timeout, transport.request(request.clone()))
+                        .await
+                        .unwrap_or_else(|_| {
+                            Err(TransportErrorKind::custom_str(&format!(
+                                "request timed out after {}s",
+                                self.policy.timeout.as_secs()
+                            )))
+                        });
+                match response {
+                    Ok(response) => {
+                        if index != start {
+                            log::warn!(
+                                "[RPC] Switched to {} after {} failed",
+                                transport.endpoint.display,
+                                self.endpoints.get(start).display
+                            );
+                            self.endpoints.set_active(index);
+                        }
+                        return Ok(response);
+                    }
+                    Err(e) => {
+                        log::debug!(
+                            "[RPC] {} (attempt {}): {e}",
+                            transport.endpoint.display,
+                            attempt + 1
+                        );
+                        last_error = Some(e);
+                    }
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| TransportErrorKind::custom_str("no RPC endpoints")))
+    }
+}
+
+impl Service<RequestPacket> for Failover {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut std::task::Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        Box::pin(self.clone().request(request))
+    }
+}
+
+/// Short name of the transport, for logs and the connection card
+pub fn transport_name(connection: &BuiltInConnectionString) -> &'static str {
+    match connection {
+        BuiltInConnectionString::Http(_) => "HTTP",
+        BuiltInConnectionString::Ws(..) => "WebSocket",
+        BuiltInConnectionString::Ipc(_) => "IPC",
+        _ => "RPC",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_connection_string() {
+        let kind = |url: &str| transport_name(&connection_string(url).unwrap());
+        assert_eq!(kind("http://localhost:8545"), "HTTP");
+        assert_eq!(kind("HTTPS://rpc.example.org/v1"), "HTTP");
+        assert_eq!(kind("ws://127.0.0.1:8546"), "WebSocket");
+        assert_eq!(kind("wss://rpc.example.org/ws"), "WebSocket");
+        assert_eq!(kind("localhost:8545"), "HTTP");
+        assert_eq!(kind("/tmp/anvil.ipc"), "IPC");
+
+        // IPC sockets need not exist yet; the prefix is stripped
+        assert_eq!(
+            connection_string("ipc:///tmp/missing.ipc").unwrap(),
+            BuiltInConnectionString::Ipc(PathBuf::from("/tmp/missing.ipc"))
+        );
+
+        assert!(connection_string("ftp://example.org").is_err());
+        assert!(connection_string("").is_err());
+    }
+
+    #[test]
+    fn test_endpoint_headers() {
+        std::env::set_var("EVM_CLI_TEST_RPC_TOKEN", "s3cret");
+        let staging = "https://staging.example.org/rpc";
+        let config = Config {
+            rpc_url: staging.to_string(),
+            fallback_rpc_urls: vec!["https://alice:pw@backup.example.org/".to_string()],
+            rpc_headers: BTreeMap::from([
+                (
+                    "*".to_string(),
+                    BTreeMap::from([("X-Client".to_string(), "evm-cli".to_string())]),
+                ),
+                (
+                    staging.to_string(),
+                    BTreeMap::from([(
+                        "Authorization".to_string(),
+                        "Bearer ${EVM_CLI_TEST_RPC_TOKEN}".to_string(),
+                    )]),
+                ),
+            ]),
+            ..Config::default()
+        };
+
+        let endpoints = Endpoints::from_config(&config).unwrap();
+        assert_eq!(endpoints.len(), 2);
+        let primary = endpoints.active();
+        assert_eq!(primary.display, staging);
+        let names: Vec<_> = primary.headers.iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, ["X-Client", "Authorization"]);
+        assert_eq!(primary.headers[1].value, "Bearer s3cret");
+        assert_eq!(
+            primary.headers[1].template,
+            "Bearer ${EVM_CLI_TEST_RPC_TOKEN}"
+        );
+
+        // Basic auth moves from the URL into a header
+        let backup = endpoints.get(1);
+        assert_eq!(backup.display, "https://backup.example.org/");
+        let auth = backup
+            .headers
+            .iter()
+            .find(|h| h.name == "Authorization")
+            .unwrap();
+        assert_eq!(auth.value, Authorization::basic("alice", "pw").to_string());
+
+        let missing = Config {
+            rpc_headers: BTreeMap::from([(
+                "*".to_string(),
+                BTreeMap::from([("X-Key".to_string(), "${EVM_CLI_TEST_UNSET}".to_string())]),
+            )]),
+            ..Config::default()
+        };
+        let error = Endpoints::from_config(&missing).unwrap_err();
+        assert!(format!("{error:#}").contains("EVM_CLI_TEST_UNSET is not set"));
+    }
+
+    /// Serve one JSON-RPC answer over HTTP on a local port, returning its URL
+    fn one_shot_node(result: &'static str) -> String {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 4096];
+            let _ = stream.read(&mut request).unwrap();
+            let body = format!(r#"{{"jsonrpc":"2.0","id":0,"result":"{result}"}}"#);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_failover() {
+        // Nothing listens on the primary; the fallback answers
+        let dead = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let primary = format!("http://{}", dead.local_addr().unwrap());
+        drop(dead);
+        let config = Config {
+            rpc_url: primary,
+            fallback_rpc_urls: vec![one_shot_node("0x7a69")],
+            rpc_retries: 1,
+            rpc_backoff_ms: 1,
+            ..Config::default()
+        };
+        let endpoints = Endpoints::from_config(&config).unwrap();
+        let (provider, _) = create_provider(
+            &config,
+            endpoints.clone(),
+            RpcLog::default(),
+            NodeSender::default(),
+        )
+        .unwrap();
+
+        let chain_id = provider.get_chain_id().await.unwrap();
+        assert_eq!(chain_id, 31337);
+        assert_eq!(endpoints.active_index(), 1);
+
+        endpoints.reset();
+        assert_eq!(endpoints.active_index(), 0);
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = RetryPolicy {
+            timeout: Duration::from_secs(1),
+            retries: 3,
+            backoff: Duration::from_millis(250),
+        };
+        assert_eq!(policy.delay(1), Duration::from_millis(250));
+        assert_eq!(policy.delay(2), Duration::from_millis(500));
+        assert_eq!(policy.delay(3), Duration::from_millis(1000));
+    }
+}
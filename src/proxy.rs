@@ -0,0 +1,514 @@
+use alloy::dyn_abi::{DynSolValue, FunctionExt, JsonAbiExt};
+use alloy::json_abi::{Event, Function, JsonAbi};
+use alloy::primitives::{b256, Address, Bytes, B256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::compile::{self, BytecodeTarget, CompilerSettings};
+
+/// `keccak256("eip1967.proxy.implementation") - 1`
+pub const IMPLEMENTATION_SLOT: B256 =
+    b256!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+/// `keccak256("eip1967.proxy.admin") - 1`
+pub const ADMIN_SLOT: B256 =
+    b256!("b53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103");
+/// `keccak256("eip1967.proxy.beacon") - 1`
+pub const BEACON_SLOT: B256 =
+    b256!("a3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50");
+
+/// Name of the proxy admin contract in `PROXY_SOURCE`
+pub const ADMIN_CONTRACT: &str = "EvmCliProxyAdmin";
+
+/// Minimal ERC-1967 proxies deployed by "Deploy behind proxy". Transparent proxies are
+/// administered by a separate `EvmCliProxyAdmin` so the deployer can still call the
+/// implementation through the proxy.
+const PROXY_SOURCE: &str = r#"// SPDX-License-Identifier: MIT
+// Generated by evm-cli, do not edit
+pragma solidity ^0.8.0;
+
+interface IEvmCliUpgradeable {
+    function upgradeToAndCall(address newImplementation, bytes calldata data) external payable;
+}
+
+abstract contract EvmCliERC1967Base {
+    bytes32 internal constant IMPLEMENTATION_SLOT =
+        0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc;
+    bytes32 internal constant ADMIN_SLOT =
+        0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103;
+
+    event Upgraded(address indexed implementation);
+    event AdminChanged(address previousAdmin, address newAdmin);
+
+    function _upgradeToAndCall(address implementation, bytes memory data) internal {
+        require(implementation.code.length > 0, "ERC1967: implementation is not a contract");
+        assembly {
+            sstore(IMPLEMENTATION_SLOT, implementation)
+        }
+        emit Upgraded(implementation);
+        if (data.length > 0) {
+            (bool ok, bytes memory ret) = implementation.delegatecall(data);
+            if (!ok) {
+                assembly {
+                    revert(add(ret, 32), mload(ret))
+                }
+            }
+        }
+    }
+
+    function _delegate() internal {
+        assembly {
+            let implementation := sload(IMPLEMENTATION_SLOT)
+            calldatacopy(0, 0, calldatasize())
+            let ok := delegatecall(gas(), implementation, 0, calldatasize(), 0, 0)
+            returndatacopy(0, 0, returndatasize())
+            switch ok
+            case 0 { revert(0, returndatasize()) }
+            default { return(0, returndatasize()) }
+        }
+    }
+}
+
+/// UUPS proxy: upgrades go through the implementation's `upgradeToAndCall`
+contract EvmCliERC1967Proxy is EvmCliERC1967Base {
+    constructor(address implementation, bytes memory data) payable {
+        _upgradeToAndCall(implementation, data);
+    }
+
+    fallback() external payable {
+        _delegate();
+    }
+
+    receive() external payable {
+        _delegate();
+    }
+}
+
+/// Transparent proxy: the admin may only upgrade, everyone else is forwarded
+contract EvmCliTransparentProxy is EvmCliERC1967Base {
+    constructor(address implementation, address admin, bytes memory data) payable {
+        assembly {
+            sstore(ADMIN_SLOT, admin)
+        }
+        emit AdminChanged(address(0), admin);
+        _upgradeToAndCall(implementation, data);
+    }
+
+    fallback() external payable {
+        _fallback();
+    }
+
+    receive() external payable {
+        _fallback();
+    }
+
+    function _fallback() internal {
+        address admin;
+        assembly {
+            admin := sload(ADMIN_SLOT)
+        }
+        if (msg.sender != admin) {
+            _delegate();
+            return;
+        }
+        require(
+            msg.sig == IEvmCliUpgradeable.upgradeToAndCall.selector,
+            "TransparentProxy: admin cannot fallback to proxy target"
+        );
+        (address implementation, bytes memory data) = abi.decode(msg.data[4:], (address, bytes));
+        _upgradeToAndCall(implementation, data);
+    }
+}
+
+contract EvmCliProxyAdmin {
+    address public owner;
+
+    constructor(address initialOwner) {
+        owner = initialOwner;
+    }
+
+    function upgradeAndCall(address proxy, address implementation, bytes calldata data) external payable {
+        require(msg.sender == owner, "ProxyAdmin: caller is not the owner");
+        IEvmCliUpgradeable(proxy).upgradeToAndCall{value: msg.value}(implementation, data);
+    }
+}
+"#;
+
+/// Proxy flavour, decided by who performs upgrades
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProxyKind {
+    /// The implementation upgrades itself (`upgradeToAndCall` on the proxy address)
+    Uups,
+    /// An admin (usually a ProxyAdmin contract) upgrades through the proxy
+    Transparent,
+    /// The implementation is read from a beacon contract
+    Beacon,
+}
+
+impl ProxyKind {
+    /// Kinds "Deploy behind proxy" can deploy
+    pub const DEPLOYABLE: [ProxyKind; 2] = [ProxyKind::Uups, ProxyKind::Transparent];
+
+    /// Contract in `PROXY_SOURCE` deployed for this kind
+    pub fn proxy_contract(&self) -> Option<&'static str> {
+        match self {
+            ProxyKind::Uups => Some("EvmCliERC1967Proxy"),
+            ProxyKind::Transparent => Some("EvmCliTransparentProxy"),
+            ProxyKind::Beacon => None,
+        }
+    }
+}
+
+impl fmt::Display for ProxyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProxyKind::Uups => write!(f, "UUPS"),
+            ProxyKind::Transparent => write!(f, "Transparent"),
+            ProxyKind::Beacon => write!(f, "Beacon"),
+        }
+    }
+}
+
+/// What the ERC-1967 slots of a proxy point at
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProxyInfo {
+    pub kind: ProxyKind,
+    pub implementation: Address,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beacon: Option<Address>,
+}
+
+impl fmt::Display for ProxyInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} proxy, implementation {:?}",
+            self.kind, self.implementation
+        )?;
+        if let Some(admin) = self.admin {
+            write!(f, ", admin {admin:?}")?;
+        }
+        if let Some(beacon) = self.beacon {
+            write!(f, ", beacon {beacon:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Address stored in a slot, if any
+fn slot_address(word: B256) -> Option<Address> {
+    let address = Address::from_word(word);
+    (!address.is_zero()).then_some(address)
+}
+
+/// Classify a proxy from its raw ERC-1967 slots. A set admin slot means a transparent
+/// proxy; an implementation without admin is assumed to be UUPS.
+fn classify(implementation: B256, admin: B256) -> Option<ProxyInfo> {
+    let implementation = slot_address(implementation)?;
+    let admin = slot_address(admin);
+    Some(ProxyInfo {
+        kind: if admin.is_some() {
+            ProxyKind::Transparent
+        } else {
+            ProxyKind::Uups
+        },
+        implementation,
+        admin,
+        beacon: None,
+    })
+}
+
+/// Read the ERC-1967 slots at `address`. Returns None for anything that isn't a proxy.
+pub async fn detect<P: Provider>(provider: &P, address: Address) -> Result<Option<ProxyInfo>> {
+    let read = |slot: B256| async move {
+        provider
+            .get_storage_at(address, slot.into())
+            .await
+            .map(B256::from)
+            .with_context(|| format!("Failed to read storage of {address:?}"))
+    };
+
+    let implementation = read(IMPLEMENTATION_SLOT).await?;
+    let admin = read(ADMIN_SLOT).await?;
+    if let Some(info) = classify(implementation, admin) {
+        return Ok(Some(info));
+    }
+
+    let Some(beacon) = slot_address(read(BEACON_SLOT).await?) else {
+        return Ok(None);
+    };
+    let implementation_fn = Function::parse("function implementation() view returns (address)")
+        .expect("valid signature");
+    let call = TransactionRequest::default()
+        .to(beacon)
+        .input(implementation_fn.abi_encode_input(&[])?.into());
+    let output = provider
+        .call(call)
+        .await
+        .with_context(|| format!("Failed to query beacon {beacon:?}"))?;
+    let implementation = match implementation_fn.abi_decode_output(&output)?.first() {
+        Some(DynSolValue::Address(implementation)) => *implementation,
+        _ => bail!("Beacon {beacon:?} returned no implementation"),
+    };
+    Ok(Some(ProxyInfo {
+        kind: ProxyKind::Beacon,
+        implementation,
+        admin: None,
+        beacon: Some(beacon),
+    }))
+}
+
+/// Proxy sources directory (~/.evm-cli/proxies)
+pub fn default_proxies_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".evm-cli")
+        .join("proxies")
+}
+
+/// Write `PROXY_SOURCE` to `dir`, leaving an identical file untouched so builds stay cached
+fn write_source(dir: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+    let path = dir.join("EvmCliProxies.sol");
+    if fs::read_to_string(&path).ok().as_deref() != Some(PROXY_SOURCE) {
+        fs::write(&path, PROXY_SOURCE)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(path)
+}
+
+/// Creation code of a proxy and, for transparent proxies, of its admin
+#[derive(Debug, Clone)]
+pub struct ProxyCode {
+    pub proxy: Vec<u8>,
+    pub admin: Option<Vec<u8>>,
+}
+
+/// Compile the proxy contracts needed for `kind` (blocking, goes through the compile cache)
+pub fn compile_proxy(kind: ProxyKind, target: BytecodeTarget) -> Result<ProxyCode> {
+    let Some(name) = kind.proxy_contract() else {
+        bail!("{kind} proxies cannot be deployed from evm-cli");
+    };
+    let path = write_source(&default_proxies_dir())?;
+    let settings = CompilerSettings::default();
+    let proxy = compile::compile_contract(&path, name, target, &settings)?.bytecode;
+    let admin = match kind {
+        ProxyKind::Transparent => {
+            Some(compile::compile_contract(&path, ADMIN_CONTRACT, target, &settings)?.bytecode)
+        }
+        _ => None,
+    };
+    Ok(ProxyCode { proxy, admin })
+}
+
+/// Creation code plus constructor arguments for a proxy pointing at `implementation`
+pub fn proxy_deploy_data(
+    code: &[u8],
+    kind: ProxyKind,
+    implementation: Address,
+    admin: Option<Address>,
+    init_data: &[u8],
+) -> Vec<u8> {
+    let mut args = vec![DynSolValue::Address(implementation)];
+    if kind == ProxyKind::Transparent {
+        args.push(DynSolValue::Address(admin.unwrap_or_default()));
+    }
+    args.push(DynSolValue::Bytes(init_data.to_vec()));
+
+    let mut data = code.to_vec();
+    data.extend(DynSolValue::Tuple(args).abi_encode_params());
+    data
+}
+
+/// Creation code plus constructor arguments for a proxy admin owned by `owner`
+pub fn admin_deploy_data(code: &[u8], owner: Address) -> Vec<u8> {
+    let mut data = code.to_vec();
+    data.extend(DynSolValue::Tuple(vec![DynSolValue::Address(owner)]).abi_encode_params());
+    data
+}
+
+/// Function and target of the call that points `proxy` at `new_implementation`, then has
+/// it run `data` (e.g. a reinitializer; empty for none)
+pub fn upgrade_call(
+    proxy: Address,
+    info: &ProxyInfo,
+    new_implementation: Address,
+    data: &[u8],
+) -> Result<(Function, Address, Vec<DynSolValue>)> {
+    let data = DynSolValue::Bytes(data.to_vec());
+    match info.kind {
+        ProxyKind::Uups => Ok((
+            Function::parse(
+                "function upgradeToAndCall(address newImplementation, bytes data) payable",
+            )?,
+            proxy,
+            vec![DynSolValue::Address(new_implementation), data],
+        )),
+        ProxyKind::Transparent => {
+            let Some(admin) = info.admin else {
+                bail!("Proxy {proxy:?} has no admin");
+            };
+            Ok((
+                Function::parse(
+                    "function upgradeAndCall(address proxy, address implementation, bytes data) payable",
+                )?,
+                admin,
+                vec![
+                    DynSolValue::Address(proxy),
+                    DynSolValue::Address(new_implementation),
+                    data,
+                ],
+            ))
+        }
+        ProxyKind::Beacon => bail!("Beacon proxies are upgraded through their beacon"),
+    }
+}
+
+/// Calldata for `initialize(...)` when the implementation has one, empty otherwise
+pub fn initializer_data(abi: &JsonAbi, args: &[DynSolValue]) -> Result<Bytes> {
+    match initializer(abi) {
+        Some(function) => Ok(function.abi_encode_input(args)?.into()),
+        None => Ok(Bytes::new()),
+    }
+}
+
+/// The implementation's `initialize` function, run through the proxy at deployment
+pub fn initializer(abi: &JsonAbi) -> Option<&Function> {
+    abi.function("initialize")
+        .and_then(|overloads| overloads.first())
+}
+
+/// ERC-1967 events emitted by the proxy itself, for decoding logs at a proxy address
+pub fn proxy_events() -> Vec<Event> {
+    [
+        "event Upgraded(address indexed implementation)",
+        "event AdminChanged(address previousAdmin, address newAdmin)",
+        "event BeaconUpgraded(address indexed beacon)",
+    ]
+    .iter()
+    .map(|signature| Event::parse(signature).expect("valid event signature"))
+    .collect()
+}
+
+/// Add the proxy events to an implementation ABI
+pub fn with_proxy_events(abi: &JsonAbi) -> JsonAbi {
+    let mut abi = abi.clone();
+    for event in proxy_events() {
+        let overloads = abi.events.entry(event.name.clone()).or_default();
+        if !overloads.iter().any(|e| e.selector() == event.selector()) {
+            overloads.push(event);
+        }
+    }
+    abi
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{keccak256, U256};
+    use tempfile::TempDir;
+
+    /// `keccak256(label) - 1`, how ERC-1967 derives its slots
+    fn eip1967_slot(label: &str) -> B256 {
+        B256::from(U256::from_be_bytes(keccak256(label).0) - U256::from(1))
+    }
+
+    #[test]
+    fn test_slots() {
+        assert_eq!(
+            IMPLEMENTATION_SLOT,
+            eip1967_slot("eip1967.proxy.implementation")
+        );
+        assert_eq!(ADMIN_SLOT, eip1967_slot("eip1967.proxy.admin"));
+        assert_eq!(BEACON_SLOT, eip1967_slot("eip1967.proxy.beacon"));
+        // The embedded source hardcodes the same slots
+        assert!(PROXY_SOURCE.contains(&IMPLEMENTATION_SLOT.to_string()));
+        assert!(PROXY_SOURCE.contains(&ADMIN_SLOT.to_string()));
+    }
+
+    #[test]
+    fn test_classify() {
+        let implementation = Address::repeat_byte(0x11);
+        let admin = Address::repeat_byte(0x22);
+        assert_eq!(classify(B256::ZERO, B256::ZERO), None);
+
+        let uups = classify(implementation.into_word(), B256::ZERO).unwrap();
+        assert_eq!(uups.kind, ProxyKind::Uups);
+        assert_eq!(uups.implementation, implementation);
+
+        let transparent = classify(implementation.into_word(), admin.into_word()).unwrap();
+        assert_eq!(transparent.kind, ProxyKind::Transparent);
+        assert_eq!(transparent.admin, Some(admin));
+    }
+
+    #[test]
+    fn test_upgrade_call() {
+        let proxy = Address::repeat_byte(0x01);
+        let new_implementation = Address::repeat_byte(0x02);
+        let admin = Address::repeat_byte(0x03);
+
+        let uups = ProxyInfo {
+            kind: ProxyKind::Uups,
+            implementation: Address::ZERO,
+            admin: None,
+            beacon: None,
+        };
+        let (function, to, args) = upgrade_call(proxy, &uups, new_implementation, &[]).unwrap();
+        assert_eq!(to, proxy);
+        assert_eq!(function.selector().to_string(), "0x4f1ef286");
+        assert_eq!(args.len(), 2);
+        assert_eq!(args[1], DynSolValue::Bytes(Vec::new()));
+
+        let transparent = ProxyInfo {
+            kind: ProxyKind::Transparent,
+            admin: Some(admin),
+            ..uups.clone()
+        };
+        let reinitialize = [0x6f, 0xb1, 0x31, 0x4c];
+        let (function, to, args) =
+            upgrade_call(proxy, &transparent, new_implementation, &reinitialize).unwrap();
+        assert_eq!(to, admin);
+        assert_eq!(function.selector().to_string(), "0x9623609d");
+        assert_eq!(args[2], DynSolValue::Bytes(reinitialize.to_vec()));
+    }
+
+    #[test]
+    fn test_deploy_data_and_source() {
+        let implementation = Address::repeat_byte(0xaa);
+        let uups = proxy_deploy_data(&[0xfe], ProxyKind::Uups, implementation, None, &[]);
+        // code + (address, offset, length) with an empty bytes tail
+        assert_eq!(uups.len(), 1 + 3 * 32);
+        assert_eq!(&uups[13..33], implementation.as_slice());
+
+        let transparent = proxy_deploy_data(
+            &[0xfe],
+            ProxyKind::Transparent,
+            implementation,
+            Some(Address::repeat_byte(0xbb)),
+            &[1, 2, 3, 4],
+        );
+        assert_eq!(transparent.len(), 1 + 5 * 32);
+
+        let dir = TempDir::new().unwrap();
+        let path = write_source(dir.path()).unwrap();
+        let modified = fs::metadata(&path).unwrap().modified().unwrap();
+        assert_eq!(write_source(dir.path()).unwrap(), path);
+        assert_eq!(fs::metadata(&path).unwrap().modified().unwrap(), modified);
+    }
+
+    #[test]
+    fn test_with_proxy_events() {
+        let abi = with_proxy_events(&JsonAbi::new());
+        assert!(abi.event("Upgraded").is_some());
+        // Adding them twice does not duplicate
+        assert_eq!(with_proxy_events(&abi).events().count(), 3);
+    }
+}
@@ -0,0 +1,328 @@
+use alloy::consensus::transaction::SignerRecoverable;
+use alloy::consensus::{Transaction, TxEnvelope};
+use alloy::eips::{Decodable2718, Encodable2718};
+use alloy::network::EthereumWallet;
+use alloy::primitives::{Address, Bytes, TxHash, TxKind, U256};
+use alloy::providers::{Provider, ProviderBuilder, SendableTx};
+use alloy::rpc::types::TransactionRequest;
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Starting point of the "Broadcast raw tx" editor
+pub const TEMPLATE: &str = "\
+# Paste a signed raw transaction (0x-prefixed hex, as written by \"Sign only\"
+# or `cast mktx`). Save and quit to broadcast it; leave it empty to cancel.
+
+";
+
+/// Hex characters of the raw transaction shown on a card; the rest is elided
+const SHOWN_HEX: usize = 64;
+
+/// A signed transaction, ready for `eth_sendRawTransaction`
+#[derive(Debug, Clone)]
+pub struct SignedTx {
+    /// EIP-2718 encoding: RLP, prefixed with the type byte for typed transactions
+    pub raw: Bytes,
+    pub hash: TxHash,
+    /// Recovered from the signature
+    pub from: Address,
+    pub envelope: TxEnvelope,
+}
+
+impl SignedTx {
+    pub fn from_envelope(envelope: TxEnvelope) -> Result<Self> {
+        let from = envelope
+            .recover_signer()
+            .map_err(|e| anyhow!("Invalid signature: {e}"))?;
+        Ok(Self {
+            raw: envelope.encoded_2718().into(),
+            hash: *envelope.tx_hash(),
+            from,
+            envelope,
+        })
+    }
+
+    /// Decode and check the signature of a raw transaction
+    pub fn decode(raw: &[u8]) -> Result<Self> {
+        let mut buf = raw;
+        let envelope =
+            TxEnvelope::decode_2718(&mut buf).context("Not a valid signed transaction")?;
+        if !buf.is_empty() {
+            bail!("{} unexpected bytes after the transaction", buf.len());
+        }
+        Self::from_envelope(envelope)
+    }
+
+    pub fn raw_hex(&self) -> String {
+        format!("0x{}", hex::encode(&self.raw))
+    }
+
+    /// Called contract or recipient, None for a contract creation
+    pub fn to(&self) -> Option<Address> {
+        match self.envelope.kind() {
+            TxKind::Call(to) => Some(to),
+            TxKind::Create => None,
+        }
+    }
+
+    /// Address of the contract a creation deploys
+    pub fn created_address(&self) -> Option<Address> {
+        self.to()
+            .is_none()
+            .then(|| self.from.create(self.envelope.nonce()))
+    }
+
+    pub fn value(&self) -> U256 {
+        self.envelope.value()
+    }
+
+    pub fn input(&self) -> &Bytes {
+        self.envelope.input()
+    }
+
+    /// None for legacy transactions signed without replay protection
+    pub fn chain_id(&self) -> Option<u64> {
+        self.envelope.chain_id()
+    }
+
+    /// The transaction as a request, for simulating it to find a revert reason
+    pub fn request(&self) -> TransactionRequest {
+        let mut request =
+            TransactionRequest::from_transaction_with_sender(self.envelope.clone(), self.from);
+        // Replayed with `eth_call`, where the signed fees only get in the way
+        request.max_fee_per_gas = None;
+        request.max_priority_fee_per_gas = None;
+        request.gas_price = None;
+        request
+    }
+
+    /// Write the raw hex to `<dir>/<hash>.txt`, for moving it to another machine
+    pub fn save(&self, dir: &Path) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let path = dir.join(format!("{:?}.txt", self.hash));
+        std::fs::write(&path, format!("{}\n", self.raw_hex()))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+impl fmt::Display for SignedTx {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Hash: {:?}", self.hash)?;
+        writeln!(f, "From: {:?}", self.from)?;
+        match (self.to(), self.created_address()) {
+            (Some(to), _) => writeln!(f, "To: {to:?}")?,
+            (None, Some(created)) => writeln!(f, "Creates: {created:?}")?,
+            (None, None) => {}
+        }
+        let chain = self
+            .chain_id()
+            .map(|chain_id| chain_id.to_string())
+            .unwrap_or_else(|| "any (pre-EIP-155)".to_string());
+        writeln!(
+            f,
+            "Nonce: {}, gas limit: {}, chain: {chain}",
+            self.envelope.nonce(),
+            self.envelope.gas_limit()
+        )?;
+        let hex = self.raw_hex();
+        if hex.len() > SHOWN_HEX {
+            write!(
+                f,
+                "Raw: {}... ({} bytes)",
+                &hex[..SHOWN_HEX],
+                self.raw.len()
+            )
+        } else {
+            write!(f, "Raw: {hex}")
+        }
+    }
+}
+
+/// Nonces used by `sign`, per chain and account. The node only knows about broadcast
+/// transactions, so without these every transaction signed before the previous one is
+/// sent would get the same nonce.
+#[derive(Debug, Default)]
+pub struct SignedNonces(HashMap<(Option<u64>, Address), u64>);
+
+impl SignedNonces {
+    /// Next nonce to sign with: the node's pending one, or past the last one signed here
+    pub fn next(&self, chain_id: Option<u64>, from: Address, pending: u64) -> u64 {
+        self.0
+            .get(&(chain_id, from))
+            .map_or(pending, |last| pending.max(last + 1))
+    }
+
+    fn record(&mut self, signed: &SignedTx) {
+        let nonce = signed.envelope.nonce();
+        let last = self
+            .0
+            .entry((signed.chain_id(), signed.from))
+            .or_insert(nonce);
+        *last = nonce.max(*last);
+    }
+}
+
+/// Fill in nonce, gas, fees and chain id from the node and sign `tx` with `wallet`,
+/// without sending it. Without a nonce in `tx`, the next one of `nonces` is used, so
+/// transactions signed one after another get consecutive nonces.
+pub async fn sign<P: Provider + Clone>(
+    provider: &P,
+    wallet: EthereumWallet,
+    mut tx: TransactionRequest,
+    nonces: &mut SignedNonces,
+) -> Result<SignedTx> {
+    if tx.nonce.is_none() {
+        let from = tx.from.context("No sender to sign for")?;
+        let pending = provider.get_transaction_count(from).pending().await?;
+        tx.nonce = Some(nonces.next(tx.chain_id, from, pending));
+    }
+    let signer = ProviderBuilder::new()
+        .wallet(wallet)
+        .connect_provider(provider.clone());
+    let signed = match signer.fill(tx).await? {
+        SendableTx::Envelope(envelope) => SignedTx::from_envelope(envelope)?,
+        SendableTx::Builder(_) => bail!("The transaction could not be completed for signing"),
+    };
+    nonces.record(&signed);
+    Ok(signed)
+}
+
+/// Parse the editor contents. `Ok(None)` means nothing was pasted.
+pub fn parse(text: &str) -> Result<Option<SignedTx>> {
+    let hex: String = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .flat_map(|line| line.split_whitespace())
+        .collect();
+    if hex.is_empty() {
+        return Ok(None);
+    }
+    let raw = hex::decode(hex.strip_prefix("0x").unwrap_or(&hex))
+        .context("The raw transaction is not valid hex")?;
+    SignedTx::decode(&raw).map(Some)
+}
+
+/// Where "Sign only" saves raw transactions (~/.evm-cli/signed)
+pub fn default_signed_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".evm-cli")
+        .join("signed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::mock_provider;
+    use alloy::consensus::{SignableTransaction, TxEip1559};
+    use alloy::network::{TransactionBuilder, TxSignerSync};
+    use alloy::signers::local::PrivateKeySigner;
+
+    fn signed_transfer(signer: &PrivateKeySigner) -> TxEnvelope {
+        let mut tx = TxEip1559 {
+            chain_id: 31337,
+            nonce: 7,
+            gas_limit: 21_000,
+            max_fee_per_gas: 2_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            to: TxKind::Call(Address::repeat_byte(0x22)),
+            value: U256::from(1),
+            ..Default::default()
+        };
+        let signature = signer.sign_transaction_sync(&mut tx).unwrap();
+        tx.into_signed(signature).into()
+    }
+
+    #[test]
+    fn test_decode_round_trip() {
+        let signer = PrivateKeySigner::random();
+        let envelope = signed_transfer(&signer);
+        let raw = envelope.encoded_2718();
+
+        let text = format!("{TEMPLATE}\n  0x{}\n", hex::encode(&raw));
+        let signed = parse(&text).unwrap().unwrap();
+        assert_eq!(signed.from, signer.address());
+        assert_eq!(signed.hash, *envelope.tx_hash());
+        assert_eq!(signed.to(), Some(Address::repeat_byte(0x22)));
+        assert_eq!(signed.created_address(), None);
+        assert_eq!(signed.value(), U256::from(1));
+        assert_eq!(signed.raw.as_ref(), raw.as_slice());
+
+        let request = signed.request();
+        assert_eq!(request.from, Some(signer.address()));
+        assert_eq!(request.nonce, Some(7));
+        assert_eq!(request.max_fee_per_gas, None);
+
+        let card = signed.to_string();
+        assert!(card.contains("Nonce: 7, gas limit: 21000, chain: 31337"));
+        assert!(card.contains(&format!("({} bytes)", raw.len())));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse(TEMPLATE).unwrap().is_none());
+        assert!(parse("0xzz").is_err());
+        assert!(parse("0x02c0").is_err());
+
+        // Trailing garbage after a valid transaction
+        let signer = PrivateKeySigner::random();
+        let mut raw = signed_transfer(&signer).encoded_2718();
+        raw.push(0);
+        assert!(parse(&hex::encode(raw)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sign_without_sending() {
+        let signer = PrivateKeySigner::random();
+        // Every field is given, so signing needs nothing from the node (the mock has no
+        // responses queued and would fail any request)
+        let (provider, _asserter) = mock_provider();
+        let tx = TransactionRequest::default()
+            .from(signer.address())
+            .to(Address::repeat_byte(0x22))
+            .nonce(3)
+            .gas_limit(21_000)
+            .max_fee_per_gas(2_000_000_000)
+            .max_priority_fee_per_gas(1_000_000_000)
+            .with_chain_id(31337);
+
+        let wallet = EthereumWallet::from(signer.clone());
+        let signed = sign(&provider, wallet, tx, &mut SignedNonces::default())
+            .await
+            .unwrap();
+        assert_eq!(signed.from, signer.address());
+        assert_eq!(signed.chain_id(), Some(31337));
+        assert_eq!(SignedTx::decode(&signed.raw).unwrap().hash, signed.hash);
+    }
+
+    #[tokio::test]
+    async fn test_signed_nonces() {
+        let signer = PrivateKeySigner::random();
+        let (provider, asserter) = mock_provider();
+        let tx = TransactionRequest::default()
+            .from(signer.address())
+            .to(Address::repeat_byte(0x22))
+            .gas_limit(21_000)
+            .max_fee_per_gas(2_000_000_000)
+            .max_priority_fee_per_gas(1_000_000_000)
+            .with_chain_id(31337);
+
+        let mut nonces = SignedNonces::default();
+        // The node's pending nonce, and the nonce signed with. Nothing is broadcast in
+        // between, so the second follows the first; once the node is ahead, it wins.
+        for (pending, expected) in [(5, 5), (5, 6), (9, 9)] {
+            asserter.push_success(&format!("{pending:#x}"));
+            let wallet = EthereumWallet::from(signer.clone());
+            let signed = sign(&provider, wallet, tx.clone(), &mut nonces)
+                .await
+                .unwrap();
+            assert_eq!(signed.envelope.nonce(), expected);
+        }
+        assert_eq!(nonces.next(Some(1), signer.address(), 0), 0);
+    }
+}
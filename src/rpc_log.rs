@@ -0,0 +1,313 @@
+use alloy::rpc::client::BuiltInConnectionString;
+use alloy::rpc::json_rpc::{RequestPacket, ResponsePacket};
+use alloy::transports::{TransportError, TransportFut};
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::{Layer, Service};
+
+use crate::provider::Endpoint;
+
+/// Exchanges kept for the inspector; older ones are dropped
+const CAPACITY: usize = 500;
+
+/// One JSON-RPC request (or batch) and what came back
+#[derive(Debug, Clone)]
+pub struct RpcExchange {
+    pub id: u64,
+    /// Method name, or the methods of a batch joined with commas
+    pub method: String,
+    /// Request body as sent
+    pub request: String,
+    /// Response body, once received
+    pub response: Option<String>,
+    /// Transport failure or JSON-RPC error message
+    pub error: Option<String>,
+    pub started_at: DateTime<Local>,
+    /// None while the request is in flight
+    pub duration: Option<Duration>,
+}
+
+impl RpcExchange {
+    pub fn is_pending(&self) -> bool {
+        self.duration.is_none()
+    }
+}
+
+#[derive(Debug, Default)]
+struct Exchanges {
+    entries: VecDeque<RpcExchange>,
+    next_id: u64,
+}
+
+/// Shared record of the provider's JSON-RPC traffic, filled by `RecordingLayer`
+#[derive(Debug, Clone, Default)]
+pub struct RpcLog {
+    exchanges: Arc<Mutex<Exchanges>>,
+}
+
+impl RpcLog {
+    fn begin(&self, method: String, request: String) -> u64 {
+        let mut exchanges = self.exchanges.lock().expect("rpc log lock");
+        let id = exchanges.next_id;
+        exchanges.next_id += 1;
+        if exchanges.entries.len() == CAPACITY {
+            exchanges.entries.pop_front();
+        }
+        exchanges.entries.push_back(RpcExchange {
+            id,
+            method,
+            request,
+            response: None,
+            error: None,
+            started_at: Local::now(),
+            duration: None,
+        });
+        id
+    }
+
+    fn finish(&self, id: u64, result: &Result<ResponsePacket, TransportError>, took: Duration) {
+        let mut exchanges = self.exchanges.lock().expect("rpc log lock");
+        let Some(exchange) = exchanges.entries.iter_mut().find(|e| e.id == id) else {
+            return;
+        };
+        exchange.duration = Some(took);
+        match result {
+            Ok(response) => {
+                let body = match response {
+                    ResponsePacket::Single(single) => serde_json::to_string(single),
+                    ResponsePacket::Batch(batch) => serde_json::to_string(batch),
+                };
+                exchange.response = body.ok();
+                let errors: Vec<String> = response.iter_errors().map(|e| e.to_string()).collect();
+                if !errors.is_empty() {
+                    exchange.error = Some(errors.join("; "));
+                }
+            }
+            Err(e) => exchange.error = Some(e.to_string()),
+        }
+    }
+
+    /// Exchanges whose method contains `filter` (case-insensitive), oldest first
+    pub fn snapshot(&self, filter: &str) -> Vec<RpcExchange> {
+        let filter = filter.to_lowercase();
+        let exchanges = self.exchanges.lock().expect("rpc log lock");
+        exchanges
+            .entries
+            .iter()
+            .filter(|e| filter.is_empty() || e.method.to_lowercase().contains(&filter))
+            .cloned()
+            .collect()
+    }
+
+    pub fn clear(&self) {
+        self.exchanges.lock().expect("rpc log lock").entries.clear();
+    }
+}
+
+/// Tower layer recording every request that passes through the transport into an `RpcLog`
+#[derive(Debug, Clone)]
+pub struct RecordingLayer {
+    log: RpcLog,
+}
+
+impl RecordingLayer {
+    pub fn new(log: RpcLog) -> Self {
+        Self { log }
+    }
+}
+
+impl<S> Layer<S> for RecordingLayer {
+    type Service = Recording<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Recording {
+            inner,
+            log: self.log.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Recording<S> {
+    inner: S,
+    log: RpcLog,
+}
+
+impl<S> Service<RequestPacket> for Recording<S>
+where
+    S: Service<
+            RequestPacket,
+            Response = ResponsePacket,
+            Error = TransportError,
+            Future = TransportFut<'static>,
+        > + Send
+        + Sync
+        + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let method = request.method_names().collect::<Vec<_>>().join(", ");
+        let body = serde_json::to_string(&request).unwrap_or_default();
+        let id = self.log.begin(method, body);
+        let log = self.log.clone();
+        let started = Instant::now();
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let result = response.await;
+            log.finish(id, &result, started.elapsed());
+            result
+        })
+    }
+}
+
+/// A shell command that replays `body` against `endpoint`: curl over HTTP, websocat for a
+/// WebSocket and socat for an IPC socket
+pub fn replay_command(endpoint: &Endpoint, body: &str) -> String {
+    let body = shell_quote(body);
+    match &endpoint.connection {
+        BuiltInConnectionString::Ws(url, _) => {
+            format!("echo {body} | websocat -n1 {}", shell_quote(url.as_str()))
+        }
+        BuiltInConnectionString::Ipc(path) => format!(
+            "echo {body} | socat - UNIX-CONNECT:{}",
+            shell_quote(&path.display().to_string())
+        ),
+        _ => {
+            let mut command = String::from("curl -s -X POST -H 'Content-Type: application/json'");
+            for header in &endpoint.headers {
+                command.push_str(&format!(
+                    " -H {}",
+                    header_quote(&format!("{}: {}", header.name, header.template))
+                ));
+            }
+            format!("{command} --data {body} {}", shell_quote(&endpoint.display))
+        }
+    }
+}
+
+/// Single-quote `text` for a POSIX shell
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// Double-quote a header so `${VAR}` references from the config are filled in by the shell
+/// instead of pasting their values
+fn header_quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        if matches!(c, '"' | '\\' | '`') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Indent a JSON body for display, leaving anything unparsable as is
+pub fn pretty_json(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .and_then(|value| serde_json::to_string_pretty(&value))
+        .unwrap_or_else(|_| body.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::Config;
+    use alloy::rpc::json_rpc::{Id, Request};
+    use std::collections::BTreeMap;
+
+    fn packet(method: &'static str) -> RequestPacket {
+        Request::new(method, Id::Number(1), ())
+            .serialize()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn test_log_capacity_and_filter() {
+        let log = RpcLog::default();
+        for i in 0..CAPACITY + 2 {
+            let method = if i % 2 == 0 {
+                "eth_call"
+            } else {
+                "eth_chainId"
+            };
+            let id = log.begin(method.to_string(), "{}".to_string());
+            log.finish(
+                id,
+                &Err(TransportError::local_usage_str("boom")),
+                Duration::from_millis(3),
+            );
+        }
+
+        let all = log.snapshot("");
+        assert_eq!(all.len(), CAPACITY);
+        assert_eq!(all[0].id, 2);
+        assert!(all.iter().all(|e| e.error.is_some() && !e.is_pending()));
+
+        let calls = log.snapshot("CALL");
+        assert_eq!(calls.len(), CAPACITY / 2);
+        assert!(calls.iter().all(|e| e.method == "eth_call"));
+
+        log.clear();
+        assert!(log.snapshot("").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recording_service() {
+        let log = RpcLog::default();
+        let asserter = alloy::transports::mock::Asserter::new();
+        asserter.push_success(&"0x7a69");
+        let mut service = RecordingLayer::new(log.clone())
+            .layer(alloy::transports::mock::MockTransport::new(asserter));
+
+        service.call(packet("eth_chainId")).await.unwrap();
+
+        let exchanges = log.snapshot("");
+        assert_eq!(exchanges.len(), 1);
+        assert_eq!(exchanges[0].method, "eth_chainId");
+        assert!(exchanges[0].request.contains("\"method\":\"eth_chainId\""));
+        assert!(exchanges[0].response.as_deref().unwrap().contains("0x7a69"));
+        assert_eq!(exchanges[0].error, None);
+    }
+
+    #[test]
+    fn test_replay_command() {
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"eth_call","params":["it's"]}"#;
+        let config = Config::default();
+        let http = Endpoint::new("http://localhost:8545", &config).unwrap();
+        assert_eq!(
+            replay_command(&http, body),
+            r#"curl -s -X POST -H 'Content-Type: application/json' --data '{"jsonrpc":"2.0","id":1,"method":"eth_call","params":["it'\''s"]}' 'http://localhost:8545/'"#
+        );
+
+        // Header values keep their environment references for the shell to fill in
+        let config = Config {
+            rpc_headers: BTreeMap::from([(
+                "*".to_string(),
+                BTreeMap::from([("Authorization".to_string(), "Bearer ${HOME}".to_string())]),
+            )]),
+            ..Config::default()
+        };
+        let staging = Endpoint::new("https://staging.example.org", &config).unwrap();
+        assert_eq!(
+            replay_command(&staging, "{}"),
+            r#"curl -s -X POST -H 'Content-Type: application/json' -H "Authorization: Bearer ${HOME}" --data '{}' 'https://staging.example.org/'"#
+        );
+
+        let ipc = Endpoint::new("/tmp/anvil.ipc", &Config::default()).unwrap();
+        assert!(replay_command(&ipc, "{}").ends_with("| socat - UNIX-CONNECT:'/tmp/anvil.ipc'"));
+    }
+}
@@ -0,0 +1,720 @@
+use alloy::dyn_abi::{DynSolValue, FunctionExt, JsonAbiExt, TypedData};
+use alloy::json_abi::{JsonAbi, Param};
+use alloy::primitives::{eip191_hash_message, Address, Signature, B256, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::signers::local::PrivateKeySigner;
+use alloy::signers::SignerSync;
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Starting point of the "Sign message" editor
+pub const MESSAGE_TEMPLATE: &str = "\
+# Write the message to sign below these comment lines. It is signed with personal_sign
+# (EIP-191) exactly as written, without the final newline; a single 0x-prefixed hex
+# line is signed as raw bytes. Save and quit to sign; leave it empty to cancel.
+";
+
+/// Starting point of the "Verify signature" editor
+pub const VERIFY_TEMPLATE: &str = "\
+# Recover who signed a message or EIP-712 typed data. Fill in the signature (65 bytes,
+# or 64 in the compact EIP-2098 form) and optionally the expected signer, then put the
+# signed content after \"message:\" (everything below that line, as signed) or after
+# \"typed data:\" (the JSON). Save and quit to verify; leave it empty to cancel.
+
+signature:
+signer:
+message:
+";
+
+/// Header of the "Sign typed data" editor, above the JSON
+const TYPED_DATA_HEADER: &str = "\
+# EIP-712 typed data to sign, as for eth_signTypedData_v4. Edit the message (and the
+# domain if needed), save and quit to sign; delete the JSON to cancel.
+";
+
+/// Domain fields in the order EIP-712 lists them, with their types
+const DOMAIN_FIELDS: [(&str, &str); 5] = [
+    ("name", "string"),
+    ("version", "string"),
+    ("chainId", "uint256"),
+    ("verifyingContract", "address"),
+    ("salt", "bytes32"),
+];
+
+/// Signature of ERC-2612 `permit`, whose `Permit` struct is not in the ABI
+const PERMIT: &str = "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)";
+
+/// The signing tools of the command palette, each editing its text in `$EDITOR`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignTool {
+    Message,
+    TypedData,
+    Verify,
+}
+
+impl SignTool {
+    pub fn title(&self) -> &'static str {
+        match self {
+            SignTool::Message => "Sign message",
+            SignTool::TypedData => "Sign typed data",
+            SignTool::Verify => "Verify signature",
+        }
+    }
+}
+
+/// What gets signed: an EIP-191 message or EIP-712 typed data
+#[derive(Debug, Clone)]
+pub enum Payload {
+    Message(Vec<u8>),
+    TypedData(Box<TypedData>),
+}
+
+impl Payload {
+    /// The digest the signature is over
+    pub fn hash(&self) -> Result<B256> {
+        match self {
+            Payload::Message(message) => Ok(eip191_hash_message(message)),
+            Payload::TypedData(typed_data) => typed_data
+                .eip712_signing_hash()
+                .map_err(|e| anyhow!("Invalid typed data: {e}")),
+        }
+    }
+}
+
+impl fmt::Display for Payload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Payload::Message(message) => match std::str::from_utf8(message) {
+                Ok(text) if !text.starts_with("0x") => {
+                    write!(f, "Message ({} bytes): {text:?}", message.len())
+                }
+                _ => write!(
+                    f,
+                    "Message ({} bytes): 0x{}",
+                    message.len(),
+                    hex::encode(message)
+                ),
+            },
+            Payload::TypedData(typed_data) => {
+                let domain = &typed_data.domain;
+                write!(f, "Typed data: {}", typed_data.primary_type)?;
+                if let Some(name) = &domain.name {
+                    write!(f, " for \"{name}\"")?;
+                }
+                if let Some(version) = &domain.version {
+                    write!(f, " v{version}")?;
+                }
+                if let Some(chain_id) = domain.chain_id {
+                    write!(f, ", chain {chain_id}")?;
+                }
+                if let Some(contract) = domain.verifying_contract {
+                    write!(f, ", contract {contract:?}")?;
+                }
+                write!(f, "\nDomain separator: {:?}", domain.separator())
+            }
+        }
+    }
+}
+
+/// A signature with what it signs, for the result card
+#[derive(Debug, Clone)]
+pub struct Signed {
+    pub payload: Payload,
+    pub hash: B256,
+    pub signature: Signature,
+}
+
+impl Signed {
+    /// The 65-byte `r ‖ s ‖ v` form with `v` as 27 or 28, which contracts and `ecrecover` expect
+    pub fn signature_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.signature.as_bytes()))
+    }
+}
+
+impl fmt::Display for Signed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.payload)?;
+        writeln!(f, "Hash: {:?}", self.hash)?;
+        writeln!(f, "Signature: {}", self.signature_hex())?;
+        write!(
+            f,
+            "r: {:#066x}\ns: {:#066x}\nv: {}",
+            self.signature.r(),
+            self.signature.s(),
+            27 + u8::from(self.signature.v())
+        )
+    }
+}
+
+/// Sign `payload` with the private key
+pub fn sign(signer: &PrivateKeySigner, payload: Payload) -> Result<Signed> {
+    let hash = payload.hash()?;
+    let signature = signer.sign_hash_sync(&hash)?;
+    Ok(Signed {
+        payload,
+        hash,
+        signature,
+    })
+}
+
+/// Who signed `payload` with `signature`
+pub fn recover(signature: &Signature, payload: &Payload) -> Result<Address> {
+    let hash = payload.hash()?;
+    signature
+        .recover_address_from_prehash(&hash)
+        .map_err(|e| anyhow!("Cannot recover a signer: {e}"))
+}
+
+/// Lines after the leading comment lines, with the newline editors add at the end removed
+fn strip_header(text: &str) -> &str {
+    let mut rest = text;
+    while rest.starts_with('#') {
+        rest = rest.split_once('\n').map_or("", |(_, after)| after);
+    }
+    rest.strip_suffix('\n')
+        .map(|rest| rest.strip_suffix('\r').unwrap_or(rest))
+        .unwrap_or(rest)
+}
+
+/// A message as written, or the bytes of a single `0x` hex line
+fn message_bytes(message: &str) -> Vec<u8> {
+    let trimmed = message.trim();
+    if !trimmed.contains('\n') {
+        if let Some(Ok(bytes)) = trimmed.strip_prefix("0x").map(hex::decode) {
+            return bytes;
+        }
+    }
+    message.as_bytes().to_vec()
+}
+
+/// Parse the "Sign message" editor contents. `Ok(None)` means there is nothing to sign.
+pub fn parse_message(text: &str) -> Option<Payload> {
+    let message = strip_header(text);
+    if message.trim().is_empty() {
+        return None;
+    }
+    Some(Payload::Message(message_bytes(message)))
+}
+
+fn typed_data_from_json(json: &str) -> Result<TypedData> {
+    let typed_data: TypedData =
+        serde_json::from_str(json).context("Typed data is not valid EIP-712 JSON")?;
+    // Encode it once so missing types and bad values are reported before signing
+    typed_data
+        .eip712_signing_hash()
+        .map_err(|e| anyhow!("Invalid typed data: {e}"))?;
+    Ok(typed_data)
+}
+
+/// Parse the "Sign typed data" editor contents. `Ok(None)` means the JSON was deleted.
+pub fn parse_typed_data(text: &str) -> Result<Option<Payload>> {
+    let json = strip_header(text);
+    if json.trim().is_empty() {
+        return Ok(None);
+    }
+    let typed_data = typed_data_from_json(json)?;
+    Ok(Some(Payload::TypedData(Box::new(typed_data))))
+}
+
+/// A signature to check, from the "Verify signature" editor
+#[derive(Debug, Clone)]
+pub struct VerifyRequest {
+    pub signature: Signature,
+    /// The signer the user expects, if given
+    pub expected: Option<Address>,
+    pub payload: Payload,
+}
+
+/// Parse the "Verify signature" editor contents. `Ok(None)` means nothing was filled in.
+pub fn parse_verify(text: &str) -> Result<Option<VerifyRequest>> {
+    let mut signature = None;
+    let mut expected = None;
+    let mut payload = None;
+
+    let mut rest = text;
+    while !rest.is_empty() {
+        let (line, after) = rest.split_once('\n').unwrap_or((rest, ""));
+        rest = after;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(value) = line.strip_prefix("signature:") {
+            let value = value.trim();
+            if !value.is_empty() {
+                let bytes = hex::decode(value.strip_prefix("0x").unwrap_or(value))
+                    .context("The signature is not valid hex")?;
+                let parsed = Signature::try_from(bytes.as_slice())
+                    .map_err(|e| anyhow!("Invalid signature: {e}"))?;
+                signature = Some(parsed);
+            }
+        } else if let Some(value) = line.strip_prefix("signer:") {
+            let value = value.trim();
+            if !value.is_empty() {
+                let parsed: Address = value
+                    .parse()
+                    .with_context(|| format!("Invalid signer address \"{value}\""))?;
+                expected = Some(parsed);
+            }
+        } else if line == "message:" {
+            let message = rest.strip_suffix('\n').unwrap_or(rest);
+            if !message.trim().is_empty() {
+                payload = Some(Payload::Message(message_bytes(message)));
+            }
+            break;
+        } else if line == "typed data:" {
+            if !rest.trim().is_empty() {
+                payload = Some(Payload::TypedData(Box::new(typed_data_from_json(rest)?)));
+            }
+            break;
+        } else {
+            bail!("Unexpected line \"{line}\"; the signed content goes after \"message:\" or \"typed data:\"");
+        }
+    }
+
+    match (signature, payload) {
+        (None, None) if expected.is_none() => Ok(None),
+        (Some(signature), Some(payload)) => Ok(Some(VerifyRequest {
+            signature,
+            expected,
+            payload,
+        })),
+        (None, _) => bail!("The signature is missing"),
+        (_, None) => bail!("The signed message or typed data is missing"),
+    }
+}
+
+/// EIP-712 struct types used by the ABI's functions and errors, by struct name, with
+/// their fields as `(name, type)`. Structs with unnamed fields cannot be typed data and
+/// are left out.
+pub fn struct_types(abi: &JsonAbi) -> BTreeMap<String, Vec<(String, String)>> {
+    let mut types = BTreeMap::new();
+    for function in abi.functions() {
+        collect_structs(&function.inputs, &mut types);
+        collect_structs(&function.outputs, &mut types);
+    }
+    for error in abi.errors() {
+        collect_structs(&error.inputs, &mut types);
+    }
+    types.retain(|_, fields| fields.iter().all(|(name, _)| !name.is_empty()));
+    types
+}
+
+fn collect_structs(params: &[Param], types: &mut BTreeMap<String, Vec<(String, String)>>) {
+    for param in params {
+        let Some(name) = struct_name(param) else {
+            continue;
+        };
+        if types.contains_key(&name) {
+            continue;
+        }
+        let fields = param
+            .components
+            .iter()
+            .map(|component| (component.name.clone(), eip712_type(component)))
+            .collect();
+        types.insert(name, fields);
+        collect_structs(&param.components, types);
+    }
+}
+
+/// Struct name from the `internalType`, without the contract and array suffix
+fn struct_name(param: &Param) -> Option<String> {
+    if !param.ty.starts_with("tuple") {
+        return None;
+    }
+    let (_, ty) = param.internal_type.as_ref()?.as_struct()?;
+    ty.split('[').next().map(str::to_string)
+}
+
+/// Type of a field in EIP-712 terms: structs by name, keeping array suffixes
+fn eip712_type(param: &Param) -> String {
+    match struct_name(param) {
+        Some(name) => format!("{name}{}", &param.ty["tuple".len()..]),
+        None => param.ty.clone(),
+    }
+}
+
+/// Whether the ABI has ERC-2612 `permit`
+pub fn has_permit(abi: &JsonAbi) -> bool {
+    abi.functions()
+        .any(|function| function.signature() == PERMIT)
+}
+
+/// Whether the contract takes typed data worth offering a template for
+pub fn offers_typed_data(abi: &JsonAbi) -> bool {
+    has_permit(abi) || !struct_types(abi).is_empty()
+}
+
+/// Domain fields from the values returned by EIP-5267 `eip712Domain()`: the `fields`
+/// bitmap says which of name, version, chainId, verifyingContract and salt are used
+pub fn domain_from_eip5267(values: &[DynSolValue]) -> Option<Map<String, Value>> {
+    let [DynSolValue::FixedBytes(fields, 1), DynSolValue::String(name), DynSolValue::String(version), DynSolValue::Uint(chain_id, _), DynSolValue::Address(contract), DynSolValue::FixedBytes(salt, 32), ..] =
+        values
+    else {
+        return None;
+    };
+    let values = [
+        json!(name),
+        json!(version),
+        json!(chain_id.to_string()),
+        json!(format!("{contract:?}")),
+        json!(format!("{salt:?}")),
+    ];
+    let mut domain = Map::new();
+    for (bit, ((field, _), value)) in DOMAIN_FIELDS.iter().zip(values).enumerate() {
+        if fields[0] & (1 << bit) != 0 {
+            domain.insert(field.to_string(), value);
+        }
+    }
+    Some(domain)
+}
+
+/// Call a view function of the ABI by name, None when it is missing or fails
+async fn call_view<P: Provider>(
+    provider: &P,
+    abi: &JsonAbi,
+    address: Address,
+    name: &str,
+    args: &[DynSolValue],
+) -> Option<Vec<DynSolValue>> {
+    let function = abi
+        .function(name)?
+        .iter()
+        .find(|function| function.inputs.len() == args.len())?;
+    let data = function.abi_encode_input(args).ok()?;
+    let tx = TransactionRequest::default().to(address).input(data.into());
+    let output = provider.call(tx).await.ok()?;
+    function.abi_decode_output(&output).ok()
+}
+
+/// EIP-712 domain of the contract at `address`: what its EIP-5267 `eip712Domain()`
+/// returns, else its `name()` (or `fallback_name`), version 1, the chain id and the address
+pub async fn contract_domain<P: Provider>(
+    provider: &P,
+    abi: &JsonAbi,
+    address: Address,
+    chain_id: Option<u64>,
+    fallback_name: &str,
+) -> Map<String, Value> {
+    if let Some(values) = call_view(provider, abi, address, "eip712Domain", &[]).await {
+        if let Some(domain) = domain_from_eip5267(&values) {
+            return domain;
+        }
+    }
+    let name = match call_view(provider, abi, address, "name", &[])
+        .await
+        .as_deref()
+    {
+        Some([DynSolValue::String(name)]) => name.clone(),
+        _ => fallback_name.to_string(),
+    };
+    let mut domain = Map::new();
+    domain.insert("name".into(), json!(name));
+    domain.insert("version".into(), json!("1"));
+    if let Some(chain_id) = chain_id {
+        domain.insert("chainId".into(), json!(chain_id.to_string()));
+    }
+    domain.insert("verifyingContract".into(), json!(format!("{address:?}")));
+    domain
+}
+
+/// ERC-2612 `nonces(owner)` of the contract at `address`
+pub async fn permit_nonce<P: Provider>(
+    provider: &P,
+    abi: &JsonAbi,
+    address: Address,
+    owner: Address,
+) -> Option<U256> {
+    match call_view(
+        provider,
+        abi,
+        address,
+        "nonces",
+        &[DynSolValue::Address(owner)],
+    )
+    .await
+    .as_deref()
+    {
+        Some([DynSolValue::Uint(nonce, _)]) => Some(*nonce),
+        _ => None,
+    }
+}
+
+/// Editor text for typed data: the domain, the ABI's struct types (and `Permit` when the
+/// contract has ERC-2612 `permit` or no structs) and a message of default values for the
+/// primary type.
+/// `owner` fills the permit owner; `permit_nonce` its nonce.
+pub fn typed_data_template(
+    abi: &JsonAbi,
+    domain: Map<String, Value>,
+    owner: Address,
+    permit_nonce: Option<U256>,
+) -> String {
+    let mut types = struct_types(abi);
+    // Without structs of its own, a contract's typed data is most likely a permit
+    let permit = has_permit(abi) || types.is_empty();
+    if permit {
+        types.insert(
+            "Permit".to_string(),
+            [
+                ("owner", "address"),
+                ("spender", "address"),
+                ("value", "uint256"),
+                ("nonce", "uint256"),
+                ("deadline", "uint256"),
+            ]
+            .iter()
+            .map(|(name, ty)| (name.to_string(), ty.to_string()))
+            .collect(),
+        );
+    }
+
+    // Sign the first struct no other struct contains, else the permit
+    let nested: Vec<String> = types
+        .values()
+        .flatten()
+        .map(|(_, ty)| ty.split('[').next().unwrap_or_default().to_string())
+        .collect();
+    let primary = types
+        .keys()
+        .find(|name| *name != "Permit" && !nested.contains(name))
+        .or_else(|| types.keys().next())
+        .cloned();
+
+    let mut header = TYPED_DATA_HEADER.to_string();
+    if types.len() > 1 {
+        let names: Vec<&str> = types.keys().map(String::as_str).collect();
+        header.push_str(&format!(
+            "# Types from the ABI: {}. Set primaryType and the message to sign another one.\n",
+            names.join(", ")
+        ));
+    }
+
+    let message = match primary.as_deref() {
+        Some("Permit") => json!({
+            "owner": format!("{owner:?}"),
+            "spender": format!("{:?}", Address::ZERO),
+            "value": "0",
+            "nonce": permit_nonce.unwrap_or_default().to_string(),
+            "deadline": U256::MAX.to_string(),
+        }),
+        Some(name) => default_struct(name, &types),
+        None => json!({}),
+    };
+
+    let mut json_types = Map::new();
+    let domain_type: Vec<Value> = DOMAIN_FIELDS
+        .iter()
+        .filter(|(field, _)| domain.contains_key(*field))
+        .map(|(name, ty)| json!({ "name": name, "type": ty }))
+        .collect();
+    json_types.insert("EIP712Domain".to_string(), Value::Array(domain_type));
+    for (name, fields) in &types {
+        let fields = fields
+            .iter()
+            .map(|(name, ty)| json!({ "name": name, "type": ty }))
+            .collect();
+        json_types.insert(name.clone(), Value::Array(fields));
+    }
+
+    let typed_data = json!({
+        "types": json_types,
+        "primaryType": primary.unwrap_or_default(),
+        "domain": domain,
+        "message": message,
+    });
+    let json = serde_json::to_string_pretty(&typed_data).unwrap_or_default();
+    format!("{header}{json}\n")
+}
+
+/// A message for struct `name` with every field at its zero value
+fn default_struct(name: &str, types: &BTreeMap<String, Vec<(String, String)>>) -> Value {
+    let fields = types.get(name).map(Vec::as_slice).unwrap_or_default();
+    let object: Map<String, Value> = fields
+        .iter()
+        .map(|(field, ty)| (field.clone(), default_value(ty, types)))
+        .collect();
+    Value::Object(object)
+}
+
+fn default_value(ty: &str, types: &BTreeMap<String, Vec<(String, String)>>) -> Value {
+    if ty.ends_with(']') {
+        return json!([]);
+    }
+    match ty {
+        "address" => json!(format!("{:?}", Address::ZERO)),
+        "bool" => json!(false),
+        "string" => json!(""),
+        "bytes" => json!("0x"),
+        _ if ty.starts_with("uint") || ty.starts_with("int") => json!("0"),
+        _ if ty.starts_with("bytes") => {
+            let size: usize = ty["bytes".len()..].parse().unwrap_or(32);
+            json!(format!("0x{}", "00".repeat(size)))
+        }
+        _ => default_struct(ty, types),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDER_ABI: &str = r#"[{
+        "type": "function",
+        "name": "fill",
+        "stateMutability": "nonpayable",
+        "inputs": [{
+            "name": "order",
+            "type": "tuple",
+            "internalType": "struct Exchange.Order",
+            "components": [
+                {"name": "maker", "type": "address", "internalType": "address"},
+                {"name": "amount", "type": "uint256", "internalType": "uint256"},
+                {"name": "legs", "type": "tuple[]", "internalType": "struct Exchange.Leg[]", "components": [
+                    {"name": "token", "type": "address", "internalType": "address"},
+                    {"name": "data", "type": "bytes32", "internalType": "bytes32"}
+                ]}
+            ]
+        }, {"name": "signature", "type": "bytes", "internalType": "bytes"}],
+        "outputs": []
+    }]"#;
+
+    fn domain() -> Map<String, Value> {
+        let mut domain = Map::new();
+        domain.insert("name".into(), json!("Exchange"));
+        domain.insert("version".into(), json!("1"));
+        domain.insert("chainId".into(), json!("31337"));
+        domain.insert(
+            "verifyingContract".into(),
+            json!(format!("{:?}", Address::repeat_byte(0x11))),
+        );
+        domain
+    }
+
+    #[test]
+    fn test_sign_and_verify_message() {
+        let signer = PrivateKeySigner::random();
+        let payload = parse_message(&format!("{MESSAGE_TEMPLATE}hello\n")).unwrap();
+        let signed = sign(&signer, payload).unwrap();
+        assert_eq!(signed.hash, eip191_hash_message(b"hello"));
+        // Same signature as signing the message directly
+        assert_eq!(
+            signed.signature,
+            signer.sign_message_sync(b"hello").unwrap()
+        );
+        assert!(signed.to_string().contains("Message (5 bytes): \"hello\""));
+        assert!(signed.signature_hex().len() == 132);
+
+        let text = VERIFY_TEMPLATE.replace(
+            "signature:\n",
+            &format!("signature: {}\n", signed.signature_hex()),
+        ) + "hello\n";
+        let request = parse_verify(&text).unwrap().unwrap();
+        assert_eq!(request.expected, None);
+        assert_eq!(
+            recover(&request.signature, &request.payload).unwrap(),
+            signer.address()
+        );
+
+        // Hex lines are signed as bytes
+        let Some(Payload::Message(bytes)) = parse_message("0xdeadbeef\n") else {
+            panic!("expected a message");
+        };
+        assert_eq!(bytes, vec![0xde, 0xad, 0xbe, 0xef]);
+        assert!(parse_message(MESSAGE_TEMPLATE).is_none());
+    }
+
+    #[test]
+    fn test_typed_data_from_abi() {
+        let abi: JsonAbi = serde_json::from_str(ORDER_ABI).unwrap();
+        let types = struct_types(&abi);
+        assert_eq!(types.keys().collect::<Vec<_>>(), ["Leg", "Order"]);
+        assert_eq!(types["Order"][2], ("legs".to_string(), "Leg[]".to_string()));
+        assert!(!has_permit(&abi));
+        assert!(offers_typed_data(&abi));
+
+        let template = typed_data_template(&abi, domain(), Address::ZERO, None);
+        assert!(template.contains("\"primaryType\": \"Order\""));
+        let Some(Payload::TypedData(typed_data)) = parse_typed_data(&template).unwrap() else {
+            panic!("expected typed data");
+        };
+        assert_eq!(typed_data.domain.chain_id, Some(U256::from(31337)));
+
+        let signer = PrivateKeySigner::random();
+        let signed = sign(&signer, Payload::TypedData(typed_data.clone())).unwrap();
+        assert_eq!(
+            signed.signature,
+            signer.sign_dynamic_typed_data_sync(&typed_data).unwrap()
+        );
+
+        let text = format!(
+            "signature: {}\nsigner: {:?}\ntyped data:\n{}",
+            signed.signature_hex(),
+            signer.address(),
+            strip_header(&template)
+        );
+        let request = parse_verify(&text).unwrap().unwrap();
+        assert_eq!(request.expected, Some(signer.address()));
+        assert_eq!(
+            recover(&request.signature, &request.payload).unwrap(),
+            signer.address()
+        );
+    }
+
+    #[test]
+    fn test_permit_and_eip5267_domain() {
+        let abi = JsonAbi::parse([
+            "function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s)",
+        ])
+        .unwrap();
+        assert!(has_permit(&abi));
+
+        // name, version, chainId and verifyingContract, without salt
+        let mut fields = B256::ZERO;
+        fields[0] = 0x0f;
+        let values = [
+            DynSolValue::FixedBytes(fields, 1),
+            DynSolValue::String("Token".into()),
+            DynSolValue::String("1".into()),
+            DynSolValue::Uint(U256::from(1), 256),
+            DynSolValue::Address(Address::repeat_byte(0x22)),
+            DynSolValue::FixedBytes(B256::ZERO, 32),
+            DynSolValue::Array(vec![]),
+        ];
+        let domain = domain_from_eip5267(&values).unwrap();
+        assert_eq!(
+            domain.keys().collect::<Vec<_>>(),
+            ["chainId", "name", "verifyingContract", "version"]
+        );
+
+        let owner = Address::repeat_byte(0x33);
+        let template = typed_data_template(&abi, domain, owner, Some(U256::from(4)));
+        let Some(Payload::TypedData(typed_data)) = parse_typed_data(&template).unwrap() else {
+            panic!("expected typed data");
+        };
+        assert_eq!(typed_data.primary_type, "Permit");
+        assert_eq!(typed_data.message["nonce"], "4");
+        assert_eq!(typed_data.message["owner"], format!("{owner:?}"));
+
+        // Without structs or permit there is nothing to offer in the sidebar, but the
+        // palette still gets a permit to edit
+        let empty = JsonAbi::new();
+        assert!(!offers_typed_data(&empty));
+        let template = typed_data_template(&empty, Map::new(), owner, None);
+        assert!(template.contains("\"primaryType\": \"Permit\""));
+        assert!(parse_typed_data(&template).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_verify_errors() {
+        assert!(parse_verify(VERIFY_TEMPLATE).unwrap().is_none());
+        assert!(parse_verify("signature: 0x1234\nmessage:\nhi").is_err());
+        assert!(parse_verify(&format!("signature: 0x{}\n", "11".repeat(65))).is_err());
+        assert!(parse_verify("hello").is_err());
+        assert!(parse_typed_data("{\"types\": {}}").is_err());
+    }
+}
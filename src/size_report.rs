@@ -0,0 +1,272 @@
+use anyhow::{Context, Result};
+use separator::Separatable;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::compile::{self, BytecodeTarget, CompilerSettings};
+
+/// EIP-170: maximum runtime code size
+pub const EIP170_RUNTIME_LIMIT: usize = 24_576;
+/// EIP-3860: maximum initcode size
+pub const EIP3860_INITCODE_LIMIT: usize = 49_152;
+
+/// Byte sizes of one build of a contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodeSizes {
+    /// Creation code (for PVM, the program blob)
+    pub init: usize,
+    /// Code stored on chain after deployment
+    pub runtime: usize,
+}
+
+/// Sizes of a contract built for both targets. A failed build keeps its error
+/// so the other target can still be reported.
+#[derive(Debug, Clone)]
+pub struct SizeReport {
+    pub contract_name: String,
+    pub evm: Result<CodeSizes, String>,
+    pub pvm: Result<CodeSizes, String>,
+}
+
+impl SizeReport {
+    /// Compile `contract_name` for both targets (through the compile cache) and measure it
+    pub fn build(sol_path: &Path, contract_name: &str, settings: &CompilerSettings) -> Self {
+        let measure = |target| -> Result<CodeSizes, String> {
+            let init = compile::compile_contract(sol_path, contract_name, target, settings)
+                .map_err(|e| e.to_string())?;
+            let runtime = compile::compile_runtime_code(sol_path, contract_name, target, settings)
+                .map_err(|e| e.to_string())?;
+            Ok(CodeSizes {
+                init: init.bytecode.len(),
+                runtime: runtime.bytecode.len(),
+            })
+        };
+
+        Self {
+            contract_name: contract_name.to_string(),
+            evm: measure(BytecodeTarget::Evm),
+            pvm: measure(BytecodeTarget::Pvm),
+        }
+    }
+
+    fn sizes(&self, target: BytecodeTarget) -> &Result<CodeSizes, String> {
+        match target {
+            BytecodeTarget::Evm => &self.evm,
+            BytecodeTarget::Pvm => &self.pvm,
+        }
+    }
+
+    /// Table rows: (label, EVM, PVM)
+    fn rows(&self) -> Vec<[String; 3]> {
+        let cell =
+            |target: BytecodeTarget, f: &dyn Fn(&CodeSizes) -> String| match self.sizes(target) {
+                Ok(sizes) => f(sizes),
+                Err(_) => "-".to_string(),
+            };
+        let both = |f: &dyn Fn(&CodeSizes) -> String| {
+            [cell(BytecodeTarget::Evm, f), cell(BytecodeTarget::Pvm, f)]
+        };
+
+        let [evm_init, pvm_init] = both(&|s| format_bytes(s.init));
+        let [evm_runtime, pvm_runtime] = both(&|s| format_bytes(s.runtime));
+        let mut rows = vec![
+            ["Init code".to_string(), evm_init, pvm_init],
+            ["Runtime code".to_string(), evm_runtime, pvm_runtime],
+        ];
+
+        // The EVM limits don't apply to PVM blobs
+        let headroom = |size: usize, limit: usize| {
+            let remaining = limit as i64 - size as i64;
+            let percent = size as f64 * 100.0 / limit as f64;
+            if remaining >= 0 {
+                format!(
+                    "{} left ({percent:.1}% used)",
+                    format_bytes(remaining as usize)
+                )
+            } else {
+                format!(
+                    "OVER by {} ({percent:.1}%)",
+                    format_bytes(-remaining as usize)
+                )
+            }
+        };
+        rows.push([
+            "EIP-170 runtime limit".to_string(),
+            cell(BytecodeTarget::Evm, &|s| {
+                headroom(s.runtime, EIP170_RUNTIME_LIMIT)
+            }),
+            "n/a".to_string(),
+        ]);
+        rows.push([
+            "EIP-3860 initcode limit".to_string(),
+            cell(BytecodeTarget::Evm, &|s| {
+                headroom(s.init, EIP3860_INITCODE_LIMIT)
+            }),
+            "n/a".to_string(),
+        ]);
+
+        if let (Ok(evm), Ok(pvm)) = (&self.evm, &self.pvm) {
+            rows.push([
+                "PVM blob vs EVM runtime".to_string(),
+                String::new(),
+                format_delta(pvm.init, evm.runtime),
+            ]);
+        }
+        rows
+    }
+
+    /// Build errors, one line per failed target
+    fn errors(&self) -> Vec<String> {
+        [BytecodeTarget::Evm, BytecodeTarget::Pvm]
+            .into_iter()
+            .filter_map(|target| {
+                self.sizes(target)
+                    .as_ref()
+                    .err()
+                    .map(|e| format!("{target} build failed: {e}"))
+            })
+            .collect()
+    }
+
+    /// Plain-text table for a log card
+    pub fn to_text(&self) -> String {
+        let rows = self.rows();
+        let width = |col: usize, header: &str| {
+            rows.iter()
+                .map(|r| r[col].chars().count())
+                .chain(std::iter::once(header.len()))
+                .max()
+                .unwrap_or(0)
+        };
+        let (w0, w1) = (width(0, ""), width(1, "EVM"));
+
+        let mut lines = vec![
+            format!("Size report: {}", self.contract_name),
+            String::new(),
+        ];
+        lines.push(format!("{:w0$}  {:w1$}  PVM", "", "EVM"));
+        for [label, evm, pvm] in &rows {
+            lines.push(format!("{label:w0$}  {evm:w1$}  {pvm}"));
+        }
+        let errors = self.errors();
+        if !errors.is_empty() {
+            lines.push(String::new());
+            lines.extend(errors);
+        }
+        lines.join("\n")
+    }
+
+    /// GitHub-flavoured markdown table
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# Size report: {}\n\n", self.contract_name);
+        out.push_str("| | EVM | PVM |\n|---|---:|---:|\n");
+        for [label, evm, pvm] in self.rows() {
+            out.push_str(&format!("| {label} | {evm} | {pvm} |\n"));
+        }
+        let errors = self.errors();
+        if !errors.is_empty() {
+            out.push('\n');
+            for error in errors {
+                out.push_str(&format!("- {error}\n"));
+            }
+        }
+        out
+    }
+
+    /// Write the markdown table to `dir/<Contract>-sizes.md`, returning the file path
+    pub fn export_markdown(&self, dir: &Path) -> Result<PathBuf> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+        let path = dir.join(format!("{}-sizes.md", self.contract_name));
+        fs::write(&path, self.to_markdown())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// Reports directory (~/.evm-cli/reports)
+pub fn default_reports_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".evm-cli")
+        .join("reports")
+}
+
+/// "12,345 B (12.1 KiB)"
+fn format_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else {
+        format!(
+            "{} B ({:.1} KiB)",
+            bytes.separated_string(),
+            bytes as f64 / 1024.0
+        )
+    }
+}
+
+/// "+1,234 B (x2.50)" relative to `base`
+fn format_delta(size: usize, base: usize) -> String {
+    let diff = size as i64 - base as i64;
+    let sign = if diff >= 0 { "+" } else { "-" };
+    let ratio = if base == 0 {
+        String::new()
+    } else {
+        format!(" (x{:.2})", size as f64 / base as f64)
+    };
+    format!("{sign}{} B{ratio}", diff.unsigned_abs().separated_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn report() -> SizeReport {
+        SizeReport {
+            contract_name: "Token".to_string(),
+            evm: Ok(CodeSizes {
+                init: 6_000,
+                runtime: 5_000,
+            }),
+            pvm: Ok(CodeSizes {
+                init: 20_000,
+                runtime: 20_000,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_limits_and_delta() {
+        let text = report().to_text();
+        assert!(text.contains("19,576 B (19.1 KiB) left (20.3% used)"));
+        assert!(text.contains("43,152 B (42.1 KiB) left (12.2% used)"));
+        assert!(text.contains("+15,000 B (x4.00)"));
+    }
+
+    #[test]
+    fn test_over_limit_and_failed_target() {
+        let report = SizeReport {
+            contract_name: "Big".to_string(),
+            evm: Ok(CodeSizes {
+                init: 30_000,
+                runtime: 25_000,
+            }),
+            pvm: Err("resolc not found".to_string()),
+        };
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("OVER by 424 B"));
+        assert!(markdown.contains("- PVM build failed: resolc not found"));
+        assert!(!markdown.contains("PVM blob vs EVM runtime"));
+    }
+
+    #[test]
+    fn test_export_markdown() {
+        let dir = TempDir::new().unwrap();
+        let path = report().export_markdown(dir.path()).unwrap();
+        assert_eq!(path, dir.path().join("Token-sizes.md"));
+        let content = fs::read_to_string(path).unwrap();
+        assert!(content.starts_with("# Size report: Token"));
+        assert!(content.contains("| Runtime code | 5,000 B (4.9 KiB) | 20,000 B (19.5 KiB) |"));
+    }
+}
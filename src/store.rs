@@ -1,13 +1,22 @@
-use alloy::primitives::Address;
+use alloy::primitives::{Address, B256};
 use anyhow::{Context, Result};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::compile::{BytecodeTarget, CompilerSettings};
+use crate::proxy::ProxyInfo;
 
 const STORE_DIR: &str = ".evm-cli";
 const STORE_FILE: &str = "config.json";
 
+/// Schema version written to config.json
+/// 0: deployments are bare address strings
+/// 1: deployments are records with chain, target and transaction metadata
+const STORE_VERSION: u32 = 1;
+
 /// Get the default global config directory (~/.evm-cli)
 fn default_store_dir() -> PathBuf {
     dirs::home_dir()
@@ -28,6 +37,16 @@ pub const DEFAULT_ADDRESS: &str = "0xf24FF3a9CF04c71Dbc94D0b566f7A27B94566cac";
 pub const DEFAULT_PRIVATE_KEY: &str =
     "5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133";
 
+/// How long to wait for a receipt by default
+pub const DEFAULT_RECEIPT_TIMEOUT_SECS: u64 = 300;
+
+/// How long a single RPC request may take by default
+pub const DEFAULT_RPC_TIMEOUT_SECS: u64 = 30;
+
+/// Default retries of a failed RPC request on the same endpoint, and the pause before the first
+pub const DEFAULT_RPC_RETRIES: u32 = 1;
+pub const DEFAULT_RPC_BACKOFF_MS: u64 = 250;
+
 /// Configuration settings stored in the .evm-cli/config.json file
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -40,6 +59,52 @@ pub struct Config {
     /// Private key for signing transactions
     #[serde(default = "default_private_key")]
     pub private_key: String,
+    /// Seconds to wait for a transaction receipt before giving up on it
+    #[serde(default = "default_receipt_timeout_secs")]
+    pub receipt_timeout_secs: u64,
+    /// Blocks a receipt must be buried under (1 = mined) before a transaction counts as confirmed
+    #[serde(default = "default_confirmations")]
+    pub confirmations: u64,
+    /// Endpoints tried in order when the current one fails
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_rpc_urls: Vec<String>,
+    /// Extra HTTP headers per endpoint, keyed by its URL as written in `rpc_url` or
+    /// `fallback_rpc_urls`, or `*` for every endpoint. `${VAR}` in a value is read from the
+    /// environment.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rpc_headers: BTreeMap<String, BTreeMap<String, String>>,
+    /// Seconds before a single RPC request is abandoned
+    #[serde(default = "default_rpc_timeout_secs")]
+    pub rpc_timeout_secs: u64,
+    /// Retries of a failed request on the same endpoint before moving to the next one
+    #[serde(default = "default_rpc_retries")]
+    pub rpc_retries: u32,
+    /// Pause before the first retry, doubled for each further one
+    #[serde(default = "default_rpc_backoff_ms")]
+    pub rpc_backoff_ms: u64,
+    /// Account to send from instead of the private key's, signed by the node
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_account: Option<NodeAccount>,
+}
+
+/// An account whose transactions the node signs, sent with `eth_sendTransaction`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeAccount {
+    pub address: Address,
+    /// Impersonated on a dev node rather than one of its unlocked `eth_accounts`
+    #[serde(default)]
+    pub impersonated: bool,
+}
+
+impl NodeAccount {
+    /// How the account is signed, as shown next to it
+    pub fn label(&self) -> &'static str {
+        if self.impersonated {
+            "impersonated"
+        } else {
+            "node"
+        }
+    }
 }
 
 fn default_rpc_url() -> String {
@@ -54,12 +119,40 @@ fn default_private_key() -> String {
     DEFAULT_PRIVATE_KEY.to_string()
 }
 
+fn default_receipt_timeout_secs() -> u64 {
+    DEFAULT_RECEIPT_TIMEOUT_SECS
+}
+
+fn default_confirmations() -> u64 {
+    1
+}
+
+fn default_rpc_timeout_secs() -> u64 {
+    DEFAULT_RPC_TIMEOUT_SECS
+}
+
+fn default_rpc_retries() -> u32 {
+    DEFAULT_RPC_RETRIES
+}
+
+fn default_rpc_backoff_ms() -> u64 {
+    DEFAULT_RPC_BACKOFF_MS
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             rpc_url: default_rpc_url(),
             address: default_address(),
             private_key: default_private_key(),
+            receipt_timeout_secs: default_receipt_timeout_secs(),
+            confirmations: default_confirmations(),
+            fallback_rpc_urls: Vec::new(),
+            rpc_headers: BTreeMap::new(),
+            rpc_timeout_secs: default_rpc_timeout_secs(),
+            rpc_retries: default_rpc_retries(),
+            rpc_backoff_ms: default_rpc_backoff_ms(),
+            node_account: None,
         }
     }
 }
@@ -105,25 +198,240 @@ impl ContractId {
     }
 }
 
+/// A deployed (or loaded) instance and where it came from. Instances loaded by address,
+/// or saved before this metadata was recorded, only have the address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeploymentRecord {
+    pub address: Address,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<u64>,
+    /// Genesis block of the chain, to tell a restarted dev node from the one deployed on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genesis_hash: Option<B256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<BytecodeTarget>,
+    /// Settings the deployed bytecode was compiled with, to verify it against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<CompilerSettings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<B256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployer: Option<Address>,
+    /// Constructor arguments as `name: value` (values only when imported from forge)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub constructor_args: Vec<String>,
+    /// RFC 3339 time the deployment was confirmed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployed_at: Option<String>,
+}
+
+impl DeploymentRecord {
+    /// A record with nothing but the address
+    pub fn new(address: Address) -> Self {
+        Self {
+            address,
+            chain_id: None,
+            genesis_hash: None,
+            target: None,
+            settings: None,
+            tx_hash: None,
+            block_number: None,
+            deployer: None,
+            constructor_args: Vec::new(),
+            deployed_at: None,
+        }
+    }
+
+    /// Whether the instance was recorded on `chain_id`. Records without a chain (loaded by
+    /// address or migrated from an old config) are on no chain in particular.
+    pub fn on_chain(&self, chain_id: u64) -> bool {
+        self.chain_id == Some(chain_id)
+    }
+
+    /// Whether the instance was recorded on an earlier incarnation of the chain: same chain
+    /// ID, different genesis block (a restarted anvil or revive dev node)
+    pub fn predates_reset(&self, chain_id: u64, genesis_hash: B256) -> bool {
+        self.chain_id == Some(chain_id) && self.genesis_hash.is_some_and(|g| g != genesis_hash)
+    }
+}
+
+impl fmt::Display for DeploymentRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Address:      {:?}", self.address)?;
+        if let Some(chain_id) = self.chain_id {
+            write!(f, "\nChain:        {chain_id}")?;
+        }
+        if let Some(target) = self.target {
+            write!(f, "\nTarget:       {target}")?;
+            if let Some(settings) = &self.settings {
+                write!(f, "\nCompiler:     {}", settings.summary(target))?;
+            }
+        }
+        let Some(tx_hash) = self.tx_hash else {
+            return write!(
+                f,
+                "\n\nLoaded by address, no deployment transaction recorded"
+            );
+        };
+        write!(f, "\nTransaction:  {tx_hash:?}")?;
+        if let Some(block) = self.block_number {
+            write!(f, "\nBlock:        {block}")?;
+        }
+        if let Some(deployer) = self.deployer {
+            write!(f, "\nDeployer:     {deployer:?}")?;
+        }
+        if let Some(deployed_at) = &self.deployed_at {
+            let local = chrono::DateTime::parse_from_rfc3339(deployed_at)
+                .map(|t| {
+                    t.with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string()
+                })
+                .unwrap_or_else(|_| deployed_at.clone());
+            write!(f, "\nDeployed at:  {local}")?;
+        }
+        if self.constructor_args.is_empty() {
+            write!(f, "\nConstructor:  (no arguments)")
+        } else {
+            write!(f, "\nConstructor:")?;
+            for arg in &self.constructor_args {
+                write!(f, "\n  {arg}")?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Read deployments written by any schema version; version 0 stored bare addresses
+fn deserialize_deployments<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<String, Vec<DeploymentRecord>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Stored {
+        Address(Address),
+        Record(Box<DeploymentRecord>),
+    }
+
+    let stored = HashMap::<String, Vec<Stored>>::deserialize(deserializer)?;
+    Ok(stored
+        .into_iter()
+        .map(|(key, entries)| {
+            let records = entries
+                .into_iter()
+                .map(|entry| match entry {
+                    Stored::Address(address) => DeploymentRecord::new(address),
+                    Stored::Record(record) => *record,
+                })
+                .collect();
+            (key, records)
+        })
+        .collect())
+}
+
+/// A contract attached by address with a hand-written, human-readable ABI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttachedContract {
+    pub name: String,
+    /// ABI items as written, e.g. `function balanceOf(address) view returns (uint256)`
+    pub signatures: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeploymentStore {
+    /// Schema version (absent in files written before versioning, i.e. 0)
+    #[serde(default)]
+    version: u32,
     /// Configuration settings
     #[serde(default)]
     pub config: Config,
-    /// Deployments: map of "path:ContractName" -> list of deployed addresses
+    /// Deployments: map of "path:ContractName" -> deployed instances
     /// Empty array means contract is saved but not yet deployed
-    #[serde(default)]
-    deployments: HashMap<String, Vec<String>>,
+    #[serde(default, deserialize_with = "deserialize_deployments")]
+    deployments: HashMap<String, Vec<DeploymentRecord>>,
+    /// Compiler settings: map of "path:ContractName" -> settings (absent means defaults)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    compiler_settings: HashMap<String, CompilerSettings>,
+    /// Proxies: map of "chainId:address" (bare address when no chain was recorded) -> ERC-1967
+    /// slots at the time it was deployed or loaded
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    proxies: HashMap<String, ProxyInfo>,
+    /// Implementations deployed behind a proxy: map of address -> "path:ContractName".
+    /// They are not instances of their own, but a proxy's ABI comes from them.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    implementations: HashMap<String, String>,
+    /// Manifest runs: map of "chainId:manifest path" -> step name -> deployed address
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    manifests: HashMap<String, HashMap<String, String>>,
+    /// Manifest runs: map of "chainId:manifest path" -> step name -> number of its calls
+    /// sent, so a run resumes at the call that failed
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    manifest_calls: HashMap<String, HashMap<String, usize>>,
+    /// Attached contracts: map of address -> name and human-readable ABI
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    attached: HashMap<String, AttachedContract>,
     /// Path to the config file (not serialized)
     #[serde(skip)]
     config_file_path: PathBuf,
 }
 
+fn manifest_key(chain_id: u64, manifest: &Path) -> String {
+    format!("{chain_id}:{}", manifest.display())
+}
+
+fn proxy_key(chain_id: Option<u64>, address: Address) -> String {
+    match chain_id {
+        Some(chain_id) => format!("{chain_id}:{address:?}"),
+        None => format!("{address:?}"),
+    }
+}
+
+/// Position of the record of `address` on `chain_id`. A record without a chain stands for
+/// the address on any chain, so it is used when the chain has none of its own.
+fn find_record(
+    records: &[DeploymentRecord],
+    chain_id: Option<u64>,
+    address: Address,
+) -> Option<usize> {
+    records
+        .iter()
+        .position(|r| r.address == address && r.chain_id == chain_id)
+        .or_else(|| {
+            records
+                .iter()
+                .position(|r| r.address == address && r.chain_id.is_none())
+        })
+}
+
+/// Like `find_record`, but without a chain (not connected) any record of `address` will do
+fn lookup_record(
+    records: &[DeploymentRecord],
+    chain_id: Option<u64>,
+    address: Address,
+) -> Option<usize> {
+    match chain_id {
+        Some(_) => find_record(records, chain_id, address),
+        None => records.iter().position(|r| r.address == address),
+    }
+}
+
 impl Default for DeploymentStore {
     fn default() -> Self {
         Self {
+            version: STORE_VERSION,
             config: Config::default(),
             deployments: HashMap::new(),
+            compiler_settings: HashMap::new(),
+            proxies: HashMap::new(),
+            implementations: HashMap::new(),
+            manifests: HashMap::new(),
+            manifest_calls: HashMap::new(),
+            attached: HashMap::new(),
             config_file_path: default_store_path(),
         }
     }
@@ -149,8 +457,15 @@ impl DeploymentStore {
                 }
             }
             let store = Self {
+                version: STORE_VERSION,
                 config: Config::default(),
                 deployments: HashMap::new(),
+                compiler_settings: HashMap::new(),
+                proxies: HashMap::new(),
+                implementations: HashMap::new(),
+                manifests: HashMap::new(),
+                manifest_calls: HashMap::new(),
+                attached: HashMap::new(),
                 config_file_path: path.clone(),
             };
             // Save the default config
@@ -165,12 +480,33 @@ impl DeploymentStore {
             let mut store: Self = serde_json::from_str(&content)
                 .with_context(|| format!("Failed to parse {}", path.display()))?;
             store.config_file_path = path;
+            if store.version < STORE_VERSION {
+                store.migrate()?;
+            }
             store
         };
 
         Ok(store)
     }
 
+    /// Rewrite an older config file in the current schema, keeping the original as
+    /// config.json.v<version>.bak
+    fn migrate(&mut self) -> Result<()> {
+        let backup = self
+            .config_file_path
+            .with_extension(format!("json.v{}.bak", self.version));
+        fs::copy(&self.config_file_path, &backup)
+            .with_context(|| format!("Failed to back up config to {}", backup.display()))?;
+        log::info!(
+            "[STORE] Migrating {} from schema {} to {STORE_VERSION} (backup: {})",
+            self.config_file_path.display(),
+            self.version,
+            backup.display()
+        );
+        self.version = STORE_VERSION;
+        self.save()
+    }
+
     pub fn save(&self) -> Result<()> {
         if let Some(parent) = self.config_file_path.parent() {
             if !parent.exists() {
@@ -193,33 +529,71 @@ impl DeploymentStore {
         &self.config_file_path
     }
 
+    /// Addresses of a contract's instances, each once even when recorded on several chains
     pub fn get_deployments(&self, contract_id: &ContractId) -> Vec<Address> {
-        let key = contract_id.to_key();
+        let mut addresses: Vec<Address> = Vec::new();
+        for record in self.get_deployment_records(contract_id) {
+            if !addresses.contains(&record.address) {
+                addresses.push(record.address);
+            }
+        }
+        addresses
+    }
+
+    /// Every record of a contract's instances, one per chain and address
+    pub fn get_deployment_records(&self, contract_id: &ContractId) -> &[DeploymentRecord] {
         self.deployments
-            .get(&key)
-            .map(|addrs| addrs.iter().filter_map(|s| s.parse().ok()).collect())
+            .get(&contract_id.to_key())
+            .map(Vec::as_slice)
             .unwrap_or_default()
     }
 
+    /// Metadata recorded for an instance on `chain_id`. Without a chain (not connected),
+    /// any record of the address.
+    pub fn get_deployment(
+        &self,
+        contract_id: &ContractId,
+        chain_id: Option<u64>,
+        address: Address,
+    ) -> Option<&DeploymentRecord> {
+        let records = self.deployments.get(&contract_id.to_key())?;
+        records.get(lookup_record(records, chain_id, address)?)
+    }
+
+    /// Register an instance by address, keeping any metadata already recorded for it
+    #[cfg(test)]
     pub fn add_deployment(&mut self, contract_id: &ContractId, address: Address) {
-        let key = contract_id.to_key();
-        let addr_str = format!("{address:?}");
+        let records = self.deployments.entry(contract_id.to_key()).or_default();
+        if !records.iter().any(|r| r.address == address) {
+            records.push(DeploymentRecord::new(address));
+        }
+    }
 
-        let addrs = self.deployments.entry(key).or_default();
-        if !addrs.contains(&addr_str) {
-            addrs.push(addr_str);
+    /// Register an instance with its metadata, replacing what was recorded for the address on
+    /// the record's chain. Records of the address on other chains are kept.
+    pub fn record_deployment(&mut self, contract_id: &ContractId, record: DeploymentRecord) {
+        let records = self.deployments.entry(contract_id.to_key()).or_default();
+        match find_record(records, record.chain_id, record.address) {
+            Some(pos) => records[pos] = record,
+            None => records.push(record),
         }
     }
 
-    /// Remove a specific deployment address from a contract
+    /// Remove the record of an instance on `chain_id` (see `get_deployment`)
     /// Note: The contract entry is preserved even if all deployments are removed
-    pub fn remove_deployment(&mut self, contract_id: &ContractId, address: Address) -> bool {
+    pub fn remove_deployment(
+        &mut self,
+        contract_id: &ContractId,
+        chain_id: Option<u64>,
+        address: Address,
+    ) -> bool {
         let key = contract_id.to_key();
-        let addr_str = format!("{address:?}");
 
-        if let Some(addrs) = self.deployments.get_mut(&key) {
-            if let Some(pos) = addrs.iter().position(|a| a == &addr_str) {
-                addrs.remove(pos);
+        if let Some(records) = self.deployments.get_mut(&key) {
+            if let Some(pos) = lookup_record(records, chain_id, address) {
+                let record = records.remove(pos);
+                self.proxies
+                    .remove(&proxy_key(record.chain_id, record.address));
                 // Keep the contract entry even if no deployments left
                 // User must explicitly delete the contract to remove it
                 return true;
@@ -231,7 +605,153 @@ impl DeploymentStore {
     /// Remove all deployments for a contract
     pub fn remove_contract(&mut self, contract_id: &ContractId) -> bool {
         let key = contract_id.to_key();
-        self.deployments.remove(&key).is_some()
+        self.compiler_settings.remove(&key);
+        match self.deployments.remove(&key) {
+            Some(records) => {
+                for record in records {
+                    self.proxies
+                        .remove(&proxy_key(record.chain_id, record.address));
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get the compiler settings for a contract (defaults if none were saved)
+    pub fn get_compiler_settings(&self, contract_id: &ContractId) -> CompilerSettings {
+        self.compiler_settings
+            .get(&contract_id.to_key())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Save compiler settings for a contract; default settings remove the entry
+    pub fn set_compiler_settings(&mut self, contract_id: &ContractId, settings: CompilerSettings) {
+        let key = contract_id.to_key();
+        if settings.is_default() {
+            self.compiler_settings.remove(&key);
+        } else {
+            self.compiler_settings.insert(key, settings);
+        }
+    }
+
+    /// Proxy details recorded for a deployed address on `chain_id`, or for the address on
+    /// any chain when none were recorded with a chain
+    pub fn get_proxy(&self, chain_id: Option<u64>, address: Address) -> Option<&ProxyInfo> {
+        self.proxies
+            .get(&proxy_key(chain_id, address))
+            .or_else(|| self.proxies.get(&proxy_key(None, address)))
+    }
+
+    /// Record (or with None, forget) that a deployed address is a proxy on `chain_id`
+    pub fn set_proxy(&mut self, chain_id: Option<u64>, address: Address, info: Option<ProxyInfo>) {
+        let key = proxy_key(chain_id, address);
+        match info {
+            Some(info) => {
+                self.proxies.insert(key, info);
+            }
+            None => {
+                self.proxies.remove(&key);
+            }
+        }
+    }
+
+    /// Record that `address` was deployed as an implementation of `contract_id`
+    pub fn record_implementation(&mut self, address: Address, contract_id: &ContractId) {
+        self.implementations
+            .insert(format!("{address:?}"), contract_id.to_key());
+    }
+
+    /// Contract of an implementation deployed behind a proxy
+    pub fn implementation_contract(&self, address: Address) -> Option<ContractId> {
+        ContractId::from_key(self.implementations.get(&format!("{address:?}"))?)
+    }
+
+    /// Contract an address was deployed or loaded as, if any
+    pub fn contract_for_address(&self, address: Address) -> Option<ContractId> {
+        self.all_contracts()
+            .into_iter()
+            .find(|contract_id| self.get_deployments(contract_id).contains(&address))
+    }
+
+    /// Address a manifest step was deployed at on `chain_id`
+    pub fn get_manifest_deployment(
+        &self,
+        chain_id: u64,
+        manifest: &Path,
+        step: &str,
+    ) -> Option<Address> {
+        self.manifests
+            .get(&manifest_key(chain_id, manifest))?
+            .get(step)?
+            .parse()
+            .ok()
+    }
+
+    /// Record where a manifest step was deployed on `chain_id`. A new address starts its
+    /// calls over.
+    pub fn set_manifest_deployment(
+        &mut self,
+        chain_id: u64,
+        manifest: &Path,
+        step: &str,
+        address: Address,
+    ) {
+        let key = manifest_key(chain_id, manifest);
+        let previous = self
+            .manifests
+            .entry(key.clone())
+            .or_default()
+            .insert(step.to_string(), format!("{address:?}"));
+        if previous != Some(format!("{address:?}")) {
+            if let Some(calls) = self.manifest_calls.get_mut(&key) {
+                calls.remove(step);
+            }
+        }
+    }
+
+    /// Number of calls of a manifest step sent on `chain_id`
+    pub fn get_manifest_calls(&self, chain_id: u64, manifest: &Path, step: &str) -> usize {
+        self.manifest_calls
+            .get(&manifest_key(chain_id, manifest))
+            .and_then(|calls| calls.get(step))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Record how many calls of a manifest step were sent on `chain_id`
+    pub fn set_manifest_calls(&mut self, chain_id: u64, manifest: &Path, step: &str, calls: usize) {
+        self.manifest_calls
+            .entry(manifest_key(chain_id, manifest))
+            .or_default()
+            .insert(step.to_string(), calls);
+    }
+
+    /// Contracts attached by signature, sorted by name then address
+    pub fn attached_contracts(&self) -> Vec<(Address, &AttachedContract)> {
+        let mut attached: Vec<_> = self
+            .attached
+            .iter()
+            .filter_map(|(key, contract)| Some((key.parse::<Address>().ok()?, contract)))
+            .collect();
+        attached.sort_by(|(a, x), (b, y)| x.name.cmp(&y.name).then(a.cmp(b)));
+        attached
+    }
+
+    /// The contract attached at `address`, if any
+    pub fn get_attached(&self, address: Address) -> Option<&AttachedContract> {
+        self.attached.get(&format!("{address:?}"))
+    }
+
+    /// Attach (or re-attach with a new ABI) a contract at `address`
+    pub fn attach(&mut self, address: Address, contract: AttachedContract) {
+        self.attached.insert(format!("{address:?}"), contract);
+    }
+
+    /// Forget an attached contract. Returns true if it was attached
+    pub fn detach(&mut self, address: Address) -> bool {
+        self.attached.remove(&format!("{address:?}")).is_some()
     }
 
     /// Returns all contracts as ContractId
@@ -248,9 +768,15 @@ impl DeploymentStore {
         self.deployments.entry(key).or_default();
     }
 
-    /// Clear all deployments
+    /// Clear all deployments and per-contract settings
     pub fn clear(&mut self) {
         self.deployments.clear();
+        self.compiler_settings.clear();
+        self.proxies.clear();
+        self.implementations.clear();
+        self.manifests.clear();
+        self.manifest_calls.clear();
+        self.attached.clear();
     }
 }
 
@@ -273,6 +799,34 @@ mod tests {
         assert_eq!(config.rpc_url, DEFAULT_RPC_URL);
         assert_eq!(config.address, DEFAULT_ADDRESS);
         assert_eq!(config.private_key, DEFAULT_PRIVATE_KEY);
+        assert_eq!(config.receipt_timeout_secs, DEFAULT_RECEIPT_TIMEOUT_SECS);
+        assert_eq!(config.confirmations, 1);
+
+        // Config files written before these settings existed get the defaults
+        let config: Config =
+            serde_json::from_str(r#"{"rpc_url": "http://localhost:8545"}"#).unwrap();
+        assert_eq!(config.receipt_timeout_secs, DEFAULT_RECEIPT_TIMEOUT_SECS);
+        assert_eq!(config.confirmations, 1);
+        assert!(config.fallback_rpc_urls.is_empty());
+        assert!(config.rpc_headers.is_empty());
+        assert_eq!(config.rpc_timeout_secs, DEFAULT_RPC_TIMEOUT_SECS);
+        assert_eq!(config.rpc_retries, DEFAULT_RPC_RETRIES);
+        assert_eq!(config.rpc_backoff_ms, DEFAULT_RPC_BACKOFF_MS);
+        assert_eq!(config.node_account, None);
+
+        // Empty endpoint settings are left out of the file
+        let written = serde_json::to_string(&Config::default()).unwrap();
+        assert!(!written.contains("fallback_rpc_urls"));
+        assert!(!written.contains("rpc_headers"));
+        assert!(!written.contains("node_account"));
+
+        let config: Config = serde_json::from_str(
+            r#"{"node_account": {"address": "0x70997970c51812dc3a010c7d01b50e0d17dc79c8"}}"#,
+        )
+        .unwrap();
+        let account = config.node_account.unwrap();
+        assert!(!account.impersonated);
+        assert_eq!(account.label(), "node");
     }
 
     #[test]
@@ -389,7 +943,7 @@ mod tests {
         assert_eq!(store.get_deployments(&contract_id).len(), 2);
 
         // Remove one
-        let removed = store.remove_deployment(&contract_id, address1);
+        let removed = store.remove_deployment(&contract_id, None, address1);
         assert!(removed);
 
         let deployments = store.get_deployments(&contract_id);
@@ -408,7 +962,7 @@ mod tests {
             .parse()
             .unwrap();
 
-        let removed = store.remove_deployment(&contract_id, address);
+        let removed = store.remove_deployment(&contract_id, None, address);
         assert!(!removed);
     }
 
@@ -476,6 +1030,305 @@ mod tests {
         assert!(names.contains(&"ContractB"));
     }
 
+    #[test]
+    fn test_compiler_settings_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        let contract_id = ContractId::new(PathBuf::from("/test/Contract.sol"), "Test".to_string());
+        let settings = CompilerSettings {
+            optimizer_runs: Some(200),
+            via_ir: true,
+            ..Default::default()
+        };
+
+        {
+            let mut store = DeploymentStore::load_from(Some(config_path.clone())).unwrap();
+            assert!(store.get_compiler_settings(&contract_id).is_default());
+            store.set_compiler_settings(&contract_id, settings.clone());
+            store.save().unwrap();
+        }
+
+        let mut store = DeploymentStore::load_from(Some(config_path)).unwrap();
+        assert_eq!(store.get_compiler_settings(&contract_id), settings);
+
+        // Resetting to defaults drops the entry; removing the contract drops it too
+        store.set_compiler_settings(&contract_id, CompilerSettings::default());
+        assert!(store.compiler_settings.is_empty());
+        store.set_compiler_settings(&contract_id, settings);
+        store.remove_contract(&contract_id);
+        assert!(store.get_compiler_settings(&contract_id).is_default());
+    }
+
+    #[test]
+    fn test_proxy_info_roundtrip() {
+        use crate::proxy::ProxyKind;
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        let contract_id = ContractId::new(PathBuf::from("/test/Token.sol"), "Token".to_string());
+        let proxy: Address = "0x1234567890123456789012345678901234567890"
+            .parse()
+            .unwrap();
+        let info = ProxyInfo {
+            kind: ProxyKind::Transparent,
+            implementation: Address::repeat_byte(0x11),
+            admin: Some(Address::repeat_byte(0x22)),
+            beacon: None,
+        };
+
+        {
+            let mut store = DeploymentStore::load_from(Some(config_path.clone())).unwrap();
+            store.add_deployment(&contract_id, proxy);
+            store.set_proxy(None, proxy, Some(info.clone()));
+            store.record_implementation(info.implementation, &contract_id);
+            store.save().unwrap();
+        }
+
+        let mut store = DeploymentStore::load_from(Some(config_path)).unwrap();
+        assert_eq!(store.get_proxy(Some(31337), proxy), Some(&info));
+        assert_eq!(store.contract_for_address(proxy), Some(contract_id.clone()));
+        // The implementation is known, but not listed as an instance
+        assert_eq!(
+            store.implementation_contract(info.implementation),
+            Some(contract_id.clone())
+        );
+        assert_eq!(store.contract_for_address(info.implementation), None);
+
+        // Removing the deployment forgets the proxy
+        store.remove_deployment(&contract_id, Some(31337), proxy);
+        assert_eq!(store.get_proxy(None, proxy), None);
+        assert_eq!(store.contract_for_address(proxy), None);
+    }
+
+    #[test]
+    fn test_manifest_deployments_are_per_chain() {
+        let (mut store, _temp) = create_test_store();
+        let manifest = PathBuf::from("/test/deploy.json");
+        let token: Address = "0x1234567890123456789012345678901234567890"
+            .parse()
+            .unwrap();
+
+        store.set_manifest_deployment(31337, &manifest, "token", token);
+        assert_eq!(
+            store.get_manifest_deployment(31337, &manifest, "token"),
+            Some(token)
+        );
+        assert_eq!(store.get_manifest_deployment(1, &manifest, "token"), None);
+        assert_eq!(
+            store.get_manifest_deployment(31337, &manifest, "vault"),
+            None
+        );
+
+        // Calls resume where they stopped, unless the step is deployed again
+        store.set_manifest_calls(31337, &manifest, "token", 2);
+        assert_eq!(store.get_manifest_calls(31337, &manifest, "token"), 2);
+        assert_eq!(store.get_manifest_calls(1, &manifest, "token"), 0);
+        store.set_manifest_deployment(31337, &manifest, "token", token);
+        assert_eq!(store.get_manifest_calls(31337, &manifest, "token"), 2);
+        store.set_manifest_deployment(31337, &manifest, "token", Address::repeat_byte(0x22));
+        assert_eq!(store.get_manifest_calls(31337, &manifest, "token"), 0);
+
+        store.clear();
+        assert_eq!(
+            store.get_manifest_deployment(31337, &manifest, "token"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_attached_contracts_persist() {
+        let (mut store, temp) = create_test_store();
+        let token = Address::repeat_byte(0x22);
+        let contract = AttachedContract {
+            name: "Token".to_string(),
+            signatures: vec!["function totalSupply() view returns (uint256)".to_string()],
+        };
+        store.attach(token, contract.clone());
+        store.attach(
+            Address::repeat_byte(0x11),
+            AttachedContract {
+                name: "Weth".to_string(),
+                signatures: vec!["function deposit() payable".to_string()],
+            },
+        );
+        store.save().unwrap();
+
+        let mut loaded = DeploymentStore::load_from(Some(temp.path().join("config.json"))).unwrap();
+        assert_eq!(loaded.get_attached(token), Some(&contract));
+        let names: Vec<_> = loaded
+            .attached_contracts()
+            .iter()
+            .map(|(_, c)| c.name.clone())
+            .collect();
+        assert_eq!(names, ["Token", "Weth"]);
+
+        assert!(loaded.detach(token));
+        assert!(!loaded.detach(token));
+        loaded.clear();
+        assert!(loaded.attached_contracts().is_empty());
+    }
+
+    #[test]
+    fn test_legacy_deployments_are_migrated() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        let legacy = r#"{
+            "config": { "rpc_url": "http://custom:8545" },
+            "deployments": {
+                "/test/Contract.sol:Test": ["0x1234567890123456789012345678901234567890"],
+                "/test/Empty.sol:Empty": []
+            }
+        }"#;
+        fs::write(&config_path, legacy).unwrap();
+
+        let store = DeploymentStore::load_from(Some(config_path.clone())).unwrap();
+        let contract_id = ContractId::new(PathBuf::from("/test/Contract.sol"), "Test".to_string());
+        let address: Address = "0x1234567890123456789012345678901234567890"
+            .parse()
+            .unwrap();
+        assert_eq!(store.get_deployments(&contract_id), vec![address]);
+        assert_eq!(
+            store.get_deployment(&contract_id, None, address),
+            Some(&DeploymentRecord::new(address))
+        );
+        assert_eq!(store.all_contracts().len(), 2);
+
+        // The file is rewritten in the current schema and the original kept as a backup
+        let rewritten: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(rewritten["version"], STORE_VERSION);
+        assert_eq!(
+            rewritten["deployments"]["/test/Contract.sol:Test"][0]["address"],
+            "0x1234567890123456789012345678901234567890"
+        );
+        let backup = temp_dir.path().join("config.json.v0.bak");
+        assert_eq!(fs::read_to_string(backup).unwrap(), legacy);
+    }
+
+    #[test]
+    fn test_deployment_record_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        let contract_id = ContractId::new(PathBuf::from("/test/Token.sol"), "Token".to_string());
+        let address: Address = "0x1234567890123456789012345678901234567890"
+            .parse()
+            .unwrap();
+        let record = DeploymentRecord {
+            address,
+            chain_id: Some(31337),
+            genesis_hash: Some(B256::repeat_byte(0x01)),
+            target: Some(BytecodeTarget::Pvm),
+            settings: Some(CompilerSettings {
+                optimizer_runs: Some(200),
+                ..Default::default()
+            }),
+            tx_hash: Some(B256::repeat_byte(0xab)),
+            block_number: Some(7),
+            deployer: Some(Address::repeat_byte(0x22)),
+            constructor_args: vec!["name: \"Token\"".to_string()],
+            deployed_at: Some("2026-01-02T03:04:05+00:00".to_string()),
+        };
+
+        {
+            let mut store = DeploymentStore::load_from(Some(config_path.clone())).unwrap();
+            // Registering by address first, then with metadata, keeps one entry
+            store.add_deployment(&contract_id, address);
+            store.record_deployment(&contract_id, record.clone());
+            store.add_deployment(&contract_id, address);
+            store.save().unwrap();
+        }
+
+        let store = DeploymentStore::load_from(Some(config_path)).unwrap();
+        assert_eq!(store.get_deployments(&contract_id), vec![address]);
+        assert_eq!(
+            store.get_deployment(&contract_id, Some(31337), address),
+            Some(&record)
+        );
+
+        let details = record.to_string();
+        assert!(details.contains("Chain:        31337"), "{details}");
+        assert!(details.contains("Target:       PVM"), "{details}");
+        assert!(
+            details.contains("Compiler:     optimizer 200 runs"),
+            "{details}"
+        );
+        assert!(details.contains("Block:        7"), "{details}");
+        assert!(details.contains("  name: \"Token\""), "{details}");
+        assert!(DeploymentRecord::new(address)
+            .to_string()
+            .contains("no deployment transaction recorded"));
+    }
+
+    #[test]
+    fn test_same_address_on_two_chains() {
+        use crate::proxy::ProxyKind;
+
+        let (mut store, _temp) = create_test_store();
+        let contract_id = ContractId::new(PathBuf::from("/test/Token.sol"), "Token".to_string());
+        let address = Address::repeat_byte(0x11);
+        let on_chain = |chain_id, block| DeploymentRecord {
+            chain_id: Some(chain_id),
+            block_number: Some(block),
+            ..DeploymentRecord::new(address)
+        };
+
+        // Loaded by address first: the first chain it is recorded on takes that record over
+        store.add_deployment(&contract_id, address);
+        store.record_deployment(&contract_id, on_chain(31337, 1));
+        store.record_deployment(&contract_id, on_chain(11155111, 2));
+        assert_eq!(store.get_deployment_records(&contract_id).len(), 2);
+        assert_eq!(store.get_deployments(&contract_id), vec![address]);
+        let block = |store: &DeploymentStore, chain_id| {
+            store
+                .get_deployment(&contract_id, Some(chain_id), address)
+                .and_then(|r| r.block_number)
+        };
+        assert_eq!(block(&store, 31337), Some(1));
+        assert_eq!(block(&store, 11155111), Some(2));
+        assert_eq!(block(&store, 1), None);
+
+        // Redeploying on one chain leaves the other alone
+        store.record_deployment(&contract_id, on_chain(31337, 3));
+        assert_eq!(block(&store, 31337), Some(3));
+        assert_eq!(block(&store, 11155111), Some(2));
+
+        let info = ProxyInfo {
+            kind: ProxyKind::Uups,
+            implementation: Address::repeat_byte(0x22),
+            admin: None,
+            beacon: None,
+        };
+        store.set_proxy(Some(31337), address, Some(info.clone()));
+        assert_eq!(store.get_proxy(Some(31337), address), Some(&info));
+        assert_eq!(store.get_proxy(Some(11155111), address), None);
+
+        assert!(store.remove_deployment(&contract_id, Some(31337), address));
+        assert_eq!(store.get_proxy(Some(31337), address), None);
+        assert_eq!(block(&store, 31337), None);
+        assert_eq!(block(&store, 11155111), Some(2));
+        assert!(!store.remove_deployment(&contract_id, Some(31337), address));
+    }
+
+    #[test]
+    fn test_deployment_record_chain_identity() {
+        let genesis = B256::repeat_byte(0x01);
+        let record = DeploymentRecord {
+            chain_id: Some(31337),
+            genesis_hash: Some(genesis),
+            ..DeploymentRecord::new(Address::repeat_byte(0x11))
+        };
+        assert!(record.on_chain(31337));
+        assert!(!record.on_chain(1));
+        assert!(!record.predates_reset(31337, genesis));
+        assert!(record.predates_reset(31337, B256::repeat_byte(0x02)));
+        assert!(!record.predates_reset(1, B256::repeat_byte(0x02)));
+
+        // Without a recorded chain the instance may live on any chain, so it is never checked
+        let legacy = DeploymentRecord::new(Address::repeat_byte(0x11));
+        assert!(!legacy.on_chain(1));
+        assert!(!legacy.predates_reset(31337, genesis));
+    }
+
     #[test]
     fn test_config_path_returns_correct_path() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,14 @@
+//! Fixtures shared by the unit tests
+
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::transports::mock::Asserter;
+
+/// A provider without fillers that answers every request with the next response queued
+/// on the returned `Asserter`, and fails once there is none left
+pub fn mock_provider() -> (impl Provider + Clone + 'static, Asserter) {
+    let asserter = Asserter::new();
+    let provider = ProviderBuilder::new()
+        .disable_recommended_fillers()
+        .connect_mocked_client(asserter.clone());
+    (provider, asserter)
+}
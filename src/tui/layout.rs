@@ -5,10 +5,12 @@ pub struct AppLayout {
     pub output: Rect,
     pub status_bar: Rect,
     pub debug_bar: Option<Rect>,
+    pub rpc_inspector: Option<Rect>,
+    pub dashboard: Option<Rect>,
 }
 
 impl AppLayout {
-    pub fn new(area: Rect, debug_mode: bool) -> Self {
+    pub fn new(area: Rect, debug_mode: bool, rpc_inspector: bool, dashboard: bool) -> Self {
         let main_split = if debug_mode {
             Layout::default()
                 .direction(Direction::Vertical)
@@ -31,6 +33,28 @@ impl AppLayout {
             (main_split[0], main_split[1], None)
         };
 
+        // The RPC inspector and the dashboard take the lower part of the content area, full
+        // width, side by side when both are shown
+        let (content_area, inspector_area, dashboard_area) = if rpc_inspector || dashboard {
+            let split = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .split(content_area);
+            match (rpc_inspector, dashboard) {
+                (true, true) => {
+                    let panels = Layout::default()
+                        .direction(Direction::Horizontal)
+                        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                        .split(split[1]);
+                    (split[0], Some(panels[0]), Some(panels[1]))
+                }
+                (true, false) => (split[0], Some(split[1]), None),
+                _ => (split[0], None, Some(split[1])),
+            }
+        } else {
+            (content_area, None, None)
+        };
+
         let content_split = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
@@ -41,6 +65,8 @@ impl AppLayout {
             output: content_split[1],
             status_bar: status_area,
             debug_bar: debug_area,
+            rpc_inspector: inspector_area,
+            dashboard: dashboard_area,
         }
     }
 }
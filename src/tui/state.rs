@@ -1,8 +1,12 @@
+use crate::accounts::AccountChoice;
 use crate::cards::{Card, CopyOption, TracerConfig, TracerType};
 use crate::compile::BytecodeTarget;
+use crate::dashboard::Dashboard;
+use crate::diagnostics::Diagnostic;
+use crate::store::NodeAccount;
 use crate::tui::widgets::PathSuggestion;
-use alloy::json_abi::Param;
-use alloy::primitives::Address;
+use alloy::json_abi::{Function, Param};
+use alloy::primitives::{Address, B256, U256};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -10,6 +14,8 @@ pub enum Focus {
     Sidebar,
     Output,
     CommandPalette,
+    RpcInspector,
+    Dashboard,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -32,6 +38,8 @@ pub enum PopupState {
         current: usize,
         /// Some for deploy operations (to select EVM/PVM), None for calls
         bytecode_target: Option<BytecodeTarget>,
+        /// View calls carry block, from and state override fields after the parameters
+        call_options: bool,
     },
     ContractSelector {
         contracts: Vec<String>,
@@ -40,10 +48,12 @@ pub enum PopupState {
     FilePicker {
         path: String,
         error: Option<String>,
+        kind: FilePickerKind,
     },
     AddressInput {
         address: String,
         error: Option<String>,
+        kind: AddressInputKind,
     },
     TracerMenu {
         card_index: usize,
@@ -60,6 +70,132 @@ pub enum PopupState {
         options: Vec<CopyOption>,
         selected: usize,
     },
+    /// Compiler diagnostics panel, listing `AppState::diagnostics`
+    Diagnostics {
+        selected: usize,
+    },
+    /// Confirm a CREATE2 deployment at its predicted address
+    ConfirmCreate2 {
+        contract_name: String,
+        predicted: Address,
+        salt: B256,
+    },
+    /// Transactions waiting for a receipt, to speed up or cancel
+    PendingTransactions {
+        selected: usize,
+    },
+    /// Choose the proxy kind for "Deploy behind proxy"
+    ProxyKindSelector {
+        contract_name: String,
+        selected: usize,
+    },
+    /// Choose the contract "Upgrade implementation" deploys as the proxy's new implementation
+    ImplementationSelector {
+        proxy: Address,
+        /// Name and source path of each loaded contract
+        contracts: Vec<(String, PathBuf)>,
+        selected: usize,
+    },
+    /// Choose the function the proxy runs right after the upgrade (a reinitializer), if
+    /// any. Entry 0 is "no call", entry `i` is `functions[i - 1]`.
+    UpgradeCallSelector {
+        contract_name: String,
+        functions: Vec<Function>,
+        selected: usize,
+    },
+    /// Choose the account transactions are sent from
+    AccountSelector {
+        accounts: Vec<(AccountChoice, Option<U256>)>,
+        selected: usize,
+    },
+    /// Per-contract compiler settings form (see `CompilerSettings::to_fields`)
+    CompilerSettings {
+        contract_name: String,
+        contract_path: PathBuf,
+        fields: Vec<FieldState>,
+        current: usize,
+    },
+}
+
+/// What the file picker is choosing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilePickerKind {
+    /// A Solidity source to load into the sidebar
+    Contract,
+    /// A deployment manifest to run
+    Manifest,
+    /// A `forge script --broadcast` run to import
+    Broadcast,
+}
+
+impl FilePickerKind {
+    /// Extension of the files offered as suggestions
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FilePickerKind::Contract => "sol",
+            FilePickerKind::Manifest | FilePickerKind::Broadcast => "json",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            FilePickerKind::Contract => "Load Contract",
+            FilePickerKind::Manifest => "Run Deployment Manifest",
+            FilePickerKind::Broadcast => "Import Forge Broadcast",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FilePickerKind::Contract => "Path to .sol file",
+            FilePickerKind::Manifest => "Path to manifest (.json)",
+            FilePickerKind::Broadcast => "Path to broadcast run (.json)",
+        }
+    }
+
+    pub fn placeholder(&self) -> &'static str {
+        match self {
+            FilePickerKind::Contract => "./contracts/MyContract.sol",
+            FilePickerKind::Manifest => "./deploy.json",
+            FilePickerKind::Broadcast => "./broadcast/Deploy.s.sol/31337/run-latest.json",
+        }
+    }
+}
+
+/// What the address input is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressInputKind {
+    /// An existing instance of the current contract
+    Instance,
+    /// An account to impersonate on a dev node
+    Impersonate,
+}
+
+impl AddressInputKind {
+    pub fn title(&self) -> &'static str {
+        match self {
+            AddressInputKind::Instance => "Enter Address",
+            AddressInputKind::Impersonate => "Impersonate Account",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AddressInputKind::Instance => "Contract address",
+            AddressInputKind::Impersonate => "Address to send from",
+        }
+    }
+}
+
+/// The RPC inspector panel, shown while `AppState::rpc_inspector` is set
+#[derive(Debug, Clone, Default)]
+pub struct RpcInspectorState {
+    /// Id of the selected exchange; None follows the newest
+    pub selected: Option<u64>,
+    /// Only exchanges whose method contains this are listed
+    pub filter: String,
+    /// Keys edit `filter` instead of navigating
+    pub editing_filter: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -75,6 +211,16 @@ pub struct SidebarState {
     /// Expanded contracts: (path, contract_name) pairs
     pub expanded_contracts: std::collections::HashSet<(PathBuf, String)>,
     pub expanded_instances: std::collections::HashSet<Address>,
+    /// Instances found dead on connect, as (chain ID, address): no code, or deployed before
+    /// the node was reset. Only the connected chain's are kept.
+    pub dead_instances: std::collections::HashSet<(u64, Address)>,
+}
+
+impl SidebarState {
+    /// Whether the instance at `address` is gone from the connected node
+    pub fn is_dead(&self, address: &Address) -> bool {
+        self.dead_instances.iter().any(|(_, dead)| dead == address)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -168,18 +314,20 @@ impl CardState {
                 Card::Transaction {
                     gas_used,
                     contract_address,
+                    compiler,
                     ..
                 } => {
                     let base = 5; // header + hash + status + function + contract_name
                     let addr_line = if contract_address.is_some() { 1 } else { 0 };
                     let gas_line = if gas_used.is_some() { 1 } else { 0 };
+                    let compiler_line = if compiler.is_some() { 1 } else { 0 };
                     // Actions only rendered for selected interactive cards
                     let actions = if is_selected && card.is_interactive() {
                         2
                     } else {
                         0
                     };
-                    base + addr_line + gas_line + actions + 2 // +2 for border line + blank line spacing
+                    base + addr_line + gas_line + compiler_line + actions + 2 // +2 for border line + blank line spacing
                 }
                 Card::Call { .. } => {
                     let base = 6; // header + function + to + from + empty + result
@@ -196,8 +344,8 @@ impl CardState {
                     1 + message_lines + 1 + 1
                 }
                 Card::Connection { error, .. } => {
-                    // header + connected/disconnected + account + balance (optional) + chain_id (optional) + error (optional) + border + spacing
-                    let base = 4; // header + status + account + border
+                    // header + connected/disconnected + account + rpc + balance (optional) + chain_id (optional) + error (optional) + border + spacing
+                    let base = 5; // header + status + account + rpc + border
                     let error_line = if error.is_some() { 1 } else { 0 };
                     base + 2 + error_line + 1 // +2 for balance and chain_id, +1 for spacing
                 }
@@ -244,9 +392,17 @@ pub struct AppState {
     pub cards: CardState,
     pub connection: ConnectionStatus,
     pub chain_id: Option<u64>,
+    /// Genesis block hash of the connected node, tells a restarted dev node apart
+    pub genesis_hash: Option<B256>,
     pub account: Option<Address>,
+    /// Set when `account` is signed by the node rather than the private key
+    pub node_account: Option<NodeAccount>,
     pub balance: Option<String>,
     pub connection_error: Option<String>,
+    /// Latest chain head seen by the block watcher
+    pub block_number: Option<u64>,
+    /// Heads arrive over an `eth_subscribe("newHeads")` subscription rather than polling
+    pub live_blocks: bool,
     pub file_picker_suggestions: Vec<PathSuggestion>,
     pub file_picker_selected_idx: usize,
     pub terminal_size: (u16, u16),
@@ -255,6 +411,18 @@ pub struct AppState {
     pub last_key: Option<String>,
     pub last_action: Option<String>,
     pub debug_mode: bool,
+    /// RPC traffic panel below the sidebar and output, None when hidden
+    pub rpc_inspector: Option<RpcInspectorState>,
+    /// Getters of one instance, refreshed on every block; None when hidden
+    pub dashboard: Option<Dashboard>,
+    /// Diagnostics from the most recent forge build
+    pub diagnostics: Vec<Diagnostic>,
+    /// Labels of compilations currently running in the background
+    pub compiling: Vec<String>,
+    /// Number of transactions sent and still waiting for a receipt
+    pub pending_transactions: usize,
+    /// Incremented once per main loop iteration, drives spinners
+    pub tick: usize,
 }
 
 impl Default for AppState {
@@ -267,9 +435,13 @@ impl Default for AppState {
             cards: CardState::default(),
             connection: ConnectionStatus::Disconnected,
             chain_id: None,
+            genesis_hash: None,
             account: None,
+            node_account: None,
             balance: None,
             connection_error: None,
+            block_number: None,
+            live_blocks: false,
             file_picker_suggestions: Vec::new(),
             file_picker_selected_idx: 0,
             terminal_size: (80, 24),
@@ -278,6 +450,12 @@ impl Default for AppState {
             last_key: None,
             last_action: None,
             debug_mode: std::env::var("EVM_CLI_DEBUG").is_ok(),
+            rpc_inspector: None,
+            dashboard: None,
+            diagnostics: Vec::new(),
+            compiling: Vec::new(),
+            pending_transactions: 0,
+            tick: 0,
         }
     }
 }
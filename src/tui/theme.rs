@@ -128,3 +128,15 @@ pub fn separator_style() -> Style {
 pub fn prompt_style() -> Style {
     Style::default().fg(PRIMARY)
 }
+
+// ============================================================================
+// Spinner
+// ============================================================================
+
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Spinner character for the given animation tick
+#[inline]
+pub fn spinner_frame(tick: usize) -> &'static str {
+    SPINNER_FRAMES[tick % SPINNER_FRAMES.len()]
+}
@@ -14,17 +14,23 @@ pub struct PathSuggestion {
     pub display_name: String,
     pub full_path: PathBuf,
     pub is_directory: bool,
-    pub is_sol_file: bool,
+    /// File with the extension the picker is looking for
+    pub is_match: bool,
 }
 
 impl PathSuggestion {
-    pub fn new(display_name: String, full_path: PathBuf, is_directory: bool) -> Self {
-        let is_sol_file = !is_directory && full_path.extension().is_some_and(|ext| ext == "sol");
+    pub fn new(
+        display_name: String,
+        full_path: PathBuf,
+        is_directory: bool,
+        extension: &str,
+    ) -> Self {
+        let is_match = !is_directory && full_path.extension().is_some_and(|ext| ext == extension);
         Self {
             display_name,
             full_path,
             is_directory,
-            is_sol_file,
+            is_match,
         }
     }
 }
@@ -70,7 +76,8 @@ fn expand_home_dir(input: &str) -> String {
     }
 }
 
-pub fn scan_path_suggestions(dir: &Path, prefix: &str) -> Vec<PathSuggestion> {
+/// Directories and files with `extension` in `dir` starting with `prefix`
+pub fn scan_path_suggestions(dir: &Path, prefix: &str, extension: &str) -> Vec<PathSuggestion> {
     let mut suggestions = Vec::new();
     let show_hidden = prefix.starts_with('.');
     let prefix_lower = prefix.to_lowercase();
@@ -80,7 +87,7 @@ pub fn scan_path_suggestions(dir: &Path, prefix: &str) -> Vec<PathSuggestion> {
         Err(_) => return suggestions,
     };
 
-    let mut sol_files = Vec::new();
+    let mut matching_files = Vec::new();
     let mut directories = Vec::new();
 
     for entry in entries.flatten() {
@@ -98,23 +105,23 @@ pub fn scan_path_suggestions(dir: &Path, prefix: &str) -> Vec<PathSuggestion> {
         let path = entry.path();
         let is_dir = path.is_dir();
 
-        let suggestion = PathSuggestion::new(name_str.to_string(), path, is_dir);
+        let suggestion = PathSuggestion::new(name_str.to_string(), path, is_dir, extension);
 
-        if suggestion.is_sol_file {
-            sol_files.push(suggestion);
+        if suggestion.is_match {
+            matching_files.push(suggestion);
         } else if suggestion.is_directory {
             directories.push(suggestion);
         }
 
-        if sol_files.len() + directories.len() >= 50 {
+        if matching_files.len() + directories.len() >= 50 {
             break;
         }
     }
 
-    sol_files.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+    matching_files.sort_by(|a, b| a.display_name.cmp(&b.display_name));
     directories.sort_by(|a, b| a.display_name.cmp(&b.display_name));
 
-    suggestions.extend(sol_files);
+    suggestions.extend(matching_files);
     suggestions.extend(directories);
 
     suggestions
@@ -242,7 +249,7 @@ impl Widget for AutocompleteInput<'_> {
 
                 let (name_style, prefix_style) = if is_selected {
                     (theme::selected_style(), theme::selected_style())
-                } else if suggestion.is_sol_file {
+                } else if suggestion.is_match {
                     (Style::default().fg(theme::PRIMARY), theme::muted_style())
                 } else if suggestion.is_directory {
                     (theme::muted_style(), theme::muted_style())
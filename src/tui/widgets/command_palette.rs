@@ -43,6 +43,49 @@ pub fn default_commands(debug_mode: bool) -> Vec<Command> {
         Command::new("Clear Logs", "Delete the application log file"),
         Command::new("Reconnect", "Retry connection to RPC server"),
         Command::new(debug_label, "Toggle debug panel visibility"),
+        Command::new(
+            "Compiler diagnostics",
+            "Show errors and warnings from the last build",
+        ),
+        Command::new("Clear build cache", "Delete cached compiler outputs"),
+        Command::new(
+            "Export size report",
+            "Write the last size report as markdown",
+        ),
+        Command::new(
+            "Export address book",
+            "Write deployed addresses and ABIs per chain",
+        ),
+        Command::new(
+            "Run deployment manifest",
+            "Deploy the contracts listed in a manifest file",
+        ),
+        Command::new(
+            "Import forge broadcast",
+            "Add instances deployed by a forge script run",
+        ),
+        Command::new(
+            "Prune dead instances",
+            "Remove instances missing from the connected node",
+        ),
+        Command::new("Raw call", "Send hex calldata and value to any address"),
+        Command::new(
+            "Pending transactions",
+            "Speed up or cancel transactions waiting for a receipt",
+        ),
+        Command::new("RPC inspector", "Show or hide recorded JSON-RPC traffic")
+            .with_shortcut("Ctrl+R"),
+        Command::new(
+            "Select account",
+            "Send from a node account or impersonate an address",
+        ),
+        Command::new("Broadcast raw tx", "Send a signed raw transaction"),
+        Command::new("Sign message", "Sign a message with personal_sign"),
+        Command::new("Sign typed data", "Sign EIP-712 typed data"),
+        Command::new(
+            "Verify signature",
+            "Recover the signer of a message or typed data",
+        ),
         Command::new("Reset", "Clear all saved state"),
         Command::new("Quit", "Exit the application").with_shortcut("Ctrl+C"),
     ]
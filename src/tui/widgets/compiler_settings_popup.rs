@@ -0,0 +1,91 @@
+use crate::tui::layout::centered_popup;
+use crate::tui::state::FieldState;
+use crate::tui::theme;
+use crate::tui::widgets::{InputField, KeyboardHints};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Widget},
+};
+
+/// Labels and placeholders, in the order of `CompilerSettings::to_fields`
+const FIELDS: [(&str, &str); 6] = [
+    ("Optimizer runs", "off"),
+    ("EVM version", "forge default (e.g. cancun)"),
+    ("Via IR", "false"),
+    ("resolc optimization (PVM)", "default (0-3, s, z)"),
+    ("Heap size (PVM)", "default"),
+    ("Stack size (PVM)", "default"),
+];
+
+/// Form for editing the compiler settings of one contract
+pub struct CompilerSettingsPopup<'a> {
+    contract_name: &'a str,
+    fields: &'a [FieldState],
+    current: usize,
+}
+
+impl<'a> CompilerSettingsPopup<'a> {
+    pub fn new(contract_name: &'a str, fields: &'a [FieldState], current: usize) -> Self {
+        Self {
+            contract_name,
+            fields,
+            current,
+        }
+    }
+}
+
+impl Widget for CompilerSettingsPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let height_percent = ((FIELDS.len() * 3 + 6) as u16 * 100 / area.height.max(1)).min(80);
+        let popup_area = centered_popup(area, 70, height_percent.max(30));
+
+        Clear.render(popup_area, buf);
+
+        let title = format!(" {} - Compiler Settings ", self.contract_name);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme::border_style())
+            .title(title);
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let mut y = inner.y + 1;
+        let note = Line::from(Span::styled(
+            "Leave a field empty to use forge's default",
+            theme::hint_desc_style(),
+        ));
+        buf.set_line(inner.x + 1, y, &note, inner.width.saturating_sub(2));
+        y += 2;
+
+        for (i, ((label, placeholder), field)) in FIELDS.iter().zip(self.fields).enumerate() {
+            if y >= inner.y + inner.height.saturating_sub(2) {
+                break;
+            }
+
+            let input = InputField::new(label, &field.value)
+                .placeholder(placeholder)
+                .error(field.error.as_deref())
+                .focused(i == self.current)
+                .cursor_position(field.value.len());
+
+            let field_height = if field.error.is_some() { 2 } else { 1 };
+            let field_area = Rect::new(inner.x + 1, y, inner.width.saturating_sub(2), field_height);
+            input.render(field_area, buf);
+
+            y += field_height + 1;
+        }
+
+        let footer_y = inner.y + inner.height.saturating_sub(1);
+        let hints = KeyboardHints::new(vec![
+            ("Tab", "next"),
+            ("Shift+Tab", "prev"),
+            ("Enter", "save"),
+            ("Esc", "cancel"),
+        ]);
+        let hints_area = Rect::new(inner.x + 1, footer_y, inner.width.saturating_sub(2), 1);
+        hints.render(hints_area, buf);
+    }
+}
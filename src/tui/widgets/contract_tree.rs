@@ -1,3 +1,4 @@
+use crate::proxy::ProxyKind;
 use crate::tui::state::SidebarState;
 use alloy::json_abi::{Function, JsonAbi};
 use alloy::primitives::Address;
@@ -16,42 +17,118 @@ use std::sync::Arc;
 #[derive(Debug, Clone)]
 pub enum TreeNode {
     NewContract,
+    AttachBySignature,
     Contract {
         name: String,
         path: PathBuf,
+        /// ABI still compiling in the background; children are shown once it is in
+        loading: bool,
     },
     Constructor {
         contract_name: String,
         contract_path: PathBuf,
         abi: Arc<JsonAbi>,
     },
+    DeployBehindProxy {
+        contract_name: String,
+        contract_path: PathBuf,
+        abi: Arc<JsonAbi>,
+    },
     LoadExistingInstance {
         contract_name: String,
         contract_path: PathBuf,
         abi: Arc<JsonAbi>,
     },
+    CompilerSettings {
+        contract_name: String,
+        contract_path: PathBuf,
+    },
+    SizeReport {
+        contract_name: String,
+        contract_path: PathBuf,
+    },
     DeployedInstance {
         address: Address,
         contract_name: String,
         contract_path: PathBuf,
     },
+    InstanceDetails {
+        address: Address,
+        contract_name: String,
+        contract_path: PathBuf,
+    },
+    VerifyBytecode {
+        address: Address,
+        contract_name: String,
+        contract_path: PathBuf,
+    },
+    UpgradeImplementation {
+        address: Address,
+        kind: ProxyKind,
+        contract_name: String,
+        contract_path: PathBuf,
+    },
+    AttachedInstance {
+        address: Address,
+        name: String,
+    },
+    RawCall {
+        address: Address,
+    },
+    /// Every getter of the instance, batched into one call
+    ReadGetters {
+        address: Address,
+        count: usize,
+    },
+    /// EIP-712 signing with the domain of this instance
+    SignTypedData {
+        address: Address,
+    },
     Method {
         function: Function,
         tag: &'static str,
         instance_address: Address,
     },
+    Fallback {
+        payable: bool,
+        instance_address: Address,
+    },
+    Receive {
+        instance_address: Address,
+    },
 }
 
 impl TreeNode {
     pub fn label(&self) -> String {
         match self {
             TreeNode::NewContract => "Load new contract...".to_string(),
+            TreeNode::AttachBySignature => "Attach by signature...".to_string(),
+            TreeNode::Contract {
+                name,
+                loading: true,
+                ..
+            } => format!("{name} (compiling...)"),
             TreeNode::Contract { name, .. } => name.clone(),
             TreeNode::Constructor { .. } => "Deploy new instance".to_string(),
+            TreeNode::DeployBehindProxy { .. } => "Deploy behind proxy...".to_string(),
             TreeNode::LoadExistingInstance { .. } => "Load existing instance...".to_string(),
+            TreeNode::CompilerSettings { .. } => "Compiler settings...".to_string(),
+            TreeNode::SizeReport { .. } => "Size report (EVM vs PVM)".to_string(),
             TreeNode::DeployedInstance { address, .. } => {
                 format!("{address:?}")
             }
+            TreeNode::AttachedInstance { address, name } => format!("{name} at {address:?}"),
+            TreeNode::InstanceDetails { .. } => "Deployment details".to_string(),
+            TreeNode::VerifyBytecode { .. } => "Verify bytecode".to_string(),
+            TreeNode::UpgradeImplementation { kind, .. } => {
+                format!("Upgrade implementation ({kind} proxy)...")
+            }
+            TreeNode::RawCall { .. } => "Raw call...".to_string(),
+            TreeNode::ReadGetters { count, .. } => format!("Read all getters ({count})"),
+            TreeNode::SignTypedData { .. } => "Sign typed data...".to_string(),
+            TreeNode::Fallback { payable: true, .. } => "fallback() [payable]".to_string(),
+            TreeNode::Fallback { payable: false, .. } => "fallback() [send]".to_string(),
+            TreeNode::Receive { .. } => "receive() [payable]".to_string(),
             TreeNode::Method { function, tag, .. } => {
                 let params: Vec<_> = function
                     .inputs
@@ -72,11 +149,24 @@ impl TreeNode {
     pub fn depth(&self) -> usize {
         match self {
             TreeNode::NewContract => 0,
+            TreeNode::AttachBySignature => 0,
             TreeNode::Contract { .. } => 0,
             TreeNode::Constructor { .. } => 1,
+            TreeNode::DeployBehindProxy { .. } => 1,
             TreeNode::LoadExistingInstance { .. } => 1,
+            TreeNode::CompilerSettings { .. } => 1,
+            TreeNode::SizeReport { .. } => 1,
             TreeNode::DeployedInstance { .. } => 1,
+            TreeNode::AttachedInstance { .. } => 0,
+            TreeNode::InstanceDetails { .. } => 2,
+            TreeNode::VerifyBytecode { .. } => 2,
+            TreeNode::UpgradeImplementation { .. } => 2,
+            TreeNode::RawCall { .. } => 2,
+            TreeNode::ReadGetters { .. } => 2,
+            TreeNode::SignTypedData { .. } => 2,
             TreeNode::Method { .. } => 2,
+            TreeNode::Fallback { .. } => 2,
+            TreeNode::Receive { .. } => 2,
         }
     }
 }
@@ -157,7 +247,7 @@ impl Widget for ContractTree<'_> {
 
             // Determine prefix based on node type
             let prefix = match node {
-                TreeNode::Contract { path, name } => {
+                TreeNode::Contract { path, name, .. } => {
                     if self
                         .state
                         .expanded_contracts
@@ -168,7 +258,8 @@ impl Widget for ContractTree<'_> {
                         "▸ "
                     }
                 }
-                TreeNode::DeployedInstance { address, .. } => {
+                TreeNode::DeployedInstance { address, .. }
+                | TreeNode::AttachedInstance { address, .. } => {
                     if self.state.expanded_instances.contains(address) {
                         "▾ "
                     } else {
@@ -176,12 +267,30 @@ impl Widget for ContractTree<'_> {
                     }
                 }
                 TreeNode::NewContract => "+ ",
+                TreeNode::AttachBySignature => "+ ",
                 TreeNode::Constructor { .. } => "◇ ",
+                TreeNode::DeployBehindProxy { .. } => "◇ ",
                 TreeNode::LoadExistingInstance { .. } => "◇ ",
+                TreeNode::CompilerSettings { .. } => "⚙ ",
+                TreeNode::SizeReport { .. } => "▤ ",
+                TreeNode::InstanceDetails { .. } => "ℹ ",
+                TreeNode::VerifyBytecode { .. } => "✓ ",
+                TreeNode::UpgradeImplementation { .. } => "⇡ ",
+                TreeNode::RawCall { .. } => "» ",
+                TreeNode::ReadGetters { .. } => "≡ ",
+                TreeNode::SignTypedData { .. } => "✎ ",
                 TreeNode::Method { .. } => "├ ",
+                TreeNode::Fallback { .. } => "├ ",
+                TreeNode::Receive { .. } => "├ ",
             };
 
-            let label = node.label();
+            let dead = matches!(node, TreeNode::DeployedInstance { address, .. }
+                if self.state.is_dead(address));
+            let label = if dead {
+                format!("{} (dead)", node.label())
+            } else {
+                node.label()
+            };
 
             // Style based on selection and node type
             let style = if is_selected {
@@ -192,12 +301,30 @@ impl Widget for ContractTree<'_> {
             } else {
                 match node {
                     TreeNode::NewContract => Style::default().fg(Color::Yellow),
+                    TreeNode::AttachBySignature => Style::default().fg(Color::Yellow),
                     TreeNode::Contract { .. } => Style::default()
                         .fg(Color::Cyan)
                         .add_modifier(Modifier::BOLD),
                     TreeNode::Constructor { .. } => Style::default().fg(Color::Green),
+                    TreeNode::DeployBehindProxy { .. } => Style::default().fg(Color::Green),
                     TreeNode::LoadExistingInstance { .. } => Style::default().fg(Color::Yellow),
+                    TreeNode::CompilerSettings { .. } => Style::default().fg(Color::DarkGray),
+                    TreeNode::SizeReport { .. } => Style::default().fg(Color::DarkGray),
+                    TreeNode::InstanceDetails { .. } => Style::default().fg(Color::DarkGray),
+                    TreeNode::VerifyBytecode { .. } => Style::default().fg(Color::DarkGray),
+                    TreeNode::UpgradeImplementation { .. } => Style::default().fg(Color::Green),
+                    TreeNode::DeployedInstance { .. } if dead => Style::default()
+                        .fg(Color::DarkGray)
+                        .add_modifier(Modifier::CROSSED_OUT),
                     TreeNode::DeployedInstance { .. } => Style::default().fg(Color::Green),
+                    TreeNode::AttachedInstance { .. } => Style::default()
+                        .fg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD),
+                    TreeNode::RawCall { .. } => Style::default().fg(Color::Yellow),
+                    TreeNode::ReadGetters { .. } => Style::default().fg(Color::Blue),
+                    TreeNode::SignTypedData { .. } => Style::default().fg(Color::Yellow),
+                    TreeNode::Fallback { .. } => Style::default().fg(Color::Yellow),
+                    TreeNode::Receive { .. } => Style::default().fg(Color::Yellow),
                     TreeNode::Method { tag, .. } => {
                         if *tag == "view" {
                             Style::default().fg(Color::Blue)
@@ -0,0 +1,145 @@
+use crate::dashboard::{Dashboard, Row};
+use crate::tui::theme;
+use crate::tui::widgets::KeyboardHints;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Widget},
+};
+
+/// Panel below the main area with the balance and getters of one instance, refreshed on
+/// every block. Values changed by the last refresh are highlighted.
+pub struct DashboardPanel<'a> {
+    dashboard: &'a Dashboard,
+    focused: bool,
+    tick: usize,
+}
+
+impl<'a> DashboardPanel<'a> {
+    pub fn new(dashboard: &'a Dashboard) -> Self {
+        Self {
+            dashboard,
+            focused: false,
+            tick: 0,
+        }
+    }
+
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    pub fn tick(mut self, tick: usize) -> Self {
+        self.tick = tick;
+        self
+    }
+
+    fn row_line(row: &Row, label_width: usize) -> Line<'static> {
+        let label = Span::styled(
+            format!("{:<label_width$}  ", row.label),
+            theme::label_style(),
+        );
+        let value = match &row.value {
+            Ok(value) if row.changed() => Span::styled(
+                value.clone(),
+                Style::default()
+                    .fg(theme::ACCENT)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Ok(value) => Span::styled(value.clone(), Style::default().fg(theme::TEXT)),
+            Err(error) => Span::styled(error.clone(), theme::error_style()),
+        };
+        let mut spans = vec![label, value];
+        if let Some(previous) = &row.previous {
+            let previous = match previous {
+                Ok(value) => value.clone(),
+                Err(_) => "error".to_string(),
+            };
+            spans.push(Span::styled(
+                format!("  (was {previous})"),
+                theme::muted_style(),
+            ));
+        }
+        Line::from(spans)
+    }
+}
+
+impl Widget for DashboardPanel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dashboard = self.dashboard;
+        let border_style = if self.focused {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let mut title = format!(" {} at {:?} ", dashboard.name, dashboard.address);
+        if let Some(block) = dashboard.block {
+            title.push_str(&format!("· block {block} "));
+        }
+        if dashboard.refreshing {
+            title.push_str(&format!("{} ", theme::spinner_frame(self.tick)));
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style)
+            .title(title);
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.height < 2 {
+            return;
+        }
+
+        let body = Rect::new(
+            inner.x + 1,
+            inner.y,
+            inner.width.saturating_sub(1),
+            inner.height - 1,
+        );
+        let hints_area = Rect::new(inner.x + 1, inner.y + inner.height - 1, inner.width, 1);
+
+        let mut lines = Vec::new();
+        if let Some(error) = &dashboard.error {
+            lines.push(Line::from(Span::styled(
+                format!("Refresh failed: {error}"),
+                theme::error_style(),
+            )));
+        }
+        if dashboard.rows.is_empty() && dashboard.error.is_none() {
+            lines.push(Line::from(Span::styled("Reading…", theme::muted_style())));
+        }
+        let label_width = dashboard
+            .rows
+            .iter()
+            .map(|row| row.label.len())
+            .max()
+            .unwrap_or(0);
+        lines.extend(
+            dashboard
+                .rows
+                .iter()
+                .map(|row| Self::row_line(row, label_width)),
+        );
+
+        let scroll = dashboard
+            .scroll
+            .min(lines.len().saturating_sub(body.height as usize));
+        for (row, line) in lines
+            .iter()
+            .skip(scroll)
+            .take(body.height as usize)
+            .enumerate()
+        {
+            buf.set_line(body.x, body.y + row as u16, line, body.width);
+        }
+
+        KeyboardHints::new(vec![
+            ("↑↓", "scroll"),
+            ("r", "refresh"),
+            ("Tab", "switch"),
+            ("Esc", "close"),
+        ])
+        .render(hints_area, buf);
+    }
+}
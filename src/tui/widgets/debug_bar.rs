@@ -21,6 +21,8 @@ impl<'a> DebugBarWidget<'a> {
             Focus::Sidebar => "Sidebar",
             Focus::Output => "Output",
             Focus::CommandPalette => "CommandPalette",
+            Focus::RpcInspector => "RpcInspector",
+            Focus::Dashboard => "Dashboard",
         }
     }
 
@@ -35,6 +37,14 @@ impl<'a> DebugBarWidget<'a> {
             PopupState::TracerMenu { .. } => "TracerMenu",
             PopupState::TracerConfig { .. } => "TracerConfig",
             PopupState::CopyMenu { .. } => "CopyMenu",
+            PopupState::Diagnostics { .. } => "Diagnostics",
+            PopupState::CompilerSettings { .. } => "CompilerSettings",
+            PopupState::AccountSelector { .. } => "AccountSelector",
+            PopupState::ConfirmCreate2 { .. } => "ConfirmCreate2",
+            PopupState::ProxyKindSelector { .. } => "ProxyKindSelector",
+            PopupState::ImplementationSelector { .. } => "ImplementationSelector",
+            PopupState::UpgradeCallSelector { .. } => "UpgradeCallSelector",
+            PopupState::PendingTransactions { .. } => "PendingTransactions",
         }
     }
 }
@@ -0,0 +1,157 @@
+use crate::diagnostics::{self, Diagnostic, Severity};
+use crate::tui::theme;
+use crate::tui::widgets::{KeyboardHints, Popup};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+/// Scrollable list of compiler errors and warnings
+pub struct DiagnosticsPanel<'a> {
+    diagnostics: &'a [Diagnostic],
+    selected: usize,
+}
+
+impl<'a> DiagnosticsPanel<'a> {
+    pub fn new(diagnostics: &'a [Diagnostic], selected: usize) -> Self {
+        Self {
+            diagnostics,
+            selected,
+        }
+    }
+
+    fn severity_style(severity: Severity) -> Style {
+        match severity {
+            Severity::Error => theme::error_style().add_modifier(Modifier::BOLD),
+            Severity::Warning => Style::default()
+                .fg(theme::ACCENT)
+                .add_modifier(Modifier::BOLD),
+            Severity::Info => theme::muted_style(),
+        }
+    }
+
+    /// Render every diagnostic into lines, returning the line range of each entry
+    fn build_lines(&self) -> (Vec<Line<'a>>, Vec<(usize, usize)>) {
+        let mut lines = Vec::new();
+        let mut ranges = Vec::new();
+
+        for (i, diagnostic) in self.diagnostics.iter().enumerate() {
+            let start = lines.len();
+            let is_selected = i == self.selected;
+
+            let marker_style = if is_selected {
+                theme::selected_style()
+            } else {
+                Style::default()
+            };
+            let mut severity = diagnostic.severity.to_string();
+            if let Some(code) = &diagnostic.code {
+                severity.push_str(&format!("[{code}]"));
+            }
+            let mut header = vec![
+                Span::styled(if is_selected { ">" } else { " " }, marker_style),
+                Span::raw(" "),
+                Span::styled(severity, Self::severity_style(diagnostic.severity)),
+            ];
+            if let Some(location) = diagnostic.location() {
+                header.push(Span::styled(format!(" {location}"), theme::muted_style()));
+            }
+            lines.push(Line::from(header));
+
+            let message_style = if is_selected {
+                Style::default()
+                    .fg(theme::TEXT)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme::TEXT)
+            };
+            for message_line in diagnostic.message.lines() {
+                lines.push(Line::from(Span::styled(
+                    format!("  {message_line}"),
+                    message_style,
+                )));
+            }
+            for snippet_line in &diagnostic.snippet {
+                lines.push(Line::from(Span::styled(
+                    format!("  {snippet_line}"),
+                    Style::default().fg(theme::PRIMARY),
+                )));
+            }
+
+            lines.push(Line::from(""));
+            ranges.push((start, lines.len()));
+        }
+
+        (lines, ranges)
+    }
+}
+
+impl Widget for DiagnosticsPanel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let title = format!(
+            "Compiler Diagnostics ({})",
+            diagnostics::summary(self.diagnostics)
+        );
+        let popup = Popup::new(&title).width_percent(80).height_percent(70);
+        let inner = popup.render_frame(area, buf);
+
+        let list_area = Rect::new(
+            inner.x + 1,
+            inner.y + 1,
+            inner.width.saturating_sub(2),
+            inner.height.saturating_sub(3),
+        );
+
+        if self.diagnostics.is_empty() {
+            let empty = Line::from(Span::styled(
+                "No diagnostics from the last build",
+                theme::muted_style(),
+            ));
+            buf.set_line(list_area.x, list_area.y, &empty, list_area.width);
+        } else {
+            let (lines, ranges) = self.build_lines();
+            let height = list_area.height as usize;
+
+            // Scroll so that the selected entry is fully visible
+            let (sel_start, sel_end) = ranges.get(self.selected).copied().unwrap_or((0, 0));
+            let scroll = if sel_end > height {
+                (sel_end - height).min(sel_start)
+            } else {
+                0
+            };
+
+            for (row, line) in lines.iter().skip(scroll).take(height).enumerate() {
+                buf.set_line(list_area.x, list_area.y + row as u16, line, list_area.width);
+            }
+
+            if scroll > 0 {
+                buf.set_string(
+                    list_area.x + list_area.width.saturating_sub(1),
+                    list_area.y,
+                    "↑",
+                    theme::muted_style(),
+                );
+            }
+            if scroll + height < lines.len() {
+                buf.set_string(
+                    list_area.x + list_area.width.saturating_sub(1),
+                    list_area.y + list_area.height.saturating_sub(1),
+                    "↓",
+                    theme::muted_style(),
+                );
+            }
+        }
+
+        let hints = KeyboardHints::new(vec![
+            ("↑↓", "navigate"),
+            ("Enter", "open in $EDITOR"),
+            ("Esc", "close"),
+        ]);
+        let hints_y = inner.y + inner.height.saturating_sub(1);
+        let hints_area = Rect::new(inner.x + 1, hints_y, inner.width.saturating_sub(2), 1);
+        hints.render(hints_area, buf);
+    }
+}
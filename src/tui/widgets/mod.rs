@@ -1,12 +1,16 @@
 pub mod autocomplete_input;
 pub mod command_palette;
+pub mod compiler_settings_popup;
 pub mod contract_tree;
+pub mod dashboard;
 pub mod debug_bar;
+pub mod diagnostics_panel;
 pub mod input_field;
 pub mod keyboard_hints;
 pub mod output_area;
 pub mod parameter_popup;
 pub mod popup;
+pub mod rpc_inspector;
 pub mod selectable_list;
 pub mod status_bar;
 
@@ -14,12 +18,16 @@ pub use autocomplete_input::{
     parse_path_for_autocomplete, scan_path_suggestions, AutocompleteInput, PathSuggestion,
 };
 pub use command_palette::CommandPalette;
+pub use compiler_settings_popup::CompilerSettingsPopup;
 pub use contract_tree::ContractTree;
+pub use dashboard::DashboardPanel;
 pub use debug_bar::DebugBarWidget;
+pub use diagnostics_panel::DiagnosticsPanel;
 pub use input_field::InputField;
 pub use keyboard_hints::KeyboardHints;
 pub use output_area::OutputArea;
 pub use parameter_popup::ParameterPopup;
 pub use popup::Popup;
+pub use rpc_inspector::RpcInspector;
 pub use selectable_list::SelectableList;
 pub use status_bar::StatusBarWidget;
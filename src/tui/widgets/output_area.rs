@@ -1,5 +1,6 @@
-use crate::cards::Card;
+use crate::cards::{Card, TransactionStatus};
 use crate::tui::state::{CardState, OutputState, OutputStyle};
+use crate::tui::theme;
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -12,6 +13,8 @@ pub struct OutputArea<'a> {
     output_state: &'a OutputState,
     card_state: &'a CardState,
     focused: bool,
+    /// Animation tick for the spinner of pending transactions
+    tick: usize,
 }
 
 impl<'a> OutputArea<'a> {
@@ -20,6 +23,7 @@ impl<'a> OutputArea<'a> {
             output_state,
             card_state,
             focused: false,
+            tick: 0,
         }
     }
 
@@ -27,6 +31,11 @@ impl<'a> OutputArea<'a> {
         self.focused = focused;
         self
     }
+
+    pub fn tick(mut self, tick: usize) -> Self {
+        self.tick = tick;
+        self
+    }
 }
 
 impl Widget for OutputArea<'_> {
@@ -153,24 +162,37 @@ impl OutputArea<'_> {
             Card::Transaction {
                 hash,
                 status,
+                nonce,
                 function_name,
                 gas_used,
                 contract_name,
                 contract_address,
                 error_message,
+                compiler,
             } => {
                 let mut content = vec![
                     format!("  Hash: {hash:?}"),
-                    format!("  Status: {status}"),
+                    match status {
+                        TransactionStatus::Pending => {
+                            format!("  Status: {} {status}", theme::spinner_frame(self.tick))
+                        }
+                        _ => format!("  Status: {status}"),
+                    },
                     format!("  Function: {function_name}"),
                     format!("  Contract: {contract_name}"),
                 ];
+                if let Some(nonce) = nonce {
+                    content.push(format!("  Nonce: {nonce}"));
+                }
                 if let Some(addr) = contract_address {
                     content.push(format!("  Address: {addr:?}"));
                 }
                 if let Some(gas) = gas_used {
                     content.push(format!("  Gas: {gas}"));
                 }
+                if let Some(compiler) = compiler {
+                    content.push(format!("  Compiler: {compiler}"));
+                }
                 if let Some(error) = error_message {
                     content.push(format!("  Error: {error}"));
                 }
@@ -199,6 +221,7 @@ impl OutputArea<'_> {
                 account,
                 balance,
                 chain_id,
+                endpoint,
                 error,
             } => {
                 let mut content = vec![];
@@ -219,6 +242,8 @@ impl OutputArea<'_> {
                     content.push(format!("  Chain ID: {chain}"));
                 }
 
+                content.push(format!("  RPC: {endpoint}"));
+
                 if let Some(err) = error {
                     content.push(format!("  Error: {err}"));
                 }
@@ -254,6 +279,8 @@ impl OutputArea<'_> {
                         crate::cards::CardAction::ViewReceipt => "View Receipt (r)",
                         crate::cards::CardAction::DebugTrace => "Debug Trace (d)",
                         crate::cards::CardAction::DebugCall => "Debug Call (d)",
+                        crate::cards::CardAction::SpeedUp => "Speed Up (s)",
+                        crate::cards::CardAction::Cancel => "Cancel (x)",
                     };
                     action_spans.push(Span::styled(action_text, text_style));
                 }
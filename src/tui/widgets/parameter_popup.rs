@@ -1,3 +1,4 @@
+use crate::call_options::CALL_OPTION_FIELDS;
 use crate::compile::BytecodeTarget;
 use crate::tui::layout::centered_popup;
 use crate::tui::state::FieldState;
@@ -18,8 +19,13 @@ pub struct ParameterPopup<'a> {
     fields: &'a [FieldState],
     current: usize,
     bytecode_target: Option<BytecodeTarget>,
+    call_options: bool,
+    sign_only: bool,
 }
 
+/// Label of the optional CREATE2 salt field that follows the constructor parameters
+pub const SALT_FIELD_LABEL: &str = "salt (CREATE2, optional)";
+
 impl<'a> ParameterPopup<'a> {
     pub fn new(
         method_name: &'a str,
@@ -33,6 +39,8 @@ impl<'a> ParameterPopup<'a> {
             fields,
             current,
             bytecode_target: None,
+            call_options: false,
+            sign_only: false,
         }
     }
 
@@ -40,6 +48,17 @@ impl<'a> ParameterPopup<'a> {
         self.bytecode_target = target;
         self
     }
+
+    pub fn call_options(mut self, call_options: bool) -> Self {
+        self.call_options = call_options;
+        self
+    }
+
+    /// Show the Ctrl+S hint for submissions that can be signed without sending
+    pub fn sign_only(mut self, sign_only: bool) -> Self {
+        self.sign_only = sign_only;
+        self
+    }
 }
 
 impl Widget for ParameterPopup<'_> {
@@ -79,20 +98,38 @@ impl Widget for ParameterPopup<'_> {
             y += 2;
         }
 
-        for (i, (param, field)) in self.params.iter().zip(self.fields.iter()).enumerate() {
+        // Deploy popups carry one extra field after the parameters, the CREATE2 salt;
+        // view calls carry the call option fields
+        for (i, field) in self.fields.iter().enumerate() {
             if y >= inner.y + inner.height.saturating_sub(2) {
                 break;
             }
 
             let is_focused = i == self.current;
-            let label = if param.name.is_empty() {
-                format!("arg{} ({})", i, param.ty)
-            } else {
-                format!("{} ({})", param.name, param.ty)
+            let (label, placeholder) = match self.params.get(i) {
+                Some(param) if param.name.is_empty() => (
+                    format!("arg{} ({})", i, param.ty),
+                    get_placeholder(&param.ty),
+                ),
+                Some(param) => (
+                    format!("{} ({})", param.name, param.ty),
+                    get_placeholder(&param.ty),
+                ),
+                None if self.call_options => {
+                    let (label, placeholder) = CALL_OPTION_FIELDS
+                        .get(i - self.params.len())
+                        .copied()
+                        .unwrap_or_default();
+                    (label.to_string(), placeholder)
+                }
+                None => (
+                    SALT_FIELD_LABEL.to_string(),
+                    "empty for CREATE, or 0x... / number",
+                ),
             };
 
             let input = InputField::new(&label, &field.value)
-                .placeholder(get_placeholder(&param.ty))
+                .placeholder(placeholder)
                 .error(field.error.as_deref())
                 .focused(is_focused)
                 .cursor_position(field.value.len());
@@ -112,6 +149,10 @@ impl Widget for ParameterPopup<'_> {
             ("Esc", "cancel"),
         ];
 
+        if self.sign_only {
+            hints.insert(3, ("Ctrl+S", "sign only"));
+        }
+
         if self.bytecode_target.is_some() {
             hints.insert(0, ("←/→", "target"));
         }
@@ -0,0 +1,203 @@
+use crate::rpc_log::{pretty_json, RpcExchange};
+use crate::tui::state::RpcInspectorState;
+use crate::tui::theme;
+use crate::tui::widgets::KeyboardHints;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Widget},
+};
+
+/// Index of the selected exchange: the one with the selected id, or the newest when the
+/// panel follows new traffic (or the selected one was filtered out or dropped)
+pub fn selected_index(state: &RpcInspectorState, exchanges: &[RpcExchange]) -> Option<usize> {
+    state
+        .selected
+        .and_then(|id| exchanges.iter().position(|e| e.id == id))
+        .or_else(|| exchanges.len().checked_sub(1))
+}
+
+/// Panel below the main area listing recorded JSON-RPC exchanges, with the request and
+/// response of the selected one
+pub struct RpcInspector<'a> {
+    exchanges: &'a [RpcExchange],
+    state: &'a RpcInspectorState,
+    focused: bool,
+}
+
+impl<'a> RpcInspector<'a> {
+    pub fn new(exchanges: &'a [RpcExchange], state: &'a RpcInspectorState) -> Self {
+        Self {
+            exchanges,
+            state,
+            focused: false,
+        }
+    }
+
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    fn list_line(exchange: &RpcExchange, is_selected: bool) -> Line<'static> {
+        let (status, status_style) = if exchange.is_pending() {
+            ("…", theme::muted_style())
+        } else if exchange.error.is_some() {
+            ("✗", theme::error_style())
+        } else {
+            ("✓", theme::success_style())
+        };
+        let took = match exchange.duration {
+            Some(duration) => format!("{}ms", duration.as_millis()),
+            None => "pending".to_string(),
+        };
+        let method_style = if is_selected {
+            theme::selected_style()
+        } else {
+            Style::default().fg(theme::TEXT)
+        };
+        Line::from(vec![
+            Span::styled(
+                exchange.started_at.format("%H:%M:%S%.3f ").to_string(),
+                theme::muted_style(),
+            ),
+            Span::styled(status, status_style),
+            Span::raw(" "),
+            Span::styled(exchange.method.clone(), method_style),
+            Span::styled(format!(" {took}"), theme::muted_style()),
+        ])
+    }
+
+    fn detail_lines(exchange: &RpcExchange) -> Vec<Line<'static>> {
+        let heading = |text: &'static str| {
+            Line::from(Span::styled(
+                text,
+                Style::default()
+                    .fg(theme::PRIMARY)
+                    .add_modifier(Modifier::BOLD),
+            ))
+        };
+        let mut lines = vec![heading("Request")];
+        lines.extend(
+            pretty_json(&exchange.request)
+                .lines()
+                .map(|l| Line::from(l.to_string())),
+        );
+        if let Some(error) = &exchange.error {
+            lines.push(heading("Error"));
+            lines.push(Line::from(Span::styled(
+                error.clone(),
+                theme::error_style(),
+            )));
+        }
+        lines.push(heading("Response"));
+        match &exchange.response {
+            Some(response) => lines.extend(
+                pretty_json(response)
+                    .lines()
+                    .map(|l| Line::from(l.to_string())),
+            ),
+            None if exchange.is_pending() => {
+                lines.push(Line::from(Span::styled("waiting…", theme::muted_style())))
+            }
+            None => lines.push(Line::from(Span::styled("(none)", theme::muted_style()))),
+        }
+        lines
+    }
+}
+
+impl Widget for RpcInspector<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let border_style = if self.focused {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let mut title = format!(" RPC Inspector ({}) ", self.exchanges.len());
+        if self.state.editing_filter || !self.state.filter.is_empty() {
+            let cursor = if self.state.editing_filter { "_" } else { "" };
+            title.push_str(&format!("filter: {}{cursor} ", self.state.filter));
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style)
+            .title(title);
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.height < 2 {
+            return;
+        }
+
+        let body = Rect::new(inner.x, inner.y, inner.width, inner.height - 1);
+        let hints_area = Rect::new(inner.x + 1, inner.y + inner.height - 1, inner.width, 1);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(body);
+        let (list_area, detail_area) = (columns[0], columns[1]);
+
+        let selected = selected_index(self.state, self.exchanges);
+        match selected {
+            None => {
+                let empty = if self.state.filter.is_empty() {
+                    "No JSON-RPC requests yet"
+                } else {
+                    "No requests match the filter"
+                };
+                buf.set_line(
+                    list_area.x + 1,
+                    list_area.y,
+                    &Line::from(Span::styled(empty, theme::muted_style())),
+                    list_area.width.saturating_sub(1),
+                );
+            }
+            Some(selected) => {
+                // Keep the selected exchange in view, newest at the bottom
+                let height = list_area.height as usize;
+                let scroll = (selected + 1).saturating_sub(height);
+                for (row, (i, exchange)) in self
+                    .exchanges
+                    .iter()
+                    .enumerate()
+                    .skip(scroll)
+                    .take(height)
+                    .enumerate()
+                {
+                    let line = Self::list_line(exchange, i == selected);
+                    buf.set_line(
+                        list_area.x + 1,
+                        list_area.y + row as u16,
+                        &line,
+                        list_area.width.saturating_sub(1),
+                    );
+                }
+
+                let detail = Self::detail_lines(&self.exchanges[selected]);
+                for (row, line) in detail.iter().take(detail_area.height as usize).enumerate() {
+                    buf.set_line(
+                        detail_area.x + 1,
+                        detail_area.y + row as u16,
+                        line,
+                        detail_area.width.saturating_sub(1),
+                    );
+                }
+            }
+        }
+
+        let hints = if self.state.editing_filter {
+            KeyboardHints::new(vec![("Enter", "apply"), ("Esc", "clear filter")])
+        } else {
+            KeyboardHints::new(vec![
+                ("↑↓", "navigate"),
+                ("/", "filter by method"),
+                ("c", "copy as curl"),
+                ("Del", "clear"),
+                ("Tab", "switch"),
+                ("Esc", "close"),
+            ])
+        };
+        hints.render(hints_area, buf);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::tui::state::{AppState, ConnectionStatus, Focus};
+use crate::tui::theme;
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -85,9 +86,27 @@ impl Widget for StatusBarWidget<'_> {
         };
         spans.push(Span::raw(chain_text));
 
+        // Chain head, marked when it is pushed by a subscription
+        match self.state.block_number {
+            Some(number) if self.state.live_blocks => {
+                spans.push(Span::raw(format!("Block: {number} ")));
+                spans.push(Span::styled("(live)", Style::default().fg(Color::Green)));
+                spans.push(Span::raw(" | "));
+            }
+            Some(number) => spans.push(Span::raw(format!("Block: {number} | "))),
+            None => spans.push(Span::raw("Block: N/A | ")),
+        }
+
         // Account (full address)
         if let Some(account) = self.state.account {
-            spans.push(Span::raw(format!("Account: {account:?} | ")));
+            spans.push(Span::raw(format!("Account: {account:?} ")));
+            if let Some(node_account) = self.state.node_account {
+                spans.push(Span::styled(
+                    format!("({}) ", node_account.label()),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            spans.push(Span::raw("| "));
         } else {
             spans.push(Span::raw("Account: N/A | "));
         }
@@ -99,6 +118,33 @@ impl Widget for StatusBarWidget<'_> {
             spans.push(Span::raw("Balance: 0 ETH"));
         }
 
+        // Background compilation progress
+        if let Some(first) = self.state.compiling.first() {
+            let more = match self.state.compiling.len() {
+                1 => String::new(),
+                n => format!(" (+{})", n - 1),
+            };
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled(
+                format!("{} {first}...{more}", theme::spinner_frame(self.state.tick)),
+                Style::default().fg(theme::ACCENT),
+            ));
+        }
+
+        // Transactions waiting for their receipt
+        if self.state.pending_transactions > 0 {
+            let count = self.state.pending_transactions;
+            let plural = if count == 1 { "" } else { "s" };
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled(
+                format!(
+                    "{} {count} pending tx{plural}",
+                    theme::spinner_frame(self.state.tick)
+                ),
+                Style::default().fg(theme::ACCENT),
+            ));
+        }
+
         // Calculate left content length
         let left_content: String = spans.iter().map(|s| s.content.as_ref()).collect();
         let left_len = left_content.len() as u16;
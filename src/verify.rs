@@ -0,0 +1,221 @@
+use std::fmt;
+
+use crate::compile::{BytecodeTarget, RuntimeCode, PVM_MAGIC};
+
+/// Result of comparing on-chain code with the locally compiled runtime bytecode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyOutcome {
+    /// No code at the address (never deployed, selfdestructed or the chain was reset)
+    NoCode,
+    /// Identical, ignoring immutables
+    Match,
+    /// Identical except for the CBOR metadata trailer (e.g. comments or paths changed)
+    MetadataMismatch,
+    /// The code itself differs
+    Mismatch {
+        onchain_len: usize,
+        local_len: usize,
+        /// First differing byte offset, ignoring metadata
+        first_difference: usize,
+    },
+}
+
+impl fmt::Display for VerifyOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyOutcome::NoCode => write!(f, "No code at this address"),
+            VerifyOutcome::Match => write!(f, "Match: on-chain code is the local build"),
+            VerifyOutcome::MetadataMismatch => write!(
+                f,
+                "Metadata-only mismatch: same code, different metadata hash (comments, paths or compiler metadata changed)"
+            ),
+            VerifyOutcome::Mismatch {
+                onchain_len,
+                local_len,
+                first_difference,
+            } => write!(
+                f,
+                "Different code: on-chain {onchain_len} bytes, local {local_len} bytes, first difference at byte {first_difference}"
+            ),
+        }
+    }
+}
+
+/// Guess the target a piece of on-chain code was compiled for
+pub fn detect_target(code: &[u8]) -> BytecodeTarget {
+    if code.starts_with(&PVM_MAGIC) {
+        BytecodeTarget::Pvm
+    } else {
+        BytecodeTarget::Evm
+    }
+}
+
+/// Compare on-chain code with the local runtime bytecode.
+///
+/// For EVM, immutable slots are masked in both copies before comparing, and a
+/// difference confined to the CBOR metadata trailer is reported separately.
+/// PVM blobs are compared byte for byte.
+pub fn compare(onchain: &[u8], local: &RuntimeCode, target: BytecodeTarget) -> VerifyOutcome {
+    if onchain.is_empty() {
+        return VerifyOutcome::NoCode;
+    }
+
+    if target == BytecodeTarget::Pvm {
+        return if onchain == local.bytecode.as_slice() {
+            VerifyOutcome::Match
+        } else {
+            mismatch(onchain, &local.bytecode)
+        };
+    }
+
+    let onchain = mask_immutables(onchain, &local.immutable_references);
+    let local_code = mask_immutables(&local.bytecode, &local.immutable_references);
+    if onchain == local_code {
+        return VerifyOutcome::Match;
+    }
+
+    let onchain = strip_metadata(&onchain);
+    let local_code = strip_metadata(&local_code);
+    if onchain == local_code {
+        VerifyOutcome::MetadataMismatch
+    } else {
+        mismatch(onchain, local_code)
+    }
+}
+
+fn mismatch(onchain: &[u8], local: &[u8]) -> VerifyOutcome {
+    let first_difference = onchain
+        .iter()
+        .zip(local)
+        .position(|(a, b)| a != b)
+        .unwrap_or_else(|| onchain.len().min(local.len()));
+    VerifyOutcome::Mismatch {
+        onchain_len: onchain.len(),
+        local_len: local.len(),
+        first_difference,
+    }
+}
+
+/// Zero the byte ranges the constructor fills in with immutable values
+fn mask_immutables(code: &[u8], references: &[(usize, usize)]) -> Vec<u8> {
+    let mut masked = code.to_vec();
+    for &(start, length) in references {
+        if let Some(range) = masked.get_mut(start..start + length) {
+            range.fill(0);
+        }
+    }
+    masked
+}
+
+/// Remove solc's metadata trailer: a CBOR map followed by its 2-byte big-endian length
+fn strip_metadata(code: &[u8]) -> &[u8] {
+    let Some(len_bytes) = code.len().checked_sub(2).map(|i| &code[i..]) else {
+        return code;
+    };
+    let metadata_len = u16::from_be_bytes([len_bytes[0], len_bytes[1]]) as usize;
+    let Some(start) = code.len().checked_sub(metadata_len + 2) else {
+        return code;
+    };
+    // CBOR map header with 1 to 5 entries (ipfs/bzzr, solc, experimental...)
+    if metadata_len > 0 && (0xa1..=0xa5).contains(&code[start]) {
+        &code[..start]
+    } else {
+        code
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `code` followed by a metadata trailer `{"a": <hash byte>}`
+    fn with_metadata(code: &[u8], hash: u8) -> Vec<u8> {
+        let mut out = code.to_vec();
+        out.extend_from_slice(&[0xa1, 0x61, b'a', hash, 0x00, 0x04]);
+        out
+    }
+
+    fn runtime(bytecode: Vec<u8>, immutable_references: Vec<(usize, usize)>) -> RuntimeCode {
+        RuntimeCode {
+            bytecode,
+            immutable_references,
+        }
+    }
+
+    #[test]
+    fn test_exact_match() {
+        let code = with_metadata(&[0x60, 0x80, 0x60, 0x40], 1);
+        let local = runtime(code.clone(), vec![]);
+        assert_eq!(
+            compare(&code, &local, BytecodeTarget::Evm),
+            VerifyOutcome::Match
+        );
+    }
+
+    #[test]
+    fn test_immutables_are_ignored() {
+        let local = runtime(with_metadata(&[0x7f, 0, 0, 0x56], 1), vec![(1, 2)]);
+        let onchain = with_metadata(&[0x7f, 0xab, 0xcd, 0x56], 1);
+        assert_eq!(
+            compare(&onchain, &local, BytecodeTarget::Evm),
+            VerifyOutcome::Match
+        );
+    }
+
+    #[test]
+    fn test_metadata_only_mismatch() {
+        let local = runtime(with_metadata(&[0x60, 0x80], 1), vec![]);
+        let onchain = with_metadata(&[0x60, 0x80], 2);
+        assert_eq!(
+            compare(&onchain, &local, BytecodeTarget::Evm),
+            VerifyOutcome::MetadataMismatch
+        );
+    }
+
+    #[test]
+    fn test_different_code() {
+        let local = runtime(with_metadata(&[0x60, 0x80, 0x01], 1), vec![]);
+        let onchain = with_metadata(&[0x60, 0x80, 0x02, 0x03], 1);
+        assert_eq!(
+            compare(&onchain, &local, BytecodeTarget::Evm),
+            VerifyOutcome::Mismatch {
+                onchain_len: 4,
+                local_len: 3,
+                first_difference: 2,
+            }
+        );
+        assert_eq!(
+            compare(&[], &local, BytecodeTarget::Evm),
+            VerifyOutcome::NoCode
+        );
+    }
+
+    #[test]
+    fn test_pvm_detection_and_exact_compare() {
+        let blob = vec![0x50, 0x56, 0x4d, 0x00, 0x01, 0x02];
+        assert_eq!(detect_target(&blob), BytecodeTarget::Pvm);
+        assert_eq!(detect_target(&[0x60, 0x80]), BytecodeTarget::Evm);
+
+        let local = runtime(blob.clone(), vec![]);
+        assert_eq!(
+            compare(&blob, &local, BytecodeTarget::Pvm),
+            VerifyOutcome::Match
+        );
+        let mut other = blob.clone();
+        other[5] = 0x03;
+        assert!(matches!(
+            compare(&other, &local, BytecodeTarget::Pvm),
+            VerifyOutcome::Mismatch {
+                first_difference: 5,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_strip_metadata_leaves_plain_code() {
+        assert_eq!(strip_metadata(&[0x60, 0x80]), &[0x60, 0x80]);
+        assert_eq!(strip_metadata(&[0x00]), &[0x00]);
+        assert_eq!(strip_metadata(&with_metadata(&[0x60], 9)), &[0x60]);
+    }
+}